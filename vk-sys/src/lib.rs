@@ -588,6 +588,8 @@ pub const STRUCTURE_TYPE_BUFFER_IMAGE_COPY_2_KHR: u32 = 1000337009;
 pub const STRUCTURE_TYPE_IMAGE_RESOLVE_2_KHR: u32 = 1000337010;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_4444_FORMATS_FEATURES_EXT: u32 = 1000340000;
 pub const STRUCTURE_TYPE_DIRECTFB_SURFACE_CREATE_INFO_EXT: u32 = 1000346000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_IMAGE_VIEW_MIN_LOD_FEATURES_EXT: u32 = 1000391000;
+pub const STRUCTURE_TYPE_IMAGE_VIEW_MIN_LOD_CREATE_INFO_EXT: u32 = 1000391001;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_VARIABLE_POINTER_FEATURES: u32 =
     STRUCTURE_TYPE_PHYSICAL_DEVICE_VARIABLE_POINTERS_FEATURES;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_DRAW_PARAMETER_FEATURES: u32 =
@@ -1595,6 +1597,14 @@ pub const ATTACHMENT_DESCRIPTION_MAY_ALIAS_BIT: u32 = 0x00000001;
 pub type AttachmentDescriptionFlags = Flags;
 pub type SubpassDescriptionFlags = Flags;
 
+pub type ResolveModeFlagBits = u32;
+pub const RESOLVE_MODE_NONE: u32 = 0;
+pub const RESOLVE_MODE_SAMPLE_ZERO_BIT: u32 = 0x00000001;
+pub const RESOLVE_MODE_AVERAGE_BIT: u32 = 0x00000002;
+pub const RESOLVE_MODE_MIN_BIT: u32 = 0x00000004;
+pub const RESOLVE_MODE_MAX_BIT: u32 = 0x00000008;
+pub type ResolveModeFlags = Flags;
+
 pub type AccessFlagBits = u32;
 pub const ACCESS_INDIRECT_COMMAND_READ_BIT: u32 = 0x00000001;
 pub const ACCESS_INDEX_READ_BIT: u32 = 0x00000002;
@@ -2403,6 +2413,13 @@ pub struct ImageViewCreateInfo {
     pub subresourceRange: ImageSubresourceRange,
 }
 
+#[repr(C)]
+pub struct ImageViewMinLodCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub minLod: f32,
+}
+
 #[repr(C)]
 pub struct ShaderModuleCreateInfo {
     pub sType: StructureType,
@@ -3292,6 +3309,16 @@ pub struct PhysicalDeviceSubgroupProperties {
     pub quadOperationsInAllStages: Bool32,
 }
 
+#[repr(C)]
+pub struct PhysicalDeviceDepthStencilResolveProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub supportedDepthResolveModes: ResolveModeFlags,
+    pub supportedStencilResolveModes: ResolveModeFlags,
+    pub independentResolveNone: Bool32,
+    pub independentResolve: Bool32,
+}
+
 #[repr(C)]
 pub struct FormatProperties2KHR {
     pub sType: StructureType,
@@ -3401,6 +3428,13 @@ pub struct PhysicalDeviceShaderFloat16Int8Features {
     pub shaderInt8: Bool32,
 }
 
+#[repr(C)]
+pub struct PhysicalDeviceImageViewMinLodFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub minLod: Bool32,
+}
+
 pub type ViSurfaceCreateFlagsNN = Flags;
 
 #[repr(C)]
@@ -3442,6 +3476,14 @@ pub struct DescriptorUpdateTemplateCreateInfoKHR {
     pub set: u32,
 }
 
+#[repr(C)]
+pub struct ImageFormatListCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub viewFormatCount: u32,
+    pub pViewFormats: *const Format,
+}
+
 #[repr(C)]
 pub struct MemoryDedicatedRequirementsKHR {
     pub sType: StructureType,
@@ -46,8 +46,9 @@ use crate::device::Queue;
 use crate::format::AcceptsPixels;
 use crate::format::ClearValue;
 use crate::format::Format;
-use crate::format::FormatTy;
 use crate::image::ImageAccess;
+use crate::image::ImageAspect;
+use crate::image::ImageDimensions;
 use crate::image::ImageLayout;
 use crate::instance::QueueFamily;
 use crate::pipeline::input_assembly::Index;
@@ -821,6 +822,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         unsafe {
             self.ensure_outside_render_pass()?;
 
+            // Copies every aspect of the source at once.
+            let aspect = ImageAspect {
+                color: source.has_color(),
+                depth: source.has_depth(),
+                stencil: source.has_stencil(),
+                ..ImageAspect::none()
+            };
+
             check_copy_image(
                 self.device(),
                 &source,
@@ -833,17 +842,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 destination_mip_level,
                 extent,
                 layer_count,
+                aspect,
             )?;
 
             let copy = UnsafeCommandBufferBuilderImageCopy {
-                // TODO: Allowing choosing a subset of the image aspects, but note that if color
-                // is included, neither depth nor stencil may.
                 aspect: UnsafeCommandBufferBuilderImageAspect {
-                    color: source.has_color(),
-                    depth: !source.has_color() && source.has_depth() && destination.has_depth(),
-                    stencil: !source.has_color()
-                        && source.has_stencil()
-                        && destination.has_stencil(),
+                    color: aspect.color,
+                    depth: aspect.depth,
+                    stencil: aspect.stencil,
                 },
                 source_mip_level,
                 destination_mip_level,
@@ -925,6 +931,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
             self.ensure_outside_render_pass()?;
 
+            // Blits every aspect of the source at once.
+            let aspect = ImageAspect {
+                color: source.has_color(),
+                depth: source.has_depth(),
+                stencil: source.has_stencil(),
+                ..ImageAspect::none()
+            };
+
             check_blit_image(
                 self.device(),
                 &source,
@@ -939,18 +953,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 destination_mip_level,
                 layer_count,
                 filter,
+                aspect,
             )?;
 
             let blit = UnsafeCommandBufferBuilderImageBlit {
-                // TODO:
-                aspect: if source.has_color() {
-                    UnsafeCommandBufferBuilderImageAspect {
-                        color: true,
-                        depth: false,
-                        stencil: false,
-                    }
-                } else {
-                    unimplemented!()
+                aspect: UnsafeCommandBufferBuilderImageAspect {
+                    color: aspect.color,
+                    depth: aspect.depth,
+                    stencil: aspect.stencil,
                 },
                 source_mip_level,
                 destination_mip_level,
@@ -1148,10 +1158,12 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         unsafe {
             self.ensure_outside_render_pass()?;
 
+            check_copy_layout_requirements(&destination, CheckCopyBufferImageTy::BufferToImage)?;
             check_copy_buffer_image(
                 self.device(),
                 &source,
                 &destination,
+                ImageLayout::TransferDstOptimal,
                 CheckCopyBufferImageTy::BufferToImage,
                 offset,
                 size,
@@ -1190,6 +1202,102 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         }
     }
 
+    /// Adds a command that copies the mipmap levels `0..levels` of every array layer of an image
+    /// from a buffer.
+    ///
+    /// The data of the levels must be laid out in `source` as described by
+    /// `ImageDimensions::packed_mip_layout`. All the levels are copied with a single
+    /// `vkCmdCopyBufferToImage`, using one region per level.
+    ///
+    /// Returns `CheckCopyBufferImageError::InvalidAspect` if the image doesn't have a color
+    /// aspect, since depth and stencil data can't be copied this way, or if its format is
+    /// multi-planar.
+    pub fn copy_buffer_to_image_mip_chain<S, D, Px>(
+        &mut self,
+        source: S,
+        destination: D,
+        levels: u32,
+    ) -> Result<&mut Self, CopyBufferImageError>
+    where
+        S: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
+        D: ImageAccess + Send + Sync + 'static,
+        Format: AcceptsPixels<Px>,
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+
+            if levels == 0 || levels > destination.mipmap_levels() {
+                return Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange.into());
+            }
+
+            // The regions only copy the color aspect.
+            let invalid_aspect = CheckCopyBufferImageError::InvalidAspect {
+                requested: ImageAspect {
+                    color: true,
+                    ..ImageAspect::none()
+                },
+                available: destination.format().aspects(),
+            };
+            if !destination.has_color() {
+                return Err(invalid_aspect.into());
+            }
+
+            check_copy_layout_requirements(&destination, CheckCopyBufferImageTy::BufferToImage)?;
+
+            let dimensions = destination.dimensions();
+            for level in 0..levels {
+                check_copy_buffer_image(
+                    self.device(),
+                    &source,
+                    &destination,
+                    ImageLayout::TransferDstOptimal,
+                    CheckCopyBufferImageTy::BufferToImage,
+                    [0, 0, 0],
+                    dimensions
+                        .mipmap_dimensions(level)
+                        .unwrap()
+                        .width_height_depth(),
+                    0,
+                    dimensions.array_layers(),
+                    level,
+                )?;
+            }
+
+            let format = destination.format();
+            let regions = match mip_chain_regions(dimensions, format, levels) {
+                Some(regions) => regions,
+                // Multi-planar formats have no block size and must be copied plane by plane.
+                None => return Err(invalid_aspect.into()),
+            };
+
+            let required_len = {
+                let last = regions.last().unwrap();
+                let level_len = required_len_for_format::<Px>(
+                    format,
+                    last.image_extent,
+                    last.image_layer_count,
+                );
+                // The offsets are multiples of the block size, so this is exact.
+                last.buffer_offset / format.size().unwrap() * format.rate() as usize + level_len
+            };
+            if required_len > source.len() {
+                return Err(CheckCopyBufferImageError::BufferTooSmall {
+                    required_len,
+                    actual_len: source.len(),
+                }
+                .into());
+            }
+
+            self.inner.copy_buffer_to_image(
+                source,
+                destination,
+                ImageLayout::TransferDstOptimal, // TODO: let choose layout
+                regions.into_iter(),
+            )?;
+            Ok(self)
+        }
+    }
+
     /// Adds a command that copies from an image to a buffer.
     // The data layout of the image on the gpu is opaque, as in, it is non of our business how the gpu stores the image.
     // This does not matter since the act of copying the image into a buffer converts it to linear form.
@@ -1228,10 +1336,12 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         unsafe {
             self.ensure_outside_render_pass()?;
 
+            check_copy_layout_requirements(&source, CheckCopyBufferImageTy::ImageToBuffer)?;
             check_copy_buffer_image(
                 self.device(),
                 &destination,
                 &source,
+                ImageLayout::TransferSrcOptimal,
                 CheckCopyBufferImageTy::ImageToBuffer,
                 offset,
                 size,
@@ -2026,33 +2136,9 @@ where
                 match clear_values_copy.next() {
                     Some((clear_i, clear_value)) => {
                         if atch_desc.load == LoadOp::Clear {
-                            match clear_value {
-                                ClearValue::None => panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: None",
-                                    clear_i, atch_i, atch_desc.format.ty()),
-                                ClearValue::Float(_) => if atch_desc.format.ty() != FormatTy::Float {
-                                   panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Float",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::Int(_) => if atch_desc.format.ty() != FormatTy::Sint {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Int",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::Uint(_) => if atch_desc.format.ty() != FormatTy::Uint {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Uint",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::Depth(_) => if atch_desc.format.ty() != FormatTy::Depth {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Depth",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::Stencil(_) => if atch_desc.format.ty() != FormatTy::Stencil {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Stencil",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::DepthStencil(_) => if atch_desc.format.ty() != FormatTy::DepthStencil {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: DepthStencil",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
+                            if !clear_value.matches_format(atch_desc.format) {
+                                panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: {:?}",
+                                    clear_i, atch_i, atch_desc.format.ty(), clear_value);
                             }
                         } else {
                             if clear_value != ClearValue::None {
@@ -2812,6 +2898,66 @@ err_gen!(CopyBufferError {
     SyncCommandBufferBuilderError,
 });
 
+// Checks the layout requirements of an image that a buffer-image copy accesses.
+//
+// The copy itself always uses the transfer layout that matches `ty`, which the automatic
+// synchronization transitions the image to. The image is then transitioned to its final layout
+// requirement, which can't be `Undefined` or `Preinitialized`. The transition from the
+// `Undefined` layout discards the content of the image, so an image whose initial layout
+// requirement is `Undefined` can't be the source of a copy either.
+fn check_copy_layout_requirements<I>(
+    image: &I,
+    ty: CheckCopyBufferImageTy,
+) -> Result<(), CheckCopyBufferImageError>
+where
+    I: ?Sized + ImageAccess,
+{
+    match image.final_layout_requirement() {
+        ImageLayout::Undefined | ImageLayout::Preinitialized => {
+            return Err(CheckCopyBufferImageError::InvalidImageLayout);
+        }
+        _ => (),
+    }
+
+    if ty == CheckCopyBufferImageTy::ImageToBuffer
+        && image.initial_layout_requirement() == ImageLayout::Undefined
+    {
+        return Err(CheckCopyBufferImageError::InvalidImageLayout);
+    }
+
+    Ok(())
+}
+
+// Builds the regions that copy the mipmap levels `0..levels` of every array layer of the color
+// aspect of an image, with the data of the levels laid out as described by
+// `ImageDimensions::packed_mip_layout`. Returns `None` if the format has no block size.
+fn mip_chain_regions(
+    dimensions: ImageDimensions,
+    format: Format,
+    levels: u32,
+) -> Option<Vec<UnsafeCommandBufferBuilderBufferImageCopy>> {
+    let layout = dimensions.packed_mip_layout(levels, format)?;
+    Some(
+        layout
+            .map(|layout| UnsafeCommandBufferBuilderBufferImageCopy {
+                buffer_offset: layout.buffer_offset as usize,
+                buffer_row_length: 0,
+                buffer_image_height: 0,
+                image_aspect: UnsafeCommandBufferBuilderImageAspect {
+                    color: true,
+                    depth: false,
+                    stencil: false,
+                },
+                image_mip_level: layout.level,
+                image_base_array_layer: 0,
+                image_layer_count: dimensions.array_layers(),
+                image_offset: [0, 0, 0],
+                image_extent: layout.dimensions.width_height_depth(),
+            })
+            .collect(),
+    )
+}
+
 err_gen!(CopyBufferImageError {
     AutoCommandBufferBuilderContextError,
     CheckCopyBufferImageError,
@@ -3014,13 +3160,21 @@ mod tests {
     use crate::buffer::BufferUsage;
     use crate::buffer::CpuAccessibleBuffer;
     use crate::command_buffer::synced::SyncCommandBufferBuilderError;
+    use crate::command_buffer::validity::CheckCopyBufferImageError;
     use crate::command_buffer::AutoCommandBufferBuilder;
     use crate::command_buffer::CommandBufferExecError;
+    use crate::command_buffer::CopyBufferImageError;
     use crate::command_buffer::ExecuteCommandsError;
     use crate::command_buffer::PrimaryCommandBuffer;
     use crate::device::Device;
     use crate::device::DeviceExtensions;
     use crate::device::Features;
+    use crate::format::Format;
+    use crate::image::ImageAccess;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageUsage;
+    use crate::image::StorageImage;
     use crate::instance;
     use crate::sync::GpuFuture;
     use std::sync::Arc;
@@ -3086,6 +3240,81 @@ mod tests {
         assert_eq!(*result, [0_u32, 1, 2, 3, 4]);
     }
 
+    #[test]
+    fn copy_from_undefined_layout() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = StorageImage::with_usage(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 4,
+                height: 4,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                transfer_source: true,
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            Some(queue.family()),
+        )
+        .unwrap();
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            false,
+            (0..4 * 4 * 4).map(|_| 0u8),
+        )
+        .unwrap();
+
+        let mut cbb =
+            AutoCommandBufferBuilder::primary_one_time_submit(device, queue.family()).unwrap();
+
+        // Nothing can be read from an image whose content is undefined.
+        let undefined = unsafe { image.clone().forced_undefined_initial_layout(false) };
+        match cbb.copy_image_to_buffer(undefined, buffer.clone()) {
+            Err(CopyBufferImageError::CheckCopyBufferImageError(
+                CheckCopyBufferImageError::InvalidImageLayout,
+            )) => (),
+            _ => panic!(),
+        }
+
+        // Writing to it is fine, since the whole image is overwritten.
+        let undefined = unsafe { image.forced_undefined_initial_layout(false) };
+        cbb.copy_buffer_to_image(buffer, undefined).unwrap();
+    }
+
+    #[test]
+    fn mip_chain_regions() {
+        let dimensions = ImageDimensions::Dim2d {
+            width: 64,
+            height: 16,
+            array_layers: 2,
+        };
+        let regions = super::mip_chain_regions(dimensions, Format::BC1_RGBUnormBlock, 7).unwrap();
+        assert_eq!(regions.len(), 7);
+
+        for (level, (region, layout)) in regions
+            .iter()
+            .zip(
+                dimensions
+                    .packed_mip_layout(7, Format::BC1_RGBUnormBlock)
+                    .unwrap(),
+            )
+            .enumerate()
+        {
+            assert_eq!(region.image_mip_level, level as u32);
+            assert_eq!(region.buffer_offset as u64, layout.buffer_offset);
+            assert_eq!(region.buffer_offset % 8, 0);
+            assert_eq!(region.image_offset, [0, 0, 0]);
+            assert_eq!(region.image_extent, layout.dimensions.width_height_depth());
+            assert_eq!(region.image_layer_count, 2);
+        }
+        assert_eq!(regions[6].image_extent, [1, 1, 1]);
+    }
+
     #[test]
     fn secondary_nonconcurrent_conflict() {
         let (device, queue) = gfx_dev_and_queue!();
@@ -22,9 +22,12 @@ use crate::descriptor::pipeline_layout::PipelineLayoutAbstract;
 use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::format::ClearValue;
+use crate::format::Format;
 use crate::format::FormatTy;
 use crate::format::PossibleCompressedFormatDesc;
 use crate::image::ImageAccess;
+use crate::image::ImageAspect;
+use crate::image::ImageDimensions;
 use crate::image::ImageLayout;
 use crate::pipeline::depth_stencil::StencilFaceFlags;
 use crate::pipeline::input_assembly::IndexType;
@@ -260,37 +263,8 @@ impl UnsafeCommandBufferBuilder {
         let raw_render_pass = framebuffer.render_pass().inner().internal_object();
         let raw_framebuffer = framebuffer.inner().internal_object();
 
-        let raw_clear_values: SmallVec<[_; 12]> = clear_values
-            .map(|clear_value| match clear_value {
-                ClearValue::None => vk::ClearValue {
-                    color: vk::ClearColorValue { float32: [0.0; 4] },
-                },
-                ClearValue::Float(val) => vk::ClearValue {
-                    color: vk::ClearColorValue { float32: val },
-                },
-                ClearValue::Int(val) => vk::ClearValue {
-                    color: vk::ClearColorValue { int32: val },
-                },
-                ClearValue::Uint(val) => vk::ClearValue {
-                    color: vk::ClearColorValue { uint32: val },
-                },
-                ClearValue::Depth(val) => vk::ClearValue {
-                    depthStencil: vk::ClearDepthStencilValue {
-                        depth: val,
-                        stencil: 0,
-                    },
-                },
-                ClearValue::Stencil(val) => vk::ClearValue {
-                    depthStencil: vk::ClearDepthStencilValue {
-                        depth: 0.0,
-                        stencil: val,
-                    },
-                },
-                ClearValue::DepthStencil((depth, stencil)) => vk::ClearValue {
-                    depthStencil: vk::ClearDepthStencilValue { depth, stencil },
-                },
-            })
-            .collect();
+        let raw_clear_values: SmallVec<[vk::ClearValue; 12]> =
+            clear_values.map(vk::ClearValue::from).collect();
 
         // TODO: allow customizing
         let rect = [
@@ -1634,6 +1608,29 @@ pub struct UnsafeCommandBufferBuilderImageAspect {
 }
 
 impl UnsafeCommandBufferBuilderImageAspect {
+    // Converts `aspect` to the aspect of a copy or blit region of an image of format `format`.
+    fn for_region(format: Format, aspect: ImageAspect) -> Option<Self> {
+        let supported = ImageAspect {
+            color: true,
+            depth: true,
+            stencil: true,
+            ..ImageAspect::none()
+        };
+
+        if aspect == ImageAspect::none()
+            || !aspect.is_subset_of(format.aspects())
+            || !aspect.is_subset_of(supported)
+        {
+            return None;
+        }
+
+        Some(UnsafeCommandBufferBuilderImageAspect {
+            color: aspect.color,
+            depth: aspect.depth,
+            stencil: aspect.stencil,
+        })
+    }
+
     pub(crate) fn to_vk_bits(&self) -> vk::ImageAspectFlagBits {
         let mut out = 0;
         if self.color {
@@ -1686,6 +1683,59 @@ pub struct UnsafeCommandBufferBuilderImageCopy {
     pub extent: [u32; 3],
 }
 
+impl UnsafeCommandBufferBuilderImageCopy {
+    /// Returns a region that copies the whole `mip_level`th mipmap level of the given array
+    /// layers of an image of dimensions `dimensions` to the same subresources of another image.
+    ///
+    /// Returns `None` if `aspect` is empty, contains an aspect that isn't present in `format` or
+    /// a plane aspect, or if `mip_level` or `array_layers` are out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
+    /// use vulkano::format::Format;
+    /// use vulkano::image::ImageAspect;
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 64, height: 32, array_layers: 4 };
+    /// let color = ImageAspect { color: true, .. ImageAspect::none() };
+    ///
+    /// let format = Format::R8G8B8A8Unorm;
+    /// let region = UnsafeCommandBufferBuilderImageCopy::full_region(&dims, format, color, 1, 0..4)
+    ///     .unwrap();
+    /// assert_eq!(region.extent, [32, 16, 1]);
+    /// assert_eq!(region.layer_count, 4);
+    /// ```
+    ///
+    pub fn full_region(
+        dimensions: &ImageDimensions,
+        format: Format,
+        aspect: ImageAspect,
+        mip_level: u32,
+        array_layers: Range<u32>,
+    ) -> Option<UnsafeCommandBufferBuilderImageCopy> {
+        let aspect = UnsafeCommandBufferBuilderImageAspect::for_region(format, aspect)?;
+        let level_dimensions = dimensions.mipmap_dimensions(mip_level)?;
+
+        if array_layers.end <= array_layers.start || array_layers.end > dimensions.array_layers() {
+            return None;
+        }
+
+        Some(UnsafeCommandBufferBuilderImageCopy {
+            aspect,
+            source_mip_level: mip_level,
+            destination_mip_level: mip_level,
+            source_base_array_layer: array_layers.start,
+            destination_base_array_layer: array_layers.start,
+            layer_count: array_layers.end - array_layers.start,
+            source_offset: [0, 0, 0],
+            destination_offset: [0, 0, 0],
+            extent: level_dimensions.width_height_depth(),
+        })
+    }
+}
+
 // TODO: move somewhere else?
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct UnsafeCommandBufferBuilderImageBlit {
@@ -1701,6 +1751,70 @@ pub struct UnsafeCommandBufferBuilderImageBlit {
     pub destination_bottom_right: [i32; 3],
 }
 
+impl UnsafeCommandBufferBuilderImageBlit {
+    /// Returns a region that blits the whole `source_mip_level`th mipmap level of all the array
+    /// layers of an image of dimensions `source` to the whole `destination_mip_level`th mipmap
+    /// level of an image of dimensions `destination`, scaling it if the two have different sizes.
+    ///
+    /// Returns `None` if `aspect` is empty, contains an aspect that isn't present in `format` or
+    /// a plane aspect, if a mipmap level is out of range, or if the images don't have the same
+    /// number of array layers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
+    /// use vulkano::format::Format;
+    /// use vulkano::image::ImageAspect;
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let src = ImageDimensions::Dim2d { width: 64, height: 64, array_layers: 1 };
+    /// let dst = ImageDimensions::Dim2d { width: 16, height: 8, array_layers: 1 };
+    /// let color = ImageAspect { color: true, .. ImageAspect::none() };
+    ///
+    /// let format = Format::R8G8B8A8Unorm;
+    /// let region =
+    ///     UnsafeCommandBufferBuilderImageBlit::full_region(&src, 0, &dst, 0, format, color).unwrap();
+    /// assert_eq!(region.source_bottom_right, [64, 64, 1]);
+    /// assert_eq!(region.destination_bottom_right, [16, 8, 1]);
+    /// ```
+    ///
+    pub fn full_region(
+        source: &ImageDimensions,
+        source_mip_level: u32,
+        destination: &ImageDimensions,
+        destination_mip_level: u32,
+        format: Format,
+        aspect: ImageAspect,
+    ) -> Option<UnsafeCommandBufferBuilderImageBlit> {
+        let aspect = UnsafeCommandBufferBuilderImageAspect::for_region(format, aspect)?;
+        let source_dimensions = source.mipmap_dimensions(source_mip_level)?;
+        let destination_dimensions = destination.mipmap_dimensions(destination_mip_level)?;
+
+        if source.array_layers() != destination.array_layers() {
+            return None;
+        }
+
+        let corner = |dimensions: ImageDimensions| {
+            let [width, height, depth] = dimensions.width_height_depth();
+            [width as i32, height as i32, depth as i32]
+        };
+
+        Some(UnsafeCommandBufferBuilderImageBlit {
+            aspect,
+            source_mip_level,
+            destination_mip_level,
+            source_base_array_layer: 0,
+            destination_base_array_layer: 0,
+            layer_count: source.array_layers(),
+            source_top_left: [0, 0, 0],
+            source_bottom_right: corner(source_dimensions),
+            destination_top_left: [0, 0, 0],
+            destination_bottom_right: corner(destination_dimensions),
+        })
+    }
+}
+
 /// Command that adds a pipeline barrier to a command buffer builder.
 ///
 /// A pipeline barrier is a low-level system-ish command that is often necessary for safety. By
@@ -1998,3 +2112,113 @@ unsafe impl VulkanObject for UnsafeCommandBuffer {
         self.command_buffer
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
+    use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
+    use crate::format::Format;
+    use crate::image::ImageAspect;
+    use crate::image::ImageDimensions;
+
+    #[test]
+    fn full_copy_region() {
+        let dims = ImageDimensions::Dim2d {
+            width: 256,
+            height: 128,
+            array_layers: 6,
+        };
+        let color = ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        };
+        let region = |aspect, mip_level, array_layers| {
+            UnsafeCommandBufferBuilderImageCopy::full_region(
+                &dims,
+                Format::R8G8B8A8Unorm,
+                aspect,
+                mip_level,
+                array_layers,
+            )
+        };
+
+        let full = region(color, 0, 0..6).unwrap();
+        assert_eq!(full.extent, [256, 128, 1]);
+        assert_eq!(full.source_mip_level, 0);
+        assert_eq!(full.layer_count, 6);
+        assert!(full.aspect.color);
+
+        let full = region(color, 2, 2..3).unwrap();
+        assert_eq!(full.extent, [64, 32, 1]);
+        assert_eq!(full.destination_mip_level, 2);
+        assert_eq!(full.source_base_array_layer, 2);
+        assert_eq!(full.layer_count, 1);
+
+        let depth = ImageAspect {
+            depth: true,
+            ..ImageAspect::none()
+        };
+        assert!(region(depth, 0, 0..6).is_none());
+        assert!(region(color, 9, 0..6).is_none());
+        assert!(region(color, 0, 0..7).is_none());
+    }
+
+    #[test]
+    fn full_blit_region() {
+        let src = ImageDimensions::Dim3d {
+            width: 64,
+            height: 64,
+            depth: 16,
+        };
+        let dst = ImageDimensions::Dim3d {
+            width: 32,
+            height: 16,
+            depth: 16,
+        };
+        let depth = ImageAspect {
+            depth: true,
+            ..ImageAspect::none()
+        };
+        let region = |aspect, mip_level| {
+            UnsafeCommandBufferBuilderImageBlit::full_region(
+                &src,
+                mip_level,
+                &dst,
+                mip_level,
+                Format::D32Sfloat,
+                aspect,
+            )
+        };
+
+        let full = region(depth, 0).unwrap();
+        assert_eq!(full.source_bottom_right, [64, 64, 16]);
+        assert_eq!(full.destination_bottom_right, [32, 16, 16]);
+        assert_eq!(full.layer_count, 1);
+        assert!(full.aspect.depth);
+
+        let full = region(depth, 2).unwrap();
+        assert_eq!(full.source_bottom_right, [16, 16, 4]);
+        assert_eq!(full.destination_bottom_right, [8, 4, 4]);
+
+        // Between two mipmap levels of the same image.
+        let full = UnsafeCommandBufferBuilderImageBlit::full_region(
+            &src,
+            1,
+            &src,
+            2,
+            Format::D32Sfloat,
+            depth,
+        )
+        .unwrap();
+        assert_eq!(full.source_mip_level, 1);
+        assert_eq!(full.source_bottom_right, [32, 32, 8]);
+        assert_eq!(full.destination_mip_level, 2);
+        assert_eq!(full.destination_bottom_right, [16, 16, 4]);
+
+        let color = ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        };
+        assert!(region(color, 0).is_none());
+    }
+}
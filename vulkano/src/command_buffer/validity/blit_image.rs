@@ -11,8 +11,9 @@ use std::error;
 use std::fmt;
 
 use crate::device::Device;
-use crate::format::FormatTy;
+use crate::format::Format;
 use crate::image::ImageAccess;
+use crate::image::ImageAspect;
 use crate::image::ImageDimensions;
 use crate::sampler::Filter;
 use crate::VulkanObject;
@@ -21,6 +22,8 @@ use crate::VulkanObject;
 ///
 /// Note that this doesn't check whether `layer_count` is equal to 0. TODO: change that?
 ///
+/// `aspect` must be present in the formats of both the source and the destination.
+///
 /// # Panic
 ///
 /// - Panics if the source or the destination was not created with `device`.
@@ -39,6 +42,7 @@ pub fn check_blit_image<S, D>(
     destination_mip_level: u32,
     layer_count: u32,
     filter: Filter,
+    aspect: ImageAspect,
 ) -> Result<(), CheckBlitImageError>
 where
     S: ?Sized + ImageAccess,
@@ -76,10 +80,16 @@ where
         return Err(CheckBlitImageError::UnexpectedMultisampled);
     }
 
-    let source_format_ty = source.format().ty();
-    let destination_format_ty = destination.format().ty();
+    for available in [source.format().aspects(), destination.format().aspects()].iter() {
+        if aspect == ImageAspect::none() || !aspect.is_subset_of(*available) {
+            return Err(CheckBlitImageError::InvalidAspect {
+                requested: aspect,
+                available: *available,
+            });
+        }
+    }
 
-    if source_format_ty.is_depth_and_or_stencil() {
+    if source.format().ty().is_depth_and_or_stencil() {
         if source.format() != destination.format() {
             return Err(CheckBlitImageError::DepthStencilFormatMismatch);
         }
@@ -89,14 +99,10 @@ where
         }
     }
 
-    let types_should_be_same = source_format_ty == FormatTy::Uint
-        || destination_format_ty == FormatTy::Uint
-        || source_format_ty == FormatTy::Sint
-        || destination_format_ty == FormatTy::Sint;
-    if types_should_be_same && (source_format_ty != destination_format_ty) {
-        return Err(CheckBlitImageError::IncompatibleFormatsTypes {
-            source_format_ty: source.format().ty(),
-            destination_format_ty: destination.format().ty(),
+    if !source.format().blit_compatible_with(destination.format()) {
+        return Err(CheckBlitImageError::BlitFormatIncompatible {
+            source_format: source.format(),
+            destination_format: destination.format(),
         });
     }
 
@@ -233,10 +239,16 @@ pub enum CheckBlitImageError {
     DepthStencilNearestMandatory,
     /// The format of the source and destination must be equal when blitting depth/stencil images.
     DepthStencilFormatMismatch,
-    /// The types of the source format and the destination format aren't compatible.
-    IncompatibleFormatsTypes {
-        source_format_ty: FormatTy,
-        destination_format_ty: FormatTy,
+    /// The aspect of the region is empty or is not present in the source or the destination
+    /// format.
+    InvalidAspect {
+        requested: ImageAspect,
+        available: ImageAspect,
+    },
+    /// The source format can't be blitted to the destination format.
+    BlitFormatIncompatible {
+        source_format: Format,
+        destination_format: Format,
     },
     /// Blitting between multisampled images is forbidden.
     UnexpectedMultisampled,
@@ -276,8 +288,12 @@ impl fmt::Display for CheckBlitImageError {
                     "the format of the source and destination must be equal when blitting \
                  depth/stencil images"
                 }
-                CheckBlitImageError::IncompatibleFormatsTypes { .. } => {
-                    "the types of the source format and the destination format aren't compatible"
+                CheckBlitImageError::InvalidAspect { .. } => {
+                    "the aspect of the region is empty or is not present in the source or the \
+                 destination format"
+                }
+                CheckBlitImageError::BlitFormatIncompatible { .. } => {
+                    "the source format can't be blitted to the destination format"
                 }
                 CheckBlitImageError::UnexpectedMultisampled => {
                     "blitting between multisampled images is forbidden"
@@ -297,3 +313,67 @@ impl fmt::Display for CheckBlitImageError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::command_buffer::validity::blit_image::check_blit_image;
+    use crate::command_buffer::validity::blit_image::CheckBlitImageError;
+    use crate::format::Format;
+    use crate::image::AttachmentImage;
+    use crate::image::ImageAspect;
+    use crate::image::ImageUsage;
+    use crate::sampler::Filter;
+
+    #[test]
+    fn depth_aspect() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            transfer_source: true,
+            transfer_destination: true,
+            ..ImageUsage::none()
+        };
+        let source =
+            AttachmentImage::with_usage(device.clone(), [32, 32], Format::D16Unorm, usage).unwrap();
+        let destination =
+            AttachmentImage::with_usage(device.clone(), [32, 32], Format::D16Unorm, usage).unwrap();
+
+        let check = |aspect| {
+            check_blit_image(
+                &device,
+                &source,
+                [0, 0, 0],
+                [32, 32, 1],
+                0,
+                0,
+                &destination,
+                [0, 0, 0],
+                [16, 16, 1],
+                0,
+                0,
+                1,
+                Filter::Nearest,
+                aspect,
+            )
+        };
+
+        match check(ImageAspect {
+            depth: true,
+            ..ImageAspect::none()
+        }) {
+            Ok(()) => (),
+            Err(CheckBlitImageError::DestinationFormatNotSupported) => return, // blit_dst is optional
+            Err(err) => panic!("{:?}", err),
+        }
+
+        match check(ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        }) {
+            Err(CheckBlitImageError::InvalidAspect { available, .. }) => {
+                assert_eq!(available, Format::D16Unorm.aspects())
+            }
+            _ => panic!(),
+        }
+    }
+}
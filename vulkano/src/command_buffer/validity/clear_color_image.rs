@@ -12,6 +12,7 @@ use std::fmt;
 
 use crate::device::Device;
 use crate::image::ImageAccess;
+use crate::image::ImageAspect;
 use crate::VulkanObject;
 
 /// Checks whether a clear color image command is valid.
@@ -40,6 +41,19 @@ where
         return Err(CheckClearColorImageError::MissingTransferUsage);
     }
 
+    let requested = ImageAspect {
+        color: true,
+        ..ImageAspect::none()
+    };
+    let available = image.format().aspects();
+
+    if !requested.is_subset_of(available) {
+        return Err(CheckClearColorImageError::InvalidAspect {
+            requested,
+            available,
+        });
+    }
+
     if first_layer + num_layers > image.dimensions().array_layers() {
         return Err(CheckClearColorImageError::OutOfRange);
     }
@@ -58,6 +72,11 @@ pub enum CheckClearColorImageError {
     MissingTransferUsage,
     /// The array layers and mipmap levels are out of range.
     OutOfRange,
+    /// The format of the image doesn't have a color aspect.
+    InvalidAspect {
+        requested: ImageAspect,
+        available: ImageAspect,
+    },
 }
 
 impl error::Error for CheckClearColorImageError {}
@@ -75,6 +94,9 @@ impl fmt::Display for CheckClearColorImageError {
                 CheckClearColorImageError::OutOfRange => {
                     "the array layers and mipmap levels are out of range"
                 }
+                CheckClearColorImageError::InvalidAspect { .. } => {
+                    "the format of the image doesn't have a color aspect"
+                }
             }
         )
     }
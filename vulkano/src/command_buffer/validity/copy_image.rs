@@ -11,9 +11,10 @@ use std::error;
 use std::fmt;
 
 use crate::device::Device;
+use crate::format::Format;
 use crate::format::FormatTy;
-use crate::format::PossibleCompressedFormatDesc;
 use crate::image::ImageAccess;
+use crate::image::ImageAspect;
 use crate::image::ImageDimensions;
 use crate::VulkanObject;
 
@@ -21,6 +22,9 @@ use crate::VulkanObject;
 ///
 /// Note that this doesn't check whether `layer_count` is equal to 0. TODO: change that?
 ///
+/// `aspect` must be present in the formats of both the source and the destination. Depth and
+/// stencil can be copied in the same region.
+///
 /// # Panic
 ///
 /// - Panics if the source or the destination was not created with `device`.
@@ -37,6 +41,7 @@ pub fn check_copy_image<S, D>(
     destination_mip_level: u32,
     extent: [u32; 3],
     layer_count: u32,
+    aspect: ImageAspect,
 ) -> Result<(), CheckCopyImageError>
 where
     S: ?Sized + ImageAccess,
@@ -66,6 +71,15 @@ where
         return Err(CheckCopyImageError::SampleCountMismatch);
     }
 
+    for available in [source.format().aspects(), destination.format().aspects()].iter() {
+        if aspect == ImageAspect::none() || !aspect.is_subset_of(*available) {
+            return Err(CheckCopyImageError::InvalidAspect {
+                requested: aspect,
+                available: *available,
+            });
+        }
+    }
+
     let source_format_ty = source.format().ty();
     let destination_format_ty = destination.format().ty();
 
@@ -75,18 +89,17 @@ where
         }
     }
 
-    // TODO: The correct check here is that the uncompressed element size of the source is
-    // equal to the compressed element size of the destination.  However, format doesn't
-    // currently expose this information, so to be safe, we simply disallow compressed formats.
-    if source.format().is_compressed()
-        || destination.format().is_compressed()
-        || (source.format().size() != destination.format().size())
-    {
-        return Err(CheckCopyImageError::SizeIncompatibleFormatsTypes {
-            source_format_ty: source.format().ty(),
-            destination_format_ty: destination.format().ty(),
-        });
-    }
+    // The extent is expressed in texels of the source. When the formats have different block
+    // dimensions, it covers a different number of texels in the destination.
+    let destination_extent = match source.format().copy_compatible_with(destination.format()) {
+        Some(ratio) => ratio.convert_extent(extent),
+        None => {
+            return Err(CheckCopyImageError::SizeIncompatibleFormatsTypes {
+                source_format_ty: source.format().ty(),
+                destination_format_ty: destination.format().ty(),
+            })
+        }
+    };
 
     let source_dimensions = match source.dimensions().mipmap_dimensions(source_mip_level) {
         Some(d) => d,
@@ -122,19 +135,19 @@ where
     }
 
     if destination_offset[0] < 0
-        || destination_offset[0] as u32 + extent[0] > destination_dimensions.width()
+        || destination_offset[0] as u32 + destination_extent[0] > destination_dimensions.width()
     {
         return Err(CheckCopyImageError::DestinationCoordinatesOutOfRange);
     }
 
     if destination_offset[1] < 0
-        || destination_offset[1] as u32 + extent[1] > destination_dimensions.height()
+        || destination_offset[1] as u32 + destination_extent[1] > destination_dimensions.height()
     {
         return Err(CheckCopyImageError::DestinationCoordinatesOutOfRange);
     }
 
     if destination_offset[2] < 0
-        || destination_offset[2] as u32 + extent[2] > destination_dimensions.depth()
+        || destination_offset[2] as u32 + destination_extent[2] > destination_dimensions.depth()
     {
         return Err(CheckCopyImageError::DestinationCoordinatesOutOfRange);
     }
@@ -158,15 +171,15 @@ where
 
     match destination_dimensions {
         ImageDimensions::Dim1d { .. } => {
-            if destination_offset[1] != 0 || extent[1] != 1 {
+            if destination_offset[1] != 0 || destination_extent[1] != 1 {
                 return Err(CheckCopyImageError::IncompatibleRangeForImageType);
             }
-            if destination_offset[2] != 0 || extent[2] != 1 {
+            if destination_offset[2] != 0 || destination_extent[2] != 1 {
                 return Err(CheckCopyImageError::IncompatibleRangeForImageType);
             }
         }
         ImageDimensions::Dim2d { .. } => {
-            if destination_offset[2] != 0 || extent[2] != 1 {
+            if destination_offset[2] != 0 || destination_extent[2] != 1 {
                 return Err(CheckCopyImageError::IncompatibleRangeForImageType);
             }
         }
@@ -176,6 +189,41 @@ where
     Ok(())
 }
 
+/// Checks that the extent of a source region and the extent of a destination region describe
+/// the same texel blocks, as required by `vkCmdCopyImage`.
+///
+/// `check_copy_image` takes a single extent, expressed in texels of the source. This function is
+/// meant for regions that are described with a separate extent for each side, for example when
+/// they come from another API. For formats with the same block dimensions, the extents must be
+/// equal. Otherwise, the source extent is converted with `BlockRatio::convert_extent`, so that
+/// copying a 16x16 region of a BC1 image covers a 4x4 region of an uncompressed 64-bit image.
+pub fn check_copy_image_extents(
+    source_format: Format,
+    source_extent: [u32; 3],
+    destination_format: Format,
+    destination_extent: [u32; 3],
+) -> Result<(), CheckCopyImageError> {
+    let ratio = match source_format.copy_compatible_with(destination_format) {
+        Some(ratio) => ratio,
+        None => {
+            return Err(CheckCopyImageError::SizeIncompatibleFormatsTypes {
+                source_format_ty: source_format.ty(),
+                destination_format_ty: destination_format.ty(),
+            })
+        }
+    };
+
+    let expected = ratio.convert_extent(source_extent);
+    if expected != destination_extent {
+        return Err(CheckCopyImageError::ExtentMismatch {
+            expected,
+            obtained: destination_extent,
+        });
+    }
+
+    Ok(())
+}
+
 /// Error that can happen from `check_copy_image`.
 #[derive(Debug, Copy, Clone)]
 pub enum CheckCopyImageError {
@@ -198,6 +246,20 @@ pub enum CheckCopyImageError {
     DestinationCoordinatesOutOfRange,
     /// The offsets or extent are incompatible with the image type.
     IncompatibleRangeForImageType,
+    /// The aspect of the region is empty, or is not present in the source or the destination
+    /// format.
+    InvalidAspect {
+        requested: ImageAspect,
+        available: ImageAspect,
+    },
+    /// The extent of the destination region doesn't cover the same texel blocks as the extent of
+    /// the source region.
+    ExtentMismatch {
+        /// The destination extent that matches the source extent.
+        expected: [u32; 3],
+        /// The destination extent that was given.
+        obtained: [u32; 3],
+    },
 }
 
 impl error::Error for CheckCopyImageError {}
@@ -236,7 +298,199 @@ impl fmt::Display for CheckCopyImageError {
                 CheckCopyImageError::IncompatibleRangeForImageType => {
                     "the offsets or extent are incompatible with the image type"
                 }
+                CheckCopyImageError::InvalidAspect { .. } => {
+                    "the aspect of the region is empty or is not present in the source or the \
+                 destination format"
+                }
+                CheckCopyImageError::ExtentMismatch { .. } => {
+                    "the extent of the destination region doesn't match the extent of the source \
+                 region"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command_buffer::validity::copy_image::check_copy_image;
+    use crate::command_buffer::validity::copy_image::check_copy_image_extents;
+    use crate::command_buffer::validity::copy_image::CheckCopyImageError;
+    use crate::format::Format;
+    use crate::image::AttachmentImage;
+    use crate::image::ImageAspect;
+    use crate::image::ImageUsage;
+
+    #[test]
+    fn depth_stencil_aspects() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            transfer_source: true,
+            transfer_destination: true,
+            ..ImageUsage::none()
+        };
+        let source = match AttachmentImage::with_usage(
+            device.clone(),
+            [32, 32],
+            Format::D24Unorm_S8Uint,
+            usage,
+        ) {
+            Ok(img) => img,
+            Err(_) => return, // D24S8 is not supported everywhere
+        };
+        let destination =
+            AttachmentImage::with_usage(device.clone(), [32, 32], Format::D24Unorm_S8Uint, usage)
+                .unwrap();
+
+        let check = |aspect| {
+            check_copy_image(
+                &device,
+                &source,
+                [0, 0, 0],
+                0,
+                0,
+                &destination,
+                [0, 0, 0],
+                0,
+                0,
+                [32, 32, 1],
+                1,
+                aspect,
+            )
+        };
+
+        check(ImageAspect {
+            depth: true,
+            ..ImageAspect::none()
+        })
+        .unwrap();
+        check(ImageAspect {
+            stencil: true,
+            ..ImageAspect::none()
+        })
+        .unwrap();
+
+        check(ImageAspect {
+            depth: true,
+            stencil: true,
+            ..ImageAspect::none()
+        })
+        .unwrap();
+
+        match check(ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        }) {
+            Err(CheckCopyImageError::InvalidAspect { available, .. }) => {
+                assert_eq!(available, Format::D24Unorm_S8Uint.aspects())
+            }
+            _ => panic!(),
+        }
+
+        match check(ImageAspect::none()) {
+            Err(CheckCopyImageError::InvalidAspect { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn matching_extents() {
+        check_copy_image_extents(
+            Format::R8G8B8A8Unorm,
+            [32, 16, 1],
+            Format::R8G8B8A8Uint,
+            [32, 16, 1],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn mismatched_extents() {
+        match check_copy_image_extents(
+            Format::R8G8B8A8Unorm,
+            [32, 16, 1],
+            Format::R8G8B8A8Unorm,
+            [16, 16, 1],
+        ) {
+            Err(CheckCopyImageError::ExtentMismatch { expected, obtained }) => {
+                assert_eq!(expected, [32, 16, 1]);
+                assert_eq!(obtained, [16, 16, 1]);
             }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn compressed_to_uncompressed_extents() {
+        check_copy_image_extents(
+            Format::BC1_RGBUnormBlock,
+            [16, 16, 1],
+            Format::R16G16B16A16Uint,
+            [4, 4, 1],
         )
+        .unwrap();
+        check_copy_image_extents(
+            Format::R16G16B16A16Uint,
+            [4, 4, 1],
+            Format::BC1_RGBUnormBlock,
+            [16, 16, 1],
+        )
+        .unwrap();
+
+        match check_copy_image_extents(
+            Format::BC1_RGBUnormBlock,
+            [16, 16, 1],
+            Format::R16G16B16A16Uint,
+            [16, 16, 1],
+        ) {
+            Err(CheckCopyImageError::ExtentMismatch { expected, .. }) => {
+                assert_eq!(expected, [4, 4, 1])
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn color_aspect_of_depth_image() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            transfer_source: true,
+            transfer_destination: true,
+            ..ImageUsage::none()
+        };
+        let source =
+            AttachmentImage::with_usage(device.clone(), [32, 32], Format::D16Unorm, usage).unwrap();
+        let destination =
+            AttachmentImage::with_usage(device.clone(), [32, 32], Format::D16Unorm, usage).unwrap();
+
+        let requested = ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        };
+        match check_copy_image(
+            &device,
+            &source,
+            [0, 0, 0],
+            0,
+            0,
+            &destination,
+            [0, 0, 0],
+            0,
+            0,
+            [32, 32, 1],
+            1,
+            requested,
+        ) {
+            Err(CheckCopyImageError::InvalidAspect {
+                requested: r,
+                available,
+            }) => {
+                assert_eq!(r, requested);
+                assert!(available.depth && !available.color);
+            }
+            _ => panic!(),
+        }
     }
 }
@@ -9,14 +9,20 @@
 
 use std::error;
 use std::fmt;
+use std::mem;
 
 use crate::buffer::TypedBufferAccess;
+use crate::command_buffer::sys::UnsafeCommandBufferBuilderBufferImageCopy;
+use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageAspect;
 use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::format::AcceptsPixels;
 use crate::format::Format;
 use crate::format::IncompatiblePixelsType;
 use crate::image::ImageAccess;
+use crate::image::ImageAspect;
+use crate::image::ImageDimensions;
+use crate::image::ImageLayout;
 use crate::VulkanObject;
 
 /// Type of operation to check.
@@ -29,6 +35,14 @@ pub enum CheckCopyBufferImageTy {
 /// Checks whether a copy buffer-image command is valid. Can check both buffer-to-image copies and
 /// image-to-buffer copies.
 ///
+/// `image_layout` is the layout the image will be in when the copy is performed. It must be
+/// `General`, or `TransferDstOptimal` for buffer-to-image copies and `TransferSrcOptimal` for
+/// image-to-buffer copies.
+///
+/// This is a shortcut for `check_buffer_image_copy_region` with a region that starts at the
+/// beginning of the buffer, whose data is tightly packed, and that covers the color aspect of the
+/// image, or its depth or stencil aspect if it has no color.
+///
 /// # Panic
 ///
 /// - Panics if the buffer and image were not created with `device`.
@@ -37,6 +51,7 @@ pub fn check_copy_buffer_image<B, I, P>(
     device: &Device,
     buffer: &B,
     image: &I,
+    image_layout: ImageLayout,
     ty: CheckCopyBufferImageTy,
     image_offset: [u32; 3],
     image_size: [u32; 3],
@@ -48,6 +63,57 @@ where
     I: ?Sized + ImageAccess,
     B: ?Sized + TypedBufferAccess<Content = [P]>,
     Format: AcceptsPixels<P>, // TODO: use a trait on the image itself instead
+{
+    let aspects = image.format().aspects();
+    let region = UnsafeCommandBufferBuilderBufferImageCopy {
+        buffer_offset: 0,
+        buffer_row_length: 0,
+        buffer_image_height: 0,
+        image_aspect: UnsafeCommandBufferBuilderImageAspect {
+            color: aspects.color,
+            depth: !aspects.color && aspects.depth,
+            stencil: !aspects.color && !aspects.depth && aspects.stencil,
+        },
+        image_mip_level: image_mipmap,
+        image_base_array_layer: image_first_layer,
+        image_layer_count: image_num_layers,
+        image_offset: [
+            image_offset[0] as i32,
+            image_offset[1] as i32,
+            image_offset[2] as i32,
+        ],
+        image_extent: image_size,
+    };
+
+    check_buffer_image_copy_region(device, buffer, image, image_layout, ty, &region)
+}
+
+/// Checks whether a fully-specified region of a copy buffer-image command is valid. Can check
+/// both buffer-to-image copies and image-to-buffer copies.
+///
+/// `image_layout` has the same meaning as in `check_copy_buffer_image`. When a command copies
+/// several regions, this function must be called once for each of them.
+///
+/// `region.buffer_offset` is in bytes, while `region.buffer_row_length` and
+/// `region.buffer_image_height` are in texels and can be 0 to indicate that the data is tightly
+/// packed.
+///
+/// # Panic
+///
+/// - Panics if the buffer and image were not created with `device`.
+///
+pub fn check_buffer_image_copy_region<B, I, P>(
+    device: &Device,
+    buffer: &B,
+    image: &I,
+    image_layout: ImageLayout,
+    ty: CheckCopyBufferImageTy,
+    region: &UnsafeCommandBufferBuilderBufferImageCopy,
+) -> Result<(), CheckCopyBufferImageError>
+where
+    I: ?Sized + ImageAccess,
+    B: ?Sized + TypedBufferAccess<Content = [P]>,
+    Format: AcceptsPixels<P>,
 {
     let buffer_inner = buffer.inner();
     let image_inner = image.inner();
@@ -80,35 +146,106 @@ where
         }
     }
 
+    match (ty, image_layout) {
+        (_, ImageLayout::General) => (),
+        (CheckCopyBufferImageTy::BufferToImage, ImageLayout::TransferDstOptimal) => (),
+        (CheckCopyBufferImageTy::ImageToBuffer, ImageLayout::TransferSrcOptimal) => (),
+        _ => return Err(CheckCopyBufferImageError::InvalidImageLayout),
+    }
+
     if image.samples() != 1 {
         return Err(CheckCopyBufferImageError::UnexpectedMultisampled);
     }
 
-    let image_dimensions = match image.dimensions().mipmap_dimensions(image_mipmap) {
+    let format = image.format();
+
+    // The region must copy exactly one aspect, and the image must have it.
+    {
+        let requested = ImageAspect {
+            color: region.image_aspect.color,
+            depth: region.image_aspect.depth,
+            stencil: region.image_aspect.stencil,
+            ..ImageAspect::none()
+        };
+        let available = format.aspects();
+        let count = requested.color as u32 + requested.depth as u32 + requested.stencil as u32;
+        if count != 1 || !requested.is_subset_of(available) {
+            return Err(CheckCopyBufferImageError::InvalidAspect {
+                requested,
+                available,
+            });
+        }
+    }
+
+    let block_size = format.size();
+    if region.buffer_offset % 4 != 0
+        || block_size.map_or(false, |size| region.buffer_offset % size != 0)
+    {
+        return Err(CheckCopyBufferImageError::UnalignedBufferOffset);
+    }
+
+    let image_offset = region.image_offset;
+    let image_size = region.image_extent;
+    if image_offset.iter().any(|&o| o < 0) {
+        return Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange);
+    }
+    let image_offset = [
+        image_offset[0] as u32,
+        image_offset[1] as u32,
+        image_offset[2] as u32,
+    ];
+
+    let (block_width, block_height) = format.block_dimensions();
+    if region.buffer_row_length != 0
+        && (region.buffer_row_length < image_size[0] || region.buffer_row_length % block_width != 0)
+    {
+        return Err(CheckCopyBufferImageError::InvalidBufferRowLength);
+    }
+    if region.buffer_image_height != 0
+        && (region.buffer_image_height < image_size[1]
+            || region.buffer_image_height % block_height != 0)
+    {
+        return Err(CheckCopyBufferImageError::InvalidBufferImageHeight);
+    }
+
+    let image_dimensions = match image.dimensions().mipmap_dimensions(region.image_mip_level) {
         Some(d) => d,
         None => return Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange),
     };
 
-    if image_first_layer + image_num_layers > image_dimensions.array_layers() {
-        return Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange);
-    }
+    // The values of the region come from the user, so the additions can overflow.
+    let in_range = |offset: u32, size: u32, max: u32| match offset.checked_add(size) {
+        Some(end) => end <= max,
+        None => false,
+    };
 
-    if image_offset[0] + image_size[0] > image_dimensions.width() {
+    if region.image_layer_count == 0
+        || !in_range(
+            region.image_base_array_layer,
+            region.image_layer_count,
+            image_dimensions.array_layers(),
+        )
+    {
         return Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange);
     }
 
-    if image_offset[1] + image_size[1] > image_dimensions.height() {
+    if image_size.iter().any(|&s| s == 0) {
         return Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange);
     }
 
-    if image_offset[2] + image_size[2] > image_dimensions.depth() {
+    if !in_range(image_offset[0], image_size[0], image_dimensions.width())
+        || !in_range(image_offset[1], image_size[1], image_dimensions.height())
+        || !in_range(image_offset[2], image_size[2], image_dimensions.depth())
+    {
         return Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange);
     }
 
-    image.format().ensure_accepts()?;
+    check_block_alignment(format, image_dimensions, image_offset, image_size)?;
+
+    format.ensure_accepts()?;
 
     {
-        let required_len = required_len_for_format(image.format(), image_size, image_num_layers);
+        let required_len = required_len_for_region(format, region);
         if required_len > buffer.len() {
             return Err(CheckCopyBufferImageError::BufferTooSmall {
                 required_len,
@@ -122,9 +259,89 @@ where
     Ok(())
 }
 
+/// Checks that a region of an image of dimensions `image_dimensions` follows the grid of texel
+/// blocks of `format`.
+///
+/// The offset must be a multiple of the block dimensions, and so must the size, unless the region
+/// touches the right or bottom edge of the image, in which case a partial block is allowed.
+fn check_block_alignment(
+    format: Format,
+    image_dimensions: ImageDimensions,
+    image_offset: [u32; 3],
+    image_size: [u32; 3],
+) -> Result<(), CheckCopyBufferImageError> {
+    let (block_width, block_height) = format.block_dimensions();
+
+    let check = |offset: u32, size: u32, block: u32, image: u32| {
+        offset % block == 0 && (size % block == 0 || offset + size == image)
+    };
+
+    if !check(
+        image_offset[0],
+        image_size[0],
+        block_width,
+        image_dimensions.width(),
+    ) || !check(
+        image_offset[1],
+        image_size[1],
+        block_height,
+        image_dimensions.height(),
+    ) {
+        return Err(CheckCopyBufferImageError::UnalignedBlockRegion);
+    }
+
+    Ok(())
+}
+
+/// Computes the minimum required len in elements of a buffer that is the source or destination
+/// of `region`, taking its offset, row length and image height into account.
+fn required_len_for_region<P>(
+    format: Format,
+    region: &UnsafeCommandBufferBuilderBufferImageCopy,
+) -> usize
+where
+    Format: AcceptsPixels<P>,
+{
+    let (block_width, block_height) = format.block_dimensions();
+    let extent = region.image_extent;
+
+    let row_length = if region.buffer_row_length == 0 {
+        extent[0]
+    } else {
+        region.buffer_row_length
+    };
+    let image_height = if region.buffer_image_height == 0 {
+        extent[1]
+    } else {
+        region.buffer_image_height
+    };
+
+    let row_blocks = ((row_length + block_width - 1) / block_width) as usize;
+    let image_rows = ((image_height + block_height - 1) / block_height) as usize;
+    let extent_columns = ((extent[0] + block_width - 1) / block_width) as usize;
+    let extent_rows = ((extent[1] + block_height - 1) / block_height) as usize;
+    let slices = extent[2] as usize * region.image_layer_count as usize;
+
+    // The last slice only needs to reach the end of its last row, and the last row only needs to
+    // reach the end of the region.
+    let num_blocks =
+        (slices - 1) * image_rows * row_blocks + (extent_rows - 1) * row_blocks + extent_columns;
+    let offset_len = match format.size() {
+        Some(size) => region.buffer_offset / size * format.rate() as usize,
+        // Without a block size, count the elements that are entirely before the offset.
+        None => region.buffer_offset / mem::size_of::<P>(),
+    };
+
+    offset_len + num_blocks * format.rate() as usize
+}
+
 /// Computes the minimum required len in elements for buffer with image data in specified
 /// format of specified size.
-fn required_len_for_format<P>(format: Format, image_size: [u32; 3], image_num_layers: u32) -> usize
+pub(crate) fn required_len_for_format<P>(
+    format: Format,
+    image_size: [u32; 3],
+    image_num_layers: u32,
+) -> usize
 where
     Format: AcceptsPixels<P>,
 {
@@ -140,8 +357,291 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use crate::command_buffer::sys::UnsafeCommandBufferBuilderBufferImageCopy;
+    use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageAspect;
+    use crate::command_buffer::validity::copy_image_buffer::check_block_alignment;
+    use crate::command_buffer::validity::copy_image_buffer::check_buffer_image_copy_region;
+    use crate::command_buffer::validity::copy_image_buffer::check_copy_buffer_image;
     use crate::command_buffer::validity::copy_image_buffer::required_len_for_format;
+    use crate::command_buffer::validity::copy_image_buffer::required_len_for_region;
+    use crate::command_buffer::validity::copy_image_buffer::CheckCopyBufferImageError;
+    use crate::command_buffer::validity::copy_image_buffer::CheckCopyBufferImageTy;
     use crate::format::Format;
+    use crate::format::Pixel;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageLayout;
+    use crate::image::StorageImage;
+
+    #[test]
+    fn preinitialized_layout() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_source(),
+            false,
+            (0..32 * 32 * 4).map(|_| 0u8),
+        )
+        .unwrap();
+        let image = StorageImage::new(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        for &layout in &[ImageLayout::Preinitialized, ImageLayout::Undefined] {
+            match check_copy_buffer_image(
+                &device,
+                &buffer,
+                &image,
+                layout,
+                CheckCopyBufferImageTy::BufferToImage,
+                [0, 0, 0],
+                [32, 32, 1],
+                0,
+                1,
+                0,
+            ) {
+                Err(CheckCopyBufferImageError::InvalidImageLayout) => (),
+                _ => panic!(),
+            }
+        }
+
+        check_copy_buffer_image(
+            &device,
+            &buffer,
+            &image,
+            ImageLayout::TransferDstOptimal,
+            CheckCopyBufferImageTy::BufferToImage,
+            [0, 0, 0],
+            [32, 32, 1],
+            0,
+            1,
+            0,
+        )
+        .unwrap();
+    }
+
+    fn full_region() -> UnsafeCommandBufferBuilderBufferImageCopy {
+        UnsafeCommandBufferBuilderBufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_aspect: UnsafeCommandBufferBuilderImageAspect {
+                color: true,
+                depth: false,
+                stencil: false,
+            },
+            image_mip_level: 0,
+            image_base_array_layer: 0,
+            image_layer_count: 1,
+            image_offset: [0, 0, 0],
+            image_extent: [32, 32, 1],
+        }
+    }
+
+    #[test]
+    fn region_fields() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_source(),
+            false,
+            (0..64 * 32 * 4 + 16).map(|_| 0u8),
+        )
+        .unwrap();
+        let image = StorageImage::new(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let check = |region: UnsafeCommandBufferBuilderBufferImageCopy| {
+            check_buffer_image_copy_region(
+                &device,
+                &buffer,
+                &image,
+                ImageLayout::TransferDstOptimal,
+                CheckCopyBufferImageTy::BufferToImage,
+                &region,
+            )
+        };
+
+        check(full_region()).unwrap();
+        check(UnsafeCommandBufferBuilderBufferImageCopy {
+            buffer_offset: 16,
+            buffer_row_length: 64,
+            buffer_image_height: 32,
+            ..full_region()
+        })
+        .unwrap();
+
+        // Aspect.
+        for &(color, depth, stencil) in &[(false, true, false), (true, true, false)] {
+            match check(UnsafeCommandBufferBuilderBufferImageCopy {
+                image_aspect: UnsafeCommandBufferBuilderImageAspect {
+                    color,
+                    depth,
+                    stencil,
+                },
+                ..full_region()
+            }) {
+                Err(CheckCopyBufferImageError::InvalidAspect {
+                    requested,
+                    available,
+                }) => {
+                    assert!(requested.depth);
+                    assert_eq!(available, Format::R8G8B8A8Unorm.aspects());
+                }
+                _ => panic!(),
+            }
+        }
+
+        // Buffer offset.
+        for &offset in &[2, 6] {
+            match check(UnsafeCommandBufferBuilderBufferImageCopy {
+                buffer_offset: offset,
+                ..full_region()
+            }) {
+                Err(CheckCopyBufferImageError::UnalignedBufferOffset) => (),
+                _ => panic!(),
+            }
+        }
+
+        // Row length and image height.
+        match check(UnsafeCommandBufferBuilderBufferImageCopy {
+            buffer_row_length: 16,
+            ..full_region()
+        }) {
+            Err(CheckCopyBufferImageError::InvalidBufferRowLength) => (),
+            _ => panic!(),
+        }
+        match check(UnsafeCommandBufferBuilderBufferImageCopy {
+            buffer_image_height: 16,
+            ..full_region()
+        }) {
+            Err(CheckCopyBufferImageError::InvalidBufferImageHeight) => (),
+            _ => panic!(),
+        }
+
+        // Subresource.
+        let out_of_range = [
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_mip_level: 1,
+                ..full_region()
+            },
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_base_array_layer: 1,
+                ..full_region()
+            },
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_layer_count: 0,
+                ..full_region()
+            },
+            // Offset and extent.
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_offset: [-1, 0, 0],
+                image_extent: [16, 16, 1],
+                ..full_region()
+            },
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_offset: [16, 0, 0],
+                image_extent: [17, 16, 1],
+                ..full_region()
+            },
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_extent: [32, 32, 2],
+                ..full_region()
+            },
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_extent: [0, 32, 1],
+                ..full_region()
+            },
+            // Overflowing values.
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_base_array_layer: u32::MAX,
+                ..full_region()
+            },
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_offset: [16, 0, 0],
+                image_extent: [u32::MAX, 32, 1],
+                ..full_region()
+            },
+            UnsafeCommandBufferBuilderBufferImageCopy {
+                image_offset: [0, 0, 1],
+                image_extent: [32, 32, u32::MAX],
+                ..full_region()
+            },
+        ];
+        for region in out_of_range.iter() {
+            match check(*region) {
+                Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange) => (),
+                _ => panic!("{:?}", region),
+            }
+        }
+
+        // Buffer size, taking the row length into account.
+        match check(UnsafeCommandBufferBuilderBufferImageCopy {
+            buffer_offset: 32,
+            buffer_row_length: 64,
+            ..full_region()
+        }) {
+            Err(CheckCopyBufferImageError::BufferTooSmall {
+                required_len,
+                actual_len,
+            }) => {
+                assert_eq!(required_len, 32 + (31 * 64 + 32) * 4);
+                assert_eq!(actual_len, 64 * 32 * 4 + 16);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn required_len_for_region_offset() {
+        #[derive(Copy, Clone)]
+        #[repr(C)]
+        struct Texel([u8; 4]);
+        unsafe impl Pixel for Texel {}
+
+        let region = UnsafeCommandBufferBuilderBufferImageCopy {
+            image_aspect: UnsafeCommandBufferBuilderImageAspect {
+                color: false,
+                depth: true,
+                stencil: false,
+            },
+            ..full_region()
+        };
+        let len = required_len_for_region::<Texel>(Format::D24Unorm_S8Uint, &region);
+
+        // The format has no block size, but the offset must still be taken into account.
+        assert_eq!(Format::D24Unorm_S8Uint.size(), None);
+        assert_eq!(
+            required_len_for_region::<Texel>(
+                Format::D24Unorm_S8Uint,
+                &UnsafeCommandBufferBuilderBufferImageCopy {
+                    buffer_offset: 16,
+                    ..region
+                }
+            ),
+            len + 4
+        );
+    }
 
     #[test]
     fn test_required_len_for_format() {
@@ -184,10 +684,48 @@ mod tests {
             29584
         );
     }
+
+    #[test]
+    fn unaligned_block_region() {
+        let dimensions = ImageDimensions::Dim2d {
+            width: 18,
+            height: 18,
+            array_layers: 1,
+        };
+
+        // A 2x2 region in the middle of the image covers a part of a block.
+        match check_block_alignment(Format::BC1_RGBUnormBlock, dimensions, [4, 4, 0], [2, 2, 1]) {
+            Err(CheckCopyBufferImageError::UnalignedBlockRegion) => (),
+            _ => panic!(),
+        }
+        match check_block_alignment(Format::BC1_RGBUnormBlock, dimensions, [2, 2, 0], [4, 4, 1]) {
+            Err(CheckCopyBufferImageError::UnalignedBlockRegion) => (),
+            _ => panic!(),
+        }
+
+        // A partial block is allowed at the right and bottom edges.
+        check_block_alignment(
+            Format::BC1_RGBUnormBlock,
+            dimensions,
+            [16, 16, 0],
+            [2, 2, 1],
+        )
+        .unwrap();
+        check_block_alignment(
+            Format::BC1_RGBUnormBlock,
+            dimensions,
+            [0, 0, 0],
+            [18, 18, 1],
+        )
+        .unwrap();
+
+        // Every region is aligned for uncompressed formats.
+        check_block_alignment(Format::R8G8B8A8Unorm, dimensions, [3, 5, 0], [2, 2, 1]).unwrap();
+    }
 }
 
 /// Error that can happen from `check_copy_buffer_image`.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum CheckCopyBufferImageError {
     /// The source buffer or image is missing the transfer source usage.
     SourceMissingTransferUsage,
@@ -195,10 +733,30 @@ pub enum CheckCopyBufferImageError {
     DestinationMissingTransferUsage,
     /// The source and destination are overlapping.
     OverlappingRanges,
+    /// The image is not in a layout that can be used for copies, for example because it is still
+    /// `Undefined` or `Preinitialized`.
+    InvalidImageLayout,
     /// The image must not be multisampled.
     UnexpectedMultisampled,
     /// The image coordinates are out of range.
     ImageCoordinatesOutOfRange,
+    /// The region doesn't copy exactly one aspect of the image, or the image doesn't have it.
+    InvalidAspect {
+        requested: ImageAspect,
+        available: ImageAspect,
+    },
+    /// The offset in the buffer is not a multiple of 4 and of the size of a texel block.
+    UnalignedBufferOffset,
+    /// The row length of the data in the buffer is neither 0 nor a multiple of the block width
+    /// that is at least the width of the region.
+    InvalidBufferRowLength,
+    /// The image height of the data in the buffer is neither 0 nor a multiple of the block height
+    /// that is at least the height of the region.
+    InvalidBufferImageHeight,
+    /// The format of the image is compressed, and the region doesn't follow the grid of texel
+    /// blocks: the offset is not a multiple of the block dimensions, or the size isn't either and
+    /// the region doesn't end at the edge of the image.
+    UnalignedBlockRegion,
     /// The type of pixels in the buffer isn't compatible with the image format.
     WrongPixelType(IncompatiblePixelsType),
     /// The buffer is too small for the copy operation.
@@ -235,12 +793,30 @@ impl fmt::Display for CheckCopyBufferImageError {
                 CheckCopyBufferImageError::OverlappingRanges => {
                     "the source and destination are overlapping"
                 }
+                CheckCopyBufferImageError::InvalidImageLayout => {
+                    "the image is not in a layout that can be used for copies"
+                }
                 CheckCopyBufferImageError::UnexpectedMultisampled => {
                     "the image must not be multisampled"
                 }
                 CheckCopyBufferImageError::ImageCoordinatesOutOfRange => {
                     "the image coordinates are out of range"
                 }
+                CheckCopyBufferImageError::InvalidAspect { .. } => {
+                    "the region doesn't copy exactly one aspect of the image"
+                }
+                CheckCopyBufferImageError::UnalignedBufferOffset => {
+                    "the offset in the buffer is not a multiple of 4 and of the texel block size"
+                }
+                CheckCopyBufferImageError::InvalidBufferRowLength => {
+                    "the row length of the data in the buffer is invalid"
+                }
+                CheckCopyBufferImageError::InvalidBufferImageHeight => {
+                    "the image height of the data in the buffer is invalid"
+                }
+                CheckCopyBufferImageError::UnalignedBlockRegion => {
+                    "the region doesn't follow the grid of texel blocks of the image format"
+                }
                 CheckCopyBufferImageError::WrongPixelType(_) => {
                     "the type of pixels in the buffer isn't compatible with the image format"
                 }
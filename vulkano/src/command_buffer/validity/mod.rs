@@ -12,9 +12,11 @@
 pub use self::blit_image::{check_blit_image, CheckBlitImageError};
 pub use self::clear_color_image::{check_clear_color_image, CheckClearColorImageError};
 pub use self::copy_buffer::{check_copy_buffer, CheckCopyBuffer, CheckCopyBufferError};
-pub use self::copy_image::{check_copy_image, CheckCopyImageError};
+pub use self::copy_image::{check_copy_image, check_copy_image_extents, CheckCopyImageError};
+pub(crate) use self::copy_image_buffer::required_len_for_format;
 pub use self::copy_image_buffer::{
-    check_copy_buffer_image, CheckCopyBufferImageError, CheckCopyBufferImageTy,
+    check_buffer_image_copy_region, check_copy_buffer_image, CheckCopyBufferImageError,
+    CheckCopyBufferImageTy,
 };
 pub use self::debug_marker::{check_debug_marker_color, CheckColorError};
 pub use self::descriptor_sets::{check_descriptor_sets_validity, CheckDescriptorSetsValidityError};
@@ -392,7 +392,7 @@ impl DescriptorDescTy {
 }
 
 /// Additional description for descriptors that contain images.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct DescriptorImageDesc {
     /// If `true`, the image can be sampled by the shader. Only images that were created with the
     /// `sampled` usage can be attached to the descriptor.
@@ -493,14 +493,14 @@ impl DescriptorImageDesc {
 }
 
 // TODO: documentation
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum DescriptorImageDescArray {
     NonArrayed,
     Arrayed { max_layers: Option<u32> },
 }
 
 // TODO: documentation
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum DescriptorImageDescDimensions {
     OneDimensional,
     TwoDimensional,
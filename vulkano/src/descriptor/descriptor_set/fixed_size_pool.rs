@@ -26,7 +26,9 @@ use crate::descriptor::descriptor_set::UnsafeDescriptorSet;
 use crate::descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use crate::device::Device;
 use crate::device::DeviceOwned;
+use crate::image::view::ImageView;
 use crate::image::view::ImageViewAbstract;
+use crate::image::ImageAccess;
 use crate::sampler::Sampler;
 use crate::OomError;
 use crate::VulkanObject;
@@ -416,6 +418,31 @@ impl<'a, R> FixedSizeDescriptorSetBuilder<'a, R> {
         })
     }
 
+    /// Binds an image as the next descriptor, through a view that matches the descriptor.
+    ///
+    /// See `PersistentDescriptorSetBuilder::add_image_auto_view`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the image doesn't have the same device as the descriptor set layout.
+    ///
+    #[inline]
+    pub fn add_image_auto_view<I>(
+        self,
+        image: Arc<I>,
+    ) -> Result<
+        FixedSizeDescriptorSetBuilder<'a, (R, PersistentDescriptorSetImg<Arc<ImageView<Arc<I>>>>)>,
+        PersistentDescriptorSetError,
+    >
+    where
+        I: ImageAccess + Send + Sync + 'static,
+    {
+        Ok(FixedSizeDescriptorSetBuilder {
+            pool: self.pool,
+            inner: self.inner.add_image_auto_view(image)?,
+        })
+    }
+
     /// Binds an image view with a sampler as the next descriptor.
     ///
     /// An error is returned if the image view isn't compatible with the descriptor.
@@ -560,6 +587,34 @@ impl<'a, R> FixedSizeDescriptorSetBuilderArray<'a, R> {
         })
     }
 
+    /// Binds an image as the next element in the array, through a view that matches the
+    /// descriptor.
+    ///
+    /// See `PersistentDescriptorSetBuilder::add_image_auto_view`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the image doesn't have the same device as the descriptor set layout.
+    ///
+    pub fn add_image_auto_view<I>(
+        self,
+        image: Arc<I>,
+    ) -> Result<
+        FixedSizeDescriptorSetBuilderArray<
+            'a,
+            (R, PersistentDescriptorSetImg<Arc<ImageView<Arc<I>>>>),
+        >,
+        PersistentDescriptorSetError,
+    >
+    where
+        I: ImageAccess + Send + Sync + 'static,
+    {
+        Ok(FixedSizeDescriptorSetBuilderArray {
+            pool: self.pool,
+            inner: self.inner.add_image_auto_view(image)?,
+        })
+    }
+
     /// Binds an image view with a sampler as the next element in the array.
     ///
     /// An error is returned if the image view isn't compatible with the descriptor.
@@ -32,7 +32,10 @@ use crate::descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::format::Format;
+use crate::image::view::ImageView;
 use crate::image::view::ImageViewAbstract;
+use crate::image::view::ImageViewCreationError;
+use crate::image::ImageAccess;
 use crate::sampler::Sampler;
 use crate::OomError;
 use crate::VulkanObject;
@@ -340,6 +343,37 @@ impl<R> PersistentDescriptorSetBuilder<R> {
         self.enter_array()?.add_image(image_view)?.leave_array()
     }
 
+    /// Binds an image as the next descriptor, through a view that matches the descriptor.
+    ///
+    /// The view is obtained with `Device::get_or_create_image_view`, so adding the same `Arc` to
+    /// several descriptor sets reuses a single view. Only image descriptors are supported, not
+    /// input attachments.
+    ///
+    /// This is the counterpart of `add_image` for images that aren't wrapped in a view yet, and
+    /// doesn't share its name because `add_image` already takes an image view.
+    ///
+    /// An error is returned if the image isn't compatible with the descriptor.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the image doesn't have the same device as the descriptor set layout.
+    ///
+    #[inline]
+    pub fn add_image_auto_view<I>(
+        self,
+        image: Arc<I>,
+    ) -> Result<
+        PersistentDescriptorSetBuilder<(R, PersistentDescriptorSetImg<Arc<ImageView<Arc<I>>>>)>,
+        PersistentDescriptorSetError,
+    >
+    where
+        I: ImageAccess + Send + Sync + 'static,
+    {
+        self.enter_array()?
+            .add_image_auto_view(image)?
+            .leave_array()
+    }
+
     /// Binds an image view with a sampler as the next descriptor.
     ///
     /// An error is returned if the image view isn't compatible with the descriptor.
@@ -735,6 +769,49 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
         })
     }
 
+    /// Binds an image as the next element in the array, through a view that matches the
+    /// descriptor.
+    ///
+    /// See `PersistentDescriptorSetBuilder::add_image_auto_view`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the image doesn't have the same device as the descriptor set layout.
+    ///
+    pub fn add_image_auto_view<I>(
+        self,
+        image: Arc<I>,
+    ) -> Result<
+        PersistentDescriptorSetBuilderArray<(
+            R,
+            PersistentDescriptorSetImg<Arc<ImageView<Arc<I>>>>,
+        )>,
+        PersistentDescriptorSetError,
+    >
+    where
+        I: ImageAccess + Send + Sync + 'static,
+    {
+        let desc = match self.builder.layout.descriptor(self.builder.binding_id) {
+            Some(DescriptorDesc {
+                ty: DescriptorDescTy::Image(desc),
+                ..
+            }) => desc,
+            Some(desc) => {
+                return Err(PersistentDescriptorSetError::WrongDescriptorTy {
+                    expected: desc.ty.ty(),
+                })
+            }
+            None => return Err(PersistentDescriptorSetError::EmptyExpected),
+        };
+
+        let view = self
+            .builder
+            .layout
+            .device()
+            .get_or_create_image_view(image, &desc)?;
+        self.add_image(view)
+    }
+
     /// Binds an image view with a sampler as the next element in the array.
     ///
     /// An error is returned if the image view isn't compatible with the descriptor.
@@ -1189,6 +1266,9 @@ pub enum PersistentDescriptorSetError {
     /// The image view isn't compatible with the sampler.
     IncompatibleImageViewSampler,
 
+    /// Creating a view of an image for the descriptor failed.
+    ImageViewCreationError(ImageViewCreationError),
+
     /// Didn't fill all the elements of an array before leaving.
     MissingArrayElements {
         /// Number of expected elements.
@@ -1216,7 +1296,22 @@ pub enum PersistentDescriptorSetError {
     },
 }
 
-impl error::Error for PersistentDescriptorSetError {}
+impl error::Error for PersistentDescriptorSetError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            PersistentDescriptorSetError::ImageViewCreationError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ImageViewCreationError> for PersistentDescriptorSetError {
+    #[inline]
+    fn from(err: ImageViewCreationError) -> PersistentDescriptorSetError {
+        PersistentDescriptorSetError::ImageViewCreationError(err)
+    }
+}
 
 impl fmt::Display for PersistentDescriptorSetError {
     #[inline]
@@ -1246,6 +1341,9 @@ impl fmt::Display for PersistentDescriptorSetError {
                 PersistentDescriptorSetError::IncompatibleImageViewSampler => {
                     "the image view isn't compatible with the sampler"
                 }
+                PersistentDescriptorSetError::ImageViewCreationError(_) => {
+                    "creating a view of an image for the descriptor failed"
+                }
                 PersistentDescriptorSetError::MissingArrayElements { .. } => {
                     "didn't fill all the elements of an array before leaving"
                 }
@@ -1308,3 +1406,76 @@ impl fmt::Display for PersistentDescriptorSetBuildError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::descriptor::descriptor::DescriptorDesc;
+    use crate::descriptor::descriptor::DescriptorDescTy;
+    use crate::descriptor::descriptor::DescriptorImageDesc;
+    use crate::descriptor::descriptor::DescriptorImageDescArray;
+    use crate::descriptor::descriptor::DescriptorImageDescDimensions;
+    use crate::descriptor::descriptor::ShaderStages;
+    use crate::descriptor::descriptor_set::PersistentDescriptorSet;
+    use crate::descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use crate::format::Format;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageUsage;
+    use crate::image::StorageImage;
+    use std::iter;
+    use std::sync::Arc;
+
+    #[test]
+    fn add_image_auto_view_reuses_view() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Image(DescriptorImageDesc {
+                sampled: true,
+                dimensions: DescriptorImageDescDimensions::TwoDimensional,
+                format: None,
+                multisampled: false,
+                array_layers: DescriptorImageDescArray::NonArrayed,
+            }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let layout = Arc::new(
+            UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(desc))).unwrap(),
+        );
+
+        let image = StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim2d {
+                width: 16,
+                height: 16,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let set1 = PersistentDescriptorSet::start(layout.clone())
+            .add_image_auto_view(image.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+        let set2 = PersistentDescriptorSet::start(layout)
+            .add_image_auto_view(image)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(Arc::ptr_eq(
+            &(set1.resources.1).image,
+            &(set2.resources.1).image
+        ));
+    }
+}
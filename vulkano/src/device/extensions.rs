@@ -134,17 +134,20 @@ device_extensions! {
     khr_maintenance1 => b"VK_KHR_maintenance1",
     khr_get_memory_requirements2 => b"VK_KHR_get_memory_requirements2",
     khr_dedicated_allocation => b"VK_KHR_dedicated_allocation",
+    khr_image_format_list => b"VK_KHR_image_format_list",
     khr_incremental_present => b"VK_KHR_incremental_present",
     khr_16bit_storage => b"VK_KHR_16bit_storage",
     khr_8bit_storage => b"VK_KHR_8bit_storage",
     khr_storage_buffer_storage_class => b"VK_KHR_storage_buffer_storage_class",
     ext_debug_utils => b"VK_EXT_debug_utils",
     khr_multiview => b"VK_KHR_multiview",
+    khr_depth_stencil_resolve => b"VK_KHR_depth_stencil_resolve",
     ext_full_screen_exclusive => b"VK_EXT_full_screen_exclusive",
     khr_external_memory => b"VK_KHR_external_memory",
     khr_external_memory_fd => b"VK_KHR_external_memory_fd",
     ext_external_memory_dmabuf => b"VK_EXT_external_memory_dma_buf",
     khr_portability_subset => b"VK_KHR_portability_subset",
+    ext_image_view_min_lod => b"VK_EXT_image_view_min_lod",
 }
 
 /// This helper type can only be instantiated inside this module.
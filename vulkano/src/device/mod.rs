@@ -91,6 +91,8 @@
 
 use fnv::FnvHasher;
 use smallvec::SmallVec;
+use std::any::Any;
+use std::cmp;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::error;
@@ -108,6 +110,7 @@ use std::sync::MutexGuard;
 use std::sync::Weak;
 
 use crate::command_buffer::pool::StandardCommandPool;
+use crate::descriptor::descriptor::DescriptorImageDesc;
 use crate::descriptor::descriptor_set::StdDescriptorPool;
 use crate::instance::Instance;
 use crate::instance::PhysicalDevice;
@@ -128,11 +131,21 @@ pub use crate::features::Features;
 mod extensions;
 
 use crate::format::Format;
+use crate::image::sys::UnsafeImage;
+use crate::image::view::ImageView;
+use crate::image::view::ImageViewCreationError;
+use crate::image::ImageAccess;
+use crate::image::ImageCapabilities;
 use crate::image::ImageCreateFlags;
 use crate::image::ImageFormatProperties;
 use crate::image::ImageTiling;
 use crate::image::ImageType;
 use crate::image::ImageUsage;
+use crate::image::SampleCounts;
+use crate::image::SparseImageFormatProperties;
+use crate::memory::MemoryPropertyFlags;
+use crate::render_pass::ResolveModes;
+use crate::version::Version;
 
 /// Represents a Vulkan context.
 pub struct Device {
@@ -151,6 +164,37 @@ pub struct Device {
     fence_pool: Mutex<Vec<vk::Fence>>,
     semaphore_pool: Mutex<Vec<vk::Semaphore>>,
     event_pool: Mutex<Vec<vk::Event>>,
+    image_views:
+        Mutex<HashMap<ImageViewCacheKey, Box<dyn CachedImageView>, BuildHasherDefault<FnvHasher>>>,
+}
+
+/// Identifies the views cached by `Device::get_or_create_image_view`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ImageViewCacheKey {
+    // Address of the `Arc` that the view was created from.
+    image: usize,
+    desc: DescriptorImageDesc,
+}
+
+/// A weak reference to an `ImageView` of any image type.
+trait CachedImageView: Send + Sync {
+    fn is_alive(&self) -> bool;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<I> CachedImageView for Weak<ImageView<I>>
+where
+    I: ImageAccess + Send + Sync + 'static,
+{
+    #[inline]
+    fn is_alive(&self) -> bool {
+        self.strong_count() != 0
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 // The `StandardCommandPool` type doesn't implement Send/Sync, so we have to manually reimplement
@@ -346,6 +390,7 @@ impl Device {
             fence_pool: Mutex::new(Vec::new()),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
+            image_views: Mutex::new(Default::default()),
         });
 
         // Iterator for the produced queues.
@@ -392,6 +437,18 @@ impl Device {
         PhysicalDevice::from_index(&self.instance, self.physical_device).unwrap()
     }
 
+    /// Returns the version of Vulkan that can be used with this device.
+    ///
+    /// This is the lowest of the version supported by the physical device and the version that
+    /// the instance was created for.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        cmp::min(
+            self.instance.api_version(),
+            self.physical_device().api_version(),
+        )
+    }
+
     /// Returns an iterator to the list of queues families that this device uses.
     ///
     /// > **Note**: Will return `-> impl ExactSizeIterator<Item = QueueFamily>` in the future.
@@ -476,6 +533,53 @@ impl Device {
         }
     }
 
+    /// Returns a view of `image` suitable for a descriptor described by `desc`, as created by
+    /// `ImageView::for_descriptor`.
+    ///
+    /// The device keeps track of the views that it created this way, and returns the same view
+    /// again if it is still alive and is requested for the same `Arc` and an identical
+    /// descriptor. Views are cached per `Arc` rather than per Vulkan image, so that a returned
+    /// view always wraps a clone of `image`. Two different `Arc`s that wrap the same image get
+    /// two different views. The device only holds weak references, so the views are destroyed
+    /// as usual once their last user is dropped.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the image doesn't belong to this device.
+    ///
+    pub fn get_or_create_image_view<I>(
+        &self,
+        image: Arc<I>,
+        desc: &DescriptorImageDesc,
+    ) -> Result<Arc<ImageView<Arc<I>>>, ImageViewCreationError>
+    where
+        I: ImageAccess + Send + Sync + 'static,
+    {
+        assert_eq!(image.inner().image.device().internal_object(), self.device);
+
+        let key = ImageViewCacheKey {
+            image: Arc::as_ptr(&image) as *const () as usize,
+            desc: *desc,
+        };
+
+        let mut image_views = self.image_views.lock().unwrap();
+
+        if let Some(cached) = image_views.get(&key) {
+            let view = cached
+                .as_any()
+                .downcast_ref::<Weak<ImageView<Arc<I>>>>()
+                .and_then(|weak| weak.upgrade());
+            if let Some(view) = view {
+                return Ok(view);
+            }
+        }
+
+        let view = ImageView::for_descriptor(image, desc)?;
+        image_views.retain(|_, cached| cached.is_alive());
+        image_views.insert(key, Box::new(Arc::downgrade(&view)));
+        Ok(view)
+    }
+
     /// Used to track the number of allocations on this device.
     ///
     /// To ensure valid usage of the Vulkan API, we cannot call `vkAllocateMemory` when
@@ -544,30 +648,226 @@ impl Device {
         usage: ImageUsage,
         create_flags: ImageCreateFlags,
     ) -> Result<ImageFormatProperties, String> {
+        self.image_format_properties_raw(format, ty, tiling, usage, create_flags)
+            .map_err(|e| format!("Image properties not supported. {:#?}", e))
+    }
+
+    // Calls `vkGetPhysicalDeviceImageFormatProperties`. The error is `FormatNotSupported` if the
+    // combination of attributes is not supported.
+    pub(crate) fn image_format_properties_raw(
+        &self,
+        format: Format,
+        ty: ImageType,
+        tiling: ImageTiling,
+        usage: ImageUsage,
+        create_flags: ImageCreateFlags,
+    ) -> Result<ImageFormatProperties, Error> {
         let vk_i = self.instance().pointers();
         let mut output = MaybeUninit::uninit();
+        unsafe {
+            check_errors(vk_i.GetPhysicalDeviceImageFormatProperties(
+                self.physical_device().internal_object(),
+                format as u32,
+                ty.into(),
+                tiling.into(),
+                usage.to_usage_bits(),
+                create_flags.into(),
+                output.as_mut_ptr(),
+            ))?;
+            Ok(output.assume_init().into())
+        }
+    }
+
+    /// Checks whether an image with the given attributes and number of samples per pixel can be
+    /// created on this device, and if so returns everything that the device supports for it.
+    ///
+    /// This combines the format features of `format` for `tiling`, which must allow `usage`, with
+    /// the image format properties of the physical device, whose sample counts must contain
+    /// `samples`. Returns `Ok(None)` if any of these checks fails, and an error if the
+    /// implementation ran out of memory while querying the properties.
+    pub fn image_capabilities(
+        &self,
+        format: Format,
+        ty: ImageType,
+        tiling: ImageTiling,
+        usage: ImageUsage,
+        create_flags: ImageCreateFlags,
+        samples: u32,
+    ) -> Result<Option<ImageCapabilities>, OomError> {
+        let properties = format.properties(self.physical_device());
+        let format_features = match tiling {
+            ImageTiling::Optimal => properties.optimal_tiling_features,
+            ImageTiling::Linear => properties.linear_tiling_features,
+        };
+        if !format_features.supports_image_usage(usage, self.loaded_extensions().khr_maintenance1) {
+            return Ok(None);
+        }
+
+        let properties =
+            match self.image_format_properties_raw(format, ty, tiling, usage, create_flags) {
+                Ok(properties) => properties,
+                Err(Error::FormatNotSupported) => return Ok(None),
+                Err(err) => return Err(err.into()),
+            };
+
+        let sample_counts = SampleCounts::from(properties.sample_counts);
+        if !sample_counts.contains(samples) {
+            return Ok(None);
+        }
+
+        Ok(Some(ImageCapabilities {
+            format_features,
+            max_extent: properties.max_extent,
+            max_mip_levels: properties.max_mip_levels,
+            max_array_layers: properties.max_array_layers,
+            sample_counts,
+            max_resource_size: properties.max_resource_size,
+        }))
+    }
+
+    /// Returns all the formats that an image with the given tiling can use for `usage` on this
+    /// device, in the order of declaration of `Format`.
+    ///
+    /// This is useful to let the user pick a format, or to build a list of fallbacks.
+    pub fn supported_formats(&self, usage: ImageUsage, tiling: ImageTiling) -> Vec<Format> {
+        let check_transfer = self.loaded_extensions().khr_maintenance1;
+
+        Format::all()
+            .filter(|format| {
+                let properties = format.properties(self.physical_device());
+                let features = match tiling {
+                    ImageTiling::Optimal => properties.optimal_tiling_features,
+                    ImageTiling::Linear => properties.linear_tiling_features,
+                };
+                features.supports_image_usage(usage, check_transfer)
+            })
+            .collect()
+    }
+
+    /// Returns the sparse image properties of the given combination of image attributes, one
+    /// element per aspect of the format.
+    ///
+    /// The returned list is empty if the combination doesn't support sparse residency, for
+    /// example if `samples` is not a power of two or if the `sparse_residency_*` feature
+    /// matching the image type is not supported by the physical device.
+    pub fn sparse_image_format_properties(
+        &self,
+        format: Format,
+        ty: ImageType,
+        samples: u32,
+        usage: ImageUsage,
+        tiling: ImageTiling,
+    ) -> Vec<SparseImageFormatProperties> {
+        let vk_i = self.instance().pointers();
         let physical_device = self.physical_device().internal_object();
+
         unsafe {
-            let r = vk_i.GetPhysicalDeviceImageFormatProperties(
+            let mut num = 0;
+            vk_i.GetPhysicalDeviceSparseImageFormatProperties(
                 physical_device,
                 format as u32,
                 ty.into(),
+                samples,
+                usage.to_usage_bits(),
                 tiling.into(),
+                &mut num,
+                ptr::null_mut(),
+            );
+
+            let mut output = Vec::with_capacity(num as usize);
+            vk_i.GetPhysicalDeviceSparseImageFormatProperties(
+                physical_device,
+                format as u32,
+                ty.into(),
+                samples,
                 usage.to_usage_bits(),
-                create_flags.into(),
+                tiling.into(),
+                &mut num,
                 output.as_mut_ptr(),
             );
+            output.set_len(num as usize);
 
-            match check_errors(r) {
-                Ok(_) => Ok(output.assume_init().into()),
-                Err(e) => {
-                    return Err(String::from(format!(
-                        "Image properties not supported. {:#?}",
-                        e
-                    )))
-                }
-            }
+            output.into_iter().map(Into::into).collect()
+        }
+    }
+
+    /// Returns the modes that can be used to resolve the depth aspect of a multisampled
+    /// attachment. Use `ResolveModes::contains` to check whether a given mode is supported.
+    ///
+    /// Returns `None` if neither the `khr_depth_stencil_resolve` extension is enabled nor the
+    /// `api_version` of the device is at least Vulkan 1.2.
+    #[inline]
+    pub fn supported_depth_resolve_modes(&self) -> Option<ResolveModes> {
+        if !self.supports_depth_stencil_resolve() {
+            return None;
+        }
+
+        *self
+            .physical_device()
+            .extended_properties()
+            .supported_depth_resolve_modes()
+    }
+
+    /// Returns the modes that can be used to resolve the stencil aspect of a multisampled
+    /// attachment. Use `ResolveModes::contains` to check whether a given mode is supported.
+    ///
+    /// Returns `None` if neither the `khr_depth_stencil_resolve` extension is enabled nor the
+    /// `api_version` of the device is at least Vulkan 1.2.
+    #[inline]
+    pub fn supported_stencil_resolve_modes(&self) -> Option<ResolveModes> {
+        if !self.supports_depth_stencil_resolve() {
+            return None;
         }
+
+        *self
+            .physical_device()
+            .extended_properties()
+            .supported_stencil_resolve_modes()
+    }
+
+    #[inline]
+    fn supports_depth_stencil_resolve(&self) -> bool {
+        self.loaded_extensions().khr_depth_stencil_resolve
+            || self.api_version()
+                >= (Version {
+                    major: 1,
+                    minor: 2,
+                    patch: 0,
+                })
+    }
+
+    /// Chooses a memory type that can be used to allocate memory for `image`.
+    ///
+    /// Only the memory types allowed by the image's memory requirements and having all the
+    /// properties in `required_props` are considered. Among those, a memory type that also has
+    /// the properties in `preferred_props` is returned if there is one. Returns `None` if no
+    /// memory type has the required properties.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `image` was not created with this device.
+    ///
+    pub fn find_memory_type_for_image(
+        &self,
+        image: &UnsafeImage,
+        required_props: MemoryPropertyFlags,
+        preferred_props: MemoryPropertyFlags,
+    ) -> Option<u32> {
+        assert_eq!(image.device().internal_object(), self.internal_object());
+
+        let memory_type_bits = image.memory_requirements().memory_type_bits;
+        let preferred_props = required_props.union(&preferred_props);
+
+        let candidates = || {
+            self.physical_device()
+                .memory_types()
+                .filter(move |ty| (memory_type_bits & (1 << ty.id())) != 0)
+        };
+
+        candidates()
+            .find(|ty| ty.property_flags().contains(&preferred_props))
+            .or_else(|| candidates().find(|ty| ty.property_flags().contains(&required_props)))
+            .map(|ty| ty.id())
     }
 }
 
@@ -850,13 +1150,148 @@ unsafe impl SynchronizedVulkanObject for Queue {
 
 #[cfg(test)]
 mod tests {
+    use crate::descriptor::descriptor::DescriptorImageDesc;
+    use crate::descriptor::descriptor::DescriptorImageDescArray;
+    use crate::descriptor::descriptor::DescriptorImageDescDimensions;
     use crate::device::Device;
     use crate::device::DeviceCreationError;
     use crate::device::DeviceExtensions;
     use crate::features::Features;
+    use crate::format::Format;
+    use crate::image::ImageAccess;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageTiling;
+    use crate::image::ImageType;
+    use crate::image::ImageUsage;
+    use crate::image::StorageImage;
     use crate::instance;
+    use crate::memory::MemoryPropertyFlags;
+    use crate::render_pass::ResolveMode;
     use std::sync::Arc;
 
+    #[test]
+    fn image_capabilities() {
+        let (device, _) = gfx_dev_and_queue!();
+        let usage = ImageUsage {
+            sampled: true,
+            transfer_destination: true,
+            ..ImageUsage::none()
+        };
+
+        // Sampling 2D images of this format with optimal tiling is mandatory.
+        let caps = device
+            .image_capabilities(
+                Format::R8G8B8A8Unorm,
+                ImageType::Dim2d,
+                ImageTiling::Optimal,
+                usage,
+                ImageCreateFlags::none(),
+                1,
+            )
+            .unwrap()
+            .unwrap();
+        assert!(caps.format_features.sampled_image);
+        assert!(caps.sample_counts.sample1);
+        assert!(caps.max_mip_levels >= 1);
+        assert!(caps.max_array_layers >= 1);
+
+        // 3 is never a valid number of samples.
+        assert!(device
+            .image_capabilities(
+                Format::R8G8B8A8Unorm,
+                ImageType::Dim2d,
+                ImageTiling::Optimal,
+                usage,
+                ImageCreateFlags::none(),
+                3,
+            )
+            .unwrap()
+            .is_none());
+
+        // Compressed formats can't be rendered to.
+        let usage = ImageUsage {
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        assert!(device
+            .image_capabilities(
+                Format::BC1_RGBAUnormBlock,
+                ImageType::Dim2d,
+                ImageTiling::Optimal,
+                usage,
+                ImageCreateFlags::none(),
+                1,
+            )
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn image_view_cache() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = StorageImage::with_usage(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 16,
+                height: 16,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                storage: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let sampled = DescriptorImageDesc {
+            sampled: true,
+            dimensions: DescriptorImageDescDimensions::TwoDimensional,
+            format: None,
+            multisampled: false,
+            array_layers: DescriptorImageDescArray::NonArrayed,
+        };
+        let storage = DescriptorImageDesc {
+            sampled: false,
+            ..sampled
+        };
+
+        let a = device
+            .get_or_create_image_view(image.clone(), &sampled)
+            .unwrap();
+        let b = device
+            .get_or_create_image_view(image.clone(), &sampled)
+            .unwrap();
+        let c = device
+            .get_or_create_image_view(image.clone(), &storage)
+            .unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+
+        // Different `Arc`s wrapping the same image get their own views.
+        let wrapper1 = Arc::new(image.clone());
+        let wrapper2 = Arc::new(image.clone());
+        let d = device
+            .get_or_create_image_view(wrapper1.clone(), &sampled)
+            .unwrap();
+        let e = device
+            .get_or_create_image_view(wrapper2.clone(), &sampled)
+            .unwrap();
+        assert!(!Arc::ptr_eq(&d, &e));
+        assert!(Arc::ptr_eq(d.image(), &wrapper1));
+        assert!(Arc::ptr_eq(e.image(), &wrapper2));
+        drop((wrapper1, wrapper2));
+
+        // Only weak references are kept.
+        drop((a, b, c, d, e));
+        assert_eq!(Arc::strong_count(&image), 1);
+    }
+
     #[test]
     fn one_ref() {
         let (mut device, _) = gfx_dev_and_queue!();
@@ -942,4 +1377,82 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn find_memory_type_for_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = StorageImage::new(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let device_local = MemoryPropertyFlags {
+            device_local: true,
+            ..MemoryPropertyFlags::none()
+        };
+        let id = device
+            .find_memory_type_for_image(
+                image.inner().image,
+                device_local,
+                MemoryPropertyFlags::none(),
+            )
+            .unwrap();
+        let ty = device.physical_device().memory_type_by_id(id).unwrap();
+        assert!(ty.is_device_local());
+    }
+
+    #[test]
+    fn depth_resolve_modes_contain_sample_zero() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if let Some(modes) = device.supported_depth_resolve_modes() {
+            assert!(modes.contains(ResolveMode::SampleZero));
+        }
+    }
+
+    #[test]
+    fn sparse_image_format_properties() {
+        let (device, _) = gfx_dev_and_queue!(sparse_residency_image2d);
+
+        let properties = device.sparse_image_format_properties(
+            Format::R8G8B8A8Unorm,
+            ImageType::Dim2d,
+            1,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageTiling::Optimal,
+        );
+        assert!(!properties.is_empty());
+
+        let color = properties.iter().find(|p| p.aspects.color).unwrap();
+        let dimensions = ImageDimensions::Dim2d {
+            width: 1024,
+            height: 1024,
+            array_layers: 1,
+        };
+        assert!(dimensions.sparse_tile_count(color, 0).unwrap() >= 1);
+    }
+
+    #[test]
+    fn supported_formats() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        let formats = device.supported_formats(usage, ImageTiling::Optimal);
+        assert!(formats.contains(&Format::R8G8B8A8Unorm));
+        assert!(!formats.contains(&Format::D16Unorm));
+    }
 }
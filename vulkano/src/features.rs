@@ -280,6 +280,8 @@ pub struct Features {
 
     pub shader_float16: bool,
     pub shader_int8: bool,
+
+    pub min_lod: bool,
 }
 
 pub(crate) struct FeaturesFfi {
@@ -291,6 +293,7 @@ pub(crate) struct FeaturesFfi {
     i8_storage: vk::PhysicalDevice8BitStorageFeatures,
     i16_storage: vk::PhysicalDevice16BitStorageFeatures,
     f16_i8: vk::PhysicalDeviceShaderFloat16Int8Features,
+    image_view_min_lod: vk::PhysicalDeviceImageViewMinLodFeaturesEXT,
 }
 
 macro_rules! features {
@@ -554,4 +557,12 @@ features! {
         shader_int8 => shaderInt8,
       ],
     },
+    extension {
+      ty: vk::PhysicalDeviceImageViewMinLodFeaturesEXT,
+      ffi_name: image_view_min_lod,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_IMAGE_VIEW_MIN_LOD_FEATURES_EXT,
+      fields: [
+        min_lod => minLod,
+      ],
+    },
 }
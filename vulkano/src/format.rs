@@ -106,8 +106,12 @@ use std::mem::MaybeUninit;
 use std::vec::IntoIter as VecIntoIter;
 use std::{error, fmt, mem};
 
+use crate::device::Device;
+use crate::image::ImageAspect;
+use crate::image::ImageUsage;
 use crate::instance::PhysicalDevice;
 use half::f16;
+use smallvec::SmallVec;
 
 use crate::vk;
 use crate::VulkanObject;
@@ -187,6 +191,9 @@ macro_rules! formats {
         }
 
         impl Format {
+            // Every format, in the order of declaration.
+            const ALL: &'static [Format] = &[$(Format::$name,)+];
+
             /*pub fn is_supported_for_vertex_attributes(&self) -> bool {
 
             }
@@ -275,6 +282,13 @@ macro_rules! formats {
 
             formats!(__inner_impl__ $name $($f_ty)*);
             formats!(__inner_strongstorage__ $name $($d_ty)*);
+
+            unsafe impl<T: Pixel> AcceptsPixels<T> for $name {
+                #[inline]
+                fn ensure_accepts(&self) -> Result<(), IncompatiblePixelsType> {
+                    <Format as AcceptsPixels<T>>::ensure_accepts(&Format::$name)
+                }
+            }
         )+
     );
 
@@ -674,6 +688,1252 @@ formats! {
     G8B8R8_2PLANE420Unorm => FORMAT_G8_B8R8_2PLANE_420_UNORM [(1, 1)] [None] [ycbcr] {},
 }
 
+impl Format {
+    /// Returns the `Format` corresponding to a raw `VkFormat` value, as stored for example in
+    /// KTX2 files. Returns `None` if the value is not a format known to vulkano.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// assert_eq!(Format::from_vk_format(37), Some(Format::R8G8B8A8Unorm));
+    /// assert_eq!(Format::from_vk_format(0), None);
+    /// ```
+    #[inline]
+    pub fn from_vk_format(raw: u32) -> Option<Format> {
+        Format::from_vulkan_num(raw)
+    }
+
+    /// Returns the `Format` corresponding to a `DXGI_FORMAT` value, as stored in the DX10
+    /// header of DDS files. Returns `None` if the DXGI format has no equivalent.
+    ///
+    /// Only the commonly used 8, 16 and 32-bit color formats, depth formats and BC compressed
+    /// formats are mapped. Typeless DXGI formats aren't, since they don't specify how the data
+    /// must be interpreted.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// assert_eq!(Format::from_dxgi(98), Some(Format::BC7UnormBlock));
+    /// ```
+    pub fn from_dxgi(dxgi_format: u32) -> Option<Format> {
+        Some(match dxgi_format {
+            2 => Format::R32G32B32A32Sfloat,
+            3 => Format::R32G32B32A32Uint,
+            4 => Format::R32G32B32A32Sint,
+            6 => Format::R32G32B32Sfloat,
+            7 => Format::R32G32B32Uint,
+            8 => Format::R32G32B32Sint,
+            10 => Format::R16G16B16A16Sfloat,
+            11 => Format::R16G16B16A16Unorm,
+            12 => Format::R16G16B16A16Uint,
+            13 => Format::R16G16B16A16Snorm,
+            14 => Format::R16G16B16A16Sint,
+            16 => Format::R32G32Sfloat,
+            17 => Format::R32G32Uint,
+            18 => Format::R32G32Sint,
+            20 => Format::D32Sfloat_S8Uint,
+            24 => Format::A2B10G10R10UnormPack32,
+            25 => Format::A2B10G10R10UintPack32,
+            26 => Format::B10G11R11UfloatPack32,
+            28 => Format::R8G8B8A8Unorm,
+            29 => Format::R8G8B8A8Srgb,
+            30 => Format::R8G8B8A8Uint,
+            31 => Format::R8G8B8A8Snorm,
+            32 => Format::R8G8B8A8Sint,
+            34 => Format::R16G16Sfloat,
+            35 => Format::R16G16Unorm,
+            36 => Format::R16G16Uint,
+            37 => Format::R16G16Snorm,
+            38 => Format::R16G16Sint,
+            40 => Format::D32Sfloat,
+            41 => Format::R32Sfloat,
+            42 => Format::R32Uint,
+            43 => Format::R32Sint,
+            45 => Format::D24Unorm_S8Uint,
+            49 => Format::R8G8Unorm,
+            50 => Format::R8G8Uint,
+            51 => Format::R8G8Snorm,
+            52 => Format::R8G8Sint,
+            54 => Format::R16Sfloat,
+            55 => Format::D16Unorm,
+            56 => Format::R16Unorm,
+            57 => Format::R16Uint,
+            58 => Format::R16Snorm,
+            59 => Format::R16Sint,
+            61 => Format::R8Unorm,
+            62 => Format::R8Uint,
+            63 => Format::R8Snorm,
+            64 => Format::R8Sint,
+            67 => Format::E5B9G9R9UfloatPack32,
+            71 => Format::BC1_RGBAUnormBlock,
+            72 => Format::BC1_RGBASrgbBlock,
+            74 => Format::BC2UnormBlock,
+            75 => Format::BC2SrgbBlock,
+            77 => Format::BC3UnormBlock,
+            78 => Format::BC3SrgbBlock,
+            80 => Format::BC4UnormBlock,
+            81 => Format::BC4SnormBlock,
+            83 => Format::BC5UnormBlock,
+            84 => Format::BC5SnormBlock,
+            85 => Format::R5G6B5UnormPack16,
+            86 => Format::A1R5G5B5UnormPack16,
+            87 => Format::B8G8R8A8Unorm,
+            91 => Format::B8G8R8A8Srgb,
+            95 => Format::BC6HUfloatBlock,
+            96 => Format::BC6HSfloatBlock,
+            98 => Format::BC7UnormBlock,
+            99 => Format::BC7SrgbBlock,
+            _ => return None,
+        })
+    }
+
+    /// Returns the order in which the color components of this format are stored.
+    ///
+    /// For packed formats (the ones whose name ends in `Pack8`, `Pack16` or `Pack32`) the order
+    /// goes from the most significant to the least significant bits of the packed value.
+    /// Depth, stencil, shared-exponent and YCbCr formats return `ChannelOrder::Other`.
+    ///
+    /// ```
+    /// use vulkano::format::{ChannelOrder, Format};
+    /// assert_eq!(Format::B8G8R8A8Unorm.channel_order(), ChannelOrder::Bgra);
+    /// assert_eq!(Format::R8G8B8A8Unorm.channel_order(), ChannelOrder::Rgba);
+    /// ```
+    pub fn channel_order(&self) -> ChannelOrder {
+        match *self {
+            Format::R8Unorm
+            | Format::R8Snorm
+            | Format::R8Uscaled
+            | Format::R8Sscaled
+            | Format::R8Uint
+            | Format::R8Sint
+            | Format::R8Srgb
+            | Format::R16Unorm
+            | Format::R16Snorm
+            | Format::R16Uscaled
+            | Format::R16Sscaled
+            | Format::R16Uint
+            | Format::R16Sint
+            | Format::R16Sfloat
+            | Format::R32Uint
+            | Format::R32Sint
+            | Format::R32Sfloat
+            | Format::R64Uint
+            | Format::R64Sint
+            | Format::R64Sfloat
+            | Format::BC4UnormBlock
+            | Format::BC4SnormBlock
+            | Format::EAC_R11UnormBlock
+            | Format::EAC_R11SnormBlock => ChannelOrder::R,
+            Format::R4G4UnormPack8
+            | Format::R8G8Unorm
+            | Format::R8G8Snorm
+            | Format::R8G8Uscaled
+            | Format::R8G8Sscaled
+            | Format::R8G8Uint
+            | Format::R8G8Sint
+            | Format::R8G8Srgb
+            | Format::R16G16Unorm
+            | Format::R16G16Snorm
+            | Format::R16G16Uscaled
+            | Format::R16G16Sscaled
+            | Format::R16G16Uint
+            | Format::R16G16Sint
+            | Format::R16G16Sfloat
+            | Format::R32G32Uint
+            | Format::R32G32Sint
+            | Format::R32G32Sfloat
+            | Format::R64G64Uint
+            | Format::R64G64Sint
+            | Format::R64G64Sfloat
+            | Format::BC5UnormBlock
+            | Format::BC5SnormBlock
+            | Format::EAC_R11G11UnormBlock
+            | Format::EAC_R11G11SnormBlock => ChannelOrder::Rg,
+            Format::R5G6B5UnormPack16
+            | Format::R8G8B8Unorm
+            | Format::R8G8B8Snorm
+            | Format::R8G8B8Uscaled
+            | Format::R8G8B8Sscaled
+            | Format::R8G8B8Uint
+            | Format::R8G8B8Sint
+            | Format::R8G8B8Srgb
+            | Format::R16G16B16Unorm
+            | Format::R16G16B16Snorm
+            | Format::R16G16B16Uscaled
+            | Format::R16G16B16Sscaled
+            | Format::R16G16B16Uint
+            | Format::R16G16B16Sint
+            | Format::R16G16B16Sfloat
+            | Format::R32G32B32Uint
+            | Format::R32G32B32Sint
+            | Format::R32G32B32Sfloat
+            | Format::R64G64B64Uint
+            | Format::R64G64B64Sint
+            | Format::R64G64B64Sfloat
+            | Format::BC1_RGBUnormBlock
+            | Format::BC1_RGBSrgbBlock
+            | Format::BC6HUfloatBlock
+            | Format::BC6HSfloatBlock
+            | Format::ETC2_R8G8B8UnormBlock
+            | Format::ETC2_R8G8B8SrgbBlock => ChannelOrder::Rgb,
+            Format::B5G6R5UnormPack16
+            | Format::B8G8R8Unorm
+            | Format::B8G8R8Snorm
+            | Format::B8G8R8Uscaled
+            | Format::B8G8R8Sscaled
+            | Format::B8G8R8Uint
+            | Format::B8G8R8Sint
+            | Format::B8G8R8Srgb
+            | Format::B10G11R11UfloatPack32 => ChannelOrder::Bgr,
+            Format::R4G4B4A4UnormPack16
+            | Format::R5G5B5A1UnormPack16
+            | Format::R8G8B8A8Unorm
+            | Format::R8G8B8A8Snorm
+            | Format::R8G8B8A8Uscaled
+            | Format::R8G8B8A8Sscaled
+            | Format::R8G8B8A8Uint
+            | Format::R8G8B8A8Sint
+            | Format::R8G8B8A8Srgb
+            | Format::R16G16B16A16Unorm
+            | Format::R16G16B16A16Snorm
+            | Format::R16G16B16A16Uscaled
+            | Format::R16G16B16A16Sscaled
+            | Format::R16G16B16A16Uint
+            | Format::R16G16B16A16Sint
+            | Format::R16G16B16A16Sfloat
+            | Format::R32G32B32A32Uint
+            | Format::R32G32B32A32Sint
+            | Format::R32G32B32A32Sfloat
+            | Format::R64G64B64A64Uint
+            | Format::R64G64B64A64Sint
+            | Format::R64G64B64A64Sfloat
+            | Format::BC1_RGBAUnormBlock
+            | Format::BC1_RGBASrgbBlock
+            | Format::BC2UnormBlock
+            | Format::BC2SrgbBlock
+            | Format::BC3UnormBlock
+            | Format::BC3SrgbBlock
+            | Format::BC7UnormBlock
+            | Format::BC7SrgbBlock
+            | Format::ETC2_R8G8B8A1UnormBlock
+            | Format::ETC2_R8G8B8A1SrgbBlock
+            | Format::ETC2_R8G8B8A8UnormBlock
+            | Format::ETC2_R8G8B8A8SrgbBlock
+            | Format::ASTC_4x4UnormBlock
+            | Format::ASTC_4x4SrgbBlock
+            | Format::ASTC_5x4UnormBlock
+            | Format::ASTC_5x4SrgbBlock
+            | Format::ASTC_5x5UnormBlock
+            | Format::ASTC_5x5SrgbBlock
+            | Format::ASTC_6x5UnormBlock
+            | Format::ASTC_6x5SrgbBlock
+            | Format::ASTC_6x6UnormBlock
+            | Format::ASTC_6x6SrgbBlock
+            | Format::ASTC_8x5UnormBlock
+            | Format::ASTC_8x5SrgbBlock
+            | Format::ASTC_8x6UnormBlock
+            | Format::ASTC_8x6SrgbBlock
+            | Format::ASTC_8x8UnormBlock
+            | Format::ASTC_8x8SrgbBlock
+            | Format::ASTC_10x5UnormBlock
+            | Format::ASTC_10x5SrgbBlock
+            | Format::ASTC_10x6UnormBlock
+            | Format::ASTC_10x6SrgbBlock
+            | Format::ASTC_10x8UnormBlock
+            | Format::ASTC_10x8SrgbBlock
+            | Format::ASTC_10x10UnormBlock
+            | Format::ASTC_10x10SrgbBlock
+            | Format::ASTC_12x10UnormBlock
+            | Format::ASTC_12x10SrgbBlock
+            | Format::ASTC_12x12UnormBlock
+            | Format::ASTC_12x12SrgbBlock => ChannelOrder::Rgba,
+            Format::B4G4R4A4UnormPack16
+            | Format::B5G5R5A1UnormPack16
+            | Format::B8G8R8A8Unorm
+            | Format::B8G8R8A8Snorm
+            | Format::B8G8R8A8Uscaled
+            | Format::B8G8R8A8Sscaled
+            | Format::B8G8R8A8Uint
+            | Format::B8G8R8A8Sint
+            | Format::B8G8R8A8Srgb => ChannelOrder::Bgra,
+            Format::A1R5G5B5UnormPack16
+            | Format::A2R10G10B10UnormPack32
+            | Format::A2R10G10B10SnormPack32
+            | Format::A2R10G10B10UscaledPack32
+            | Format::A2R10G10B10SscaledPack32
+            | Format::A2R10G10B10UintPack32
+            | Format::A2R10G10B10SintPack32 => ChannelOrder::Argb,
+            Format::A8B8G8R8UnormPack32
+            | Format::A8B8G8R8SnormPack32
+            | Format::A8B8G8R8UscaledPack32
+            | Format::A8B8G8R8SscaledPack32
+            | Format::A8B8G8R8UintPack32
+            | Format::A8B8G8R8SintPack32
+            | Format::A8B8G8R8SrgbPack32
+            | Format::A2B10G10R10UnormPack32
+            | Format::A2B10G10R10SnormPack32
+            | Format::A2B10G10R10UscaledPack32
+            | Format::A2B10G10R10SscaledPack32
+            | Format::A2B10G10R10UintPack32
+            | Format::A2B10G10R10SintPack32 => ChannelOrder::Abgr,
+            Format::E5B9G9R9UfloatPack32
+            | Format::D16Unorm
+            | Format::X8_D24UnormPack32
+            | Format::D32Sfloat
+            | Format::S8Uint
+            | Format::D16Unorm_S8Uint
+            | Format::D24Unorm_S8Uint
+            | Format::D32Sfloat_S8Uint
+            | Format::G8B8R8_3PLANE420Unorm
+            | Format::G8B8R8_2PLANE420Unorm => ChannelOrder::Other,
+        }
+    }
+
+    /// Returns true if this format stores color components in the sRGB color space.
+    ///
+    /// The components of such a format are converted from sRGB to linear when sampled, and from
+    /// linear to sRGB when written.
+    #[inline]
+    pub fn is_srgb(&self) -> bool {
+        self.linear_sibling().is_some()
+    }
+
+    /// If this is an sRGB format, returns the `Unorm` format with the same layout, whose
+    /// components are read and written as-is.
+    ///
+    /// Returns `None` if this is not an sRGB format.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// assert_eq!(Format::R8G8B8A8Srgb.linear_sibling(), Some(Format::R8G8B8A8Unorm));
+    /// assert_eq!(Format::R8G8B8A8Unorm.linear_sibling(), None);
+    /// ```
+    pub fn linear_sibling(&self) -> Option<Format> {
+        match *self {
+            Format::R8Srgb => Some(Format::R8Unorm),
+            Format::R8G8Srgb => Some(Format::R8G8Unorm),
+            Format::R8G8B8Srgb => Some(Format::R8G8B8Unorm),
+            Format::B8G8R8Srgb => Some(Format::B8G8R8Unorm),
+            Format::R8G8B8A8Srgb => Some(Format::R8G8B8A8Unorm),
+            Format::B8G8R8A8Srgb => Some(Format::B8G8R8A8Unorm),
+            Format::A8B8G8R8SrgbPack32 => Some(Format::A8B8G8R8UnormPack32),
+            Format::BC1_RGBSrgbBlock => Some(Format::BC1_RGBUnormBlock),
+            Format::BC1_RGBASrgbBlock => Some(Format::BC1_RGBAUnormBlock),
+            Format::BC2SrgbBlock => Some(Format::BC2UnormBlock),
+            Format::BC3SrgbBlock => Some(Format::BC3UnormBlock),
+            Format::BC7SrgbBlock => Some(Format::BC7UnormBlock),
+            Format::ETC2_R8G8B8SrgbBlock => Some(Format::ETC2_R8G8B8UnormBlock),
+            Format::ETC2_R8G8B8A1SrgbBlock => Some(Format::ETC2_R8G8B8A1UnormBlock),
+            Format::ETC2_R8G8B8A8SrgbBlock => Some(Format::ETC2_R8G8B8A8UnormBlock),
+            Format::ASTC_4x4SrgbBlock => Some(Format::ASTC_4x4UnormBlock),
+            Format::ASTC_5x4SrgbBlock => Some(Format::ASTC_5x4UnormBlock),
+            Format::ASTC_5x5SrgbBlock => Some(Format::ASTC_5x5UnormBlock),
+            Format::ASTC_6x5SrgbBlock => Some(Format::ASTC_6x5UnormBlock),
+            Format::ASTC_6x6SrgbBlock => Some(Format::ASTC_6x6UnormBlock),
+            Format::ASTC_8x5SrgbBlock => Some(Format::ASTC_8x5UnormBlock),
+            Format::ASTC_8x6SrgbBlock => Some(Format::ASTC_8x6UnormBlock),
+            Format::ASTC_8x8SrgbBlock => Some(Format::ASTC_8x8UnormBlock),
+            Format::ASTC_10x5SrgbBlock => Some(Format::ASTC_10x5UnormBlock),
+            Format::ASTC_10x6SrgbBlock => Some(Format::ASTC_10x6UnormBlock),
+            Format::ASTC_10x8SrgbBlock => Some(Format::ASTC_10x8UnormBlock),
+            Format::ASTC_10x10SrgbBlock => Some(Format::ASTC_10x10UnormBlock),
+            Format::ASTC_12x10SrgbBlock => Some(Format::ASTC_12x10UnormBlock),
+            Format::ASTC_12x12SrgbBlock => Some(Format::ASTC_12x12UnormBlock),
+            _ => None,
+        }
+    }
+
+    /// Returns true if images of this format can be sampled with anisotropic filtering.
+    ///
+    /// Anisotropic filtering only makes sense for formats that can be linearly filtered. Integer
+    /// formats and depth/stencil formats can't, and samplers with a YCbCr conversion must not
+    /// enable anisotropy. This doesn't check whether the device supports the
+    /// `sampler_anisotropy` feature or linear filtering of the format.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// assert!(Format::R8G8B8A8Unorm.supports_anisotropy());
+    /// assert!(!Format::D32Sfloat.supports_anisotropy());
+    /// ```
+    pub fn supports_anisotropy(&self) -> bool {
+        match self.ty() {
+            FormatTy::Float | FormatTy::Compressed => true,
+            FormatTy::Uint
+            | FormatTy::Sint
+            | FormatTy::Depth
+            | FormatTy::Stencil
+            | FormatTy::DepthStencil
+            | FormatTy::Ycbcr => false,
+        }
+    }
+
+    /// Returns true if the format is one of the packed unsigned float formats used for compact
+    /// HDR color, `B10G11R11UfloatPack32` and `E5B9G9R9UfloatPack32`.
+    ///
+    /// `E5B9G9R9UfloatPack32` shares its exponent between the three channels, and most devices
+    /// can't use it as a color attachment or a storage image.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// assert!(Format::B10G11R11UfloatPack32.is_hdr_packed());
+    /// assert!(!Format::R16G16B16A16Sfloat.is_hdr_packed());
+    /// ```
+    #[inline]
+    pub fn is_hdr_packed(&self) -> bool {
+        matches!(
+            *self,
+            Format::B10G11R11UfloatPack32 | Format::E5B9G9R9UfloatPack32
+        )
+    }
+
+    /// Returns the aspects that an image of this format has.
+    ///
+    /// Multi-planar formats have a color aspect, plus one aspect for each of their planes.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// use vulkano::image::ImageAspect;
+    /// assert_eq!(
+    ///     Format::D24Unorm_S8Uint.aspects(),
+    ///     ImageAspect { depth: true, stencil: true, ..ImageAspect::none() },
+    /// );
+    /// ```
+    pub fn aspects(&self) -> ImageAspect {
+        match self.ty() {
+            FormatTy::Float | FormatTy::Uint | FormatTy::Sint | FormatTy::Compressed => {
+                ImageAspect {
+                    color: true,
+                    ..ImageAspect::none()
+                }
+            }
+            FormatTy::Depth => ImageAspect {
+                depth: true,
+                ..ImageAspect::none()
+            },
+            FormatTy::Stencil => ImageAspect {
+                stencil: true,
+                ..ImageAspect::none()
+            },
+            FormatTy::DepthStencil => ImageAspect {
+                depth: true,
+                stencil: true,
+                ..ImageAspect::none()
+            },
+            FormatTy::Ycbcr => ImageAspect {
+                color: true,
+                plane0: true,
+                plane1: true,
+                plane2: *self == Format::G8B8R8_3PLANE420Unorm,
+                ..ImageAspect::none()
+            },
+        }
+    }
+
+    /// Returns the size in bytes of a texel block of this format, including every aspect.
+    ///
+    /// This is meant for memory accounting. For most formats it is the same as `size`, which is
+    /// the size of the data exchanged by copies. Copies of depth/stencil formats are done one
+    /// aspect at a time, so `size` returns `None` for them, while this function returns the
+    /// combined size of the depth and stencil components, including padding.
+    ///
+    /// Returns `None` if the format has no texel block size, which is the case of YCbCr formats.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// assert_eq!(Format::D24Unorm_S8Uint.total_texel_size(), Some(4));
+    /// assert_eq!(Format::D32Sfloat_S8Uint.total_texel_size(), Some(5));
+    /// assert_eq!(Format::R8G8B8A8Unorm.total_texel_size(), Some(4));
+    /// ```
+    pub fn total_texel_size(&self) -> Option<usize> {
+        match *self {
+            Format::D16Unorm_S8Uint => Some(3),
+            Format::D24Unorm_S8Uint => Some(4),
+            Format::D32Sfloat_S8Uint => Some(5),
+            _ => self.size(),
+        }
+    }
+
+    /// Returns the number of bits of each component of this format, in the order in which the
+    /// components appear in the name of the format.
+    ///
+    /// For depth/stencil formats, the components are the depth and stencil values, and the unused
+    /// bits of `X8_D24UnormPack32` are not included. Block-compressed formats don't store their
+    /// components individually and return an empty list.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// assert_eq!(&Format::R5G6B5UnormPack16.component_bits()[..], &[5, 6, 5]);
+    /// assert_eq!(&Format::A2B10G10R10UnormPack32.component_bits()[..], &[2, 10, 10, 10]);
+    /// ```
+    pub fn component_bits(&self) -> SmallVec<[u8; 4]> {
+        let bits: &[u8] = match *self {
+            Format::R4G4UnormPack8 => &[4, 4],
+            Format::R4G4B4A4UnormPack16 | Format::B4G4R4A4UnormPack16 => &[4, 4, 4, 4],
+            Format::R5G6B5UnormPack16 | Format::B5G6R5UnormPack16 => &[5, 6, 5],
+            Format::R5G5B5A1UnormPack16 | Format::B5G5R5A1UnormPack16 => &[5, 5, 5, 1],
+            Format::A1R5G5B5UnormPack16 => &[1, 5, 5, 5],
+            Format::R8Unorm
+            | Format::R8Snorm
+            | Format::R8Uscaled
+            | Format::R8Sscaled
+            | Format::R8Uint
+            | Format::R8Sint
+            | Format::R8Srgb
+            | Format::S8Uint => &[8],
+            Format::R8G8Unorm
+            | Format::R8G8Snorm
+            | Format::R8G8Uscaled
+            | Format::R8G8Sscaled
+            | Format::R8G8Uint
+            | Format::R8G8Sint
+            | Format::R8G8Srgb => &[8, 8],
+            Format::R8G8B8Unorm
+            | Format::R8G8B8Snorm
+            | Format::R8G8B8Uscaled
+            | Format::R8G8B8Sscaled
+            | Format::R8G8B8Uint
+            | Format::R8G8B8Sint
+            | Format::R8G8B8Srgb
+            | Format::B8G8R8Unorm
+            | Format::B8G8R8Snorm
+            | Format::B8G8R8Uscaled
+            | Format::B8G8R8Sscaled
+            | Format::B8G8R8Uint
+            | Format::B8G8R8Sint
+            | Format::B8G8R8Srgb
+            | Format::G8B8R8_3PLANE420Unorm
+            | Format::G8B8R8_2PLANE420Unorm => &[8, 8, 8],
+            Format::R8G8B8A8Unorm
+            | Format::R8G8B8A8Snorm
+            | Format::R8G8B8A8Uscaled
+            | Format::R8G8B8A8Sscaled
+            | Format::R8G8B8A8Uint
+            | Format::R8G8B8A8Sint
+            | Format::R8G8B8A8Srgb
+            | Format::B8G8R8A8Unorm
+            | Format::B8G8R8A8Snorm
+            | Format::B8G8R8A8Uscaled
+            | Format::B8G8R8A8Sscaled
+            | Format::B8G8R8A8Uint
+            | Format::B8G8R8A8Sint
+            | Format::B8G8R8A8Srgb
+            | Format::A8B8G8R8UnormPack32
+            | Format::A8B8G8R8SnormPack32
+            | Format::A8B8G8R8UscaledPack32
+            | Format::A8B8G8R8SscaledPack32
+            | Format::A8B8G8R8UintPack32
+            | Format::A8B8G8R8SintPack32
+            | Format::A8B8G8R8SrgbPack32 => &[8, 8, 8, 8],
+            Format::A2R10G10B10UnormPack32
+            | Format::A2R10G10B10SnormPack32
+            | Format::A2R10G10B10UscaledPack32
+            | Format::A2R10G10B10SscaledPack32
+            | Format::A2R10G10B10UintPack32
+            | Format::A2R10G10B10SintPack32
+            | Format::A2B10G10R10UnormPack32
+            | Format::A2B10G10R10SnormPack32
+            | Format::A2B10G10R10UscaledPack32
+            | Format::A2B10G10R10SscaledPack32
+            | Format::A2B10G10R10UintPack32
+            | Format::A2B10G10R10SintPack32 => &[2, 10, 10, 10],
+            Format::R16Unorm
+            | Format::R16Snorm
+            | Format::R16Uscaled
+            | Format::R16Sscaled
+            | Format::R16Uint
+            | Format::R16Sint
+            | Format::R16Sfloat
+            | Format::D16Unorm => &[16],
+            Format::R16G16Unorm
+            | Format::R16G16Snorm
+            | Format::R16G16Uscaled
+            | Format::R16G16Sscaled
+            | Format::R16G16Uint
+            | Format::R16G16Sint
+            | Format::R16G16Sfloat => &[16, 16],
+            Format::R16G16B16Unorm
+            | Format::R16G16B16Snorm
+            | Format::R16G16B16Uscaled
+            | Format::R16G16B16Sscaled
+            | Format::R16G16B16Uint
+            | Format::R16G16B16Sint
+            | Format::R16G16B16Sfloat => &[16, 16, 16],
+            Format::R16G16B16A16Unorm
+            | Format::R16G16B16A16Snorm
+            | Format::R16G16B16A16Uscaled
+            | Format::R16G16B16A16Sscaled
+            | Format::R16G16B16A16Uint
+            | Format::R16G16B16A16Sint
+            | Format::R16G16B16A16Sfloat => &[16, 16, 16, 16],
+            Format::R32Uint | Format::R32Sint | Format::R32Sfloat | Format::D32Sfloat => &[32],
+            Format::R32G32Uint | Format::R32G32Sint | Format::R32G32Sfloat => &[32, 32],
+            Format::R32G32B32Uint | Format::R32G32B32Sint | Format::R32G32B32Sfloat => {
+                &[32, 32, 32]
+            }
+            Format::R32G32B32A32Uint | Format::R32G32B32A32Sint | Format::R32G32B32A32Sfloat => {
+                &[32, 32, 32, 32]
+            }
+            Format::R64Uint | Format::R64Sint | Format::R64Sfloat => &[64],
+            Format::R64G64Uint | Format::R64G64Sint | Format::R64G64Sfloat => &[64, 64],
+            Format::R64G64B64Uint | Format::R64G64B64Sint | Format::R64G64B64Sfloat => {
+                &[64, 64, 64]
+            }
+            Format::R64G64B64A64Uint | Format::R64G64B64A64Sint | Format::R64G64B64A64Sfloat => {
+                &[64, 64, 64, 64]
+            }
+            Format::B10G11R11UfloatPack32 => &[10, 11, 11],
+            Format::E5B9G9R9UfloatPack32 => &[5, 9, 9, 9],
+            Format::X8_D24UnormPack32 => &[24],
+            Format::D16Unorm_S8Uint => &[16, 8],
+            Format::D24Unorm_S8Uint => &[24, 8],
+            Format::D32Sfloat_S8Uint => &[32, 8],
+            Format::BC1_RGBUnormBlock
+            | Format::BC1_RGBSrgbBlock
+            | Format::BC1_RGBAUnormBlock
+            | Format::BC1_RGBASrgbBlock
+            | Format::BC2UnormBlock
+            | Format::BC2SrgbBlock
+            | Format::BC3UnormBlock
+            | Format::BC3SrgbBlock
+            | Format::BC4UnormBlock
+            | Format::BC4SnormBlock
+            | Format::BC5UnormBlock
+            | Format::BC5SnormBlock
+            | Format::BC6HUfloatBlock
+            | Format::BC6HSfloatBlock
+            | Format::BC7UnormBlock
+            | Format::BC7SrgbBlock
+            | Format::ETC2_R8G8B8UnormBlock
+            | Format::ETC2_R8G8B8SrgbBlock
+            | Format::ETC2_R8G8B8A1UnormBlock
+            | Format::ETC2_R8G8B8A1SrgbBlock
+            | Format::ETC2_R8G8B8A8UnormBlock
+            | Format::ETC2_R8G8B8A8SrgbBlock
+            | Format::EAC_R11UnormBlock
+            | Format::EAC_R11SnormBlock
+            | Format::EAC_R11G11UnormBlock
+            | Format::EAC_R11G11SnormBlock
+            | Format::ASTC_4x4UnormBlock
+            | Format::ASTC_4x4SrgbBlock
+            | Format::ASTC_5x4UnormBlock
+            | Format::ASTC_5x4SrgbBlock
+            | Format::ASTC_5x5UnormBlock
+            | Format::ASTC_5x5SrgbBlock
+            | Format::ASTC_6x5UnormBlock
+            | Format::ASTC_6x5SrgbBlock
+            | Format::ASTC_6x6UnormBlock
+            | Format::ASTC_6x6SrgbBlock
+            | Format::ASTC_8x5UnormBlock
+            | Format::ASTC_8x5SrgbBlock
+            | Format::ASTC_8x6UnormBlock
+            | Format::ASTC_8x6SrgbBlock
+            | Format::ASTC_8x8UnormBlock
+            | Format::ASTC_8x8SrgbBlock
+            | Format::ASTC_10x5UnormBlock
+            | Format::ASTC_10x5SrgbBlock
+            | Format::ASTC_10x6UnormBlock
+            | Format::ASTC_10x6SrgbBlock
+            | Format::ASTC_10x8UnormBlock
+            | Format::ASTC_10x8SrgbBlock
+            | Format::ASTC_10x10UnormBlock
+            | Format::ASTC_10x10SrgbBlock
+            | Format::ASTC_12x10UnormBlock
+            | Format::ASTC_12x10SrgbBlock
+            | Format::ASTC_12x12UnormBlock
+            | Format::ASTC_12x12SrgbBlock => &[],
+        };
+
+        SmallVec::from_slice(bits)
+    }
+
+    /// Returns how the texel blocks of this format map to the texel blocks of `other` if an image
+    /// of this format can be copied to an image of `other` by reinterpreting its data, or `None`
+    /// otherwise.
+    ///
+    /// Such a copy is possible if the texel blocks of both formats have the same size in bytes.
+    /// This makes it possible to copy a compressed image to an uncompressed image whose texels
+    /// have the size of a compressed block, or the other way around. Two compressed formats are
+    /// only compatible if they only differ by their color space, and depth/stencil formats are
+    /// only compatible with themselves.
+    ///
+    /// ```
+    /// use vulkano::format::{BlockRatio, Format};
+    /// assert_eq!(
+    ///     Format::BC1_RGBUnormBlock.copy_compatible_with(Format::R16G16B16A16Uint),
+    ///     Some(BlockRatio { src_block: (4, 4), dst_block: (1, 1) }),
+    /// );
+    /// ```
+    pub fn copy_compatible_with(&self, other: Format) -> Option<BlockRatio> {
+        let ratio = BlockRatio {
+            src_block: self.block_dimensions(),
+            dst_block: other.block_dimensions(),
+        };
+
+        if *self == other {
+            return Some(ratio);
+        }
+
+        match (self.ty(), other.ty()) {
+            (FormatTy::Depth, _)
+            | (FormatTy::Stencil, _)
+            | (FormatTy::DepthStencil, _)
+            | (FormatTy::Ycbcr, _)
+            | (_, FormatTy::Depth)
+            | (_, FormatTy::Stencil)
+            | (_, FormatTy::DepthStencil)
+            | (_, FormatTy::Ycbcr) => return None,
+            (FormatTy::Compressed, FormatTy::Compressed)
+                if self.linear_sibling().unwrap_or(*self)
+                    != other.linear_sibling().unwrap_or(other) =>
+            {
+                return None
+            }
+            _ => (),
+        }
+
+        match (self.size(), other.size()) {
+            (Some(src_size), Some(dst_size)) if src_size == dst_size => Some(ratio),
+            _ => None,
+        }
+    }
+
+    /// Returns true if an image of this format can be blitted to an image of `other`.
+    ///
+    /// Unsigned integer formats can only be blitted to unsigned integer formats, and signed
+    /// integer formats to signed integer formats. Other color formats, such as floating-point and
+    /// normalized formats, can be blitted between each other. Depth/stencil formats can only be
+    /// blitted to the same format.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// assert!(Format::R8G8B8A8Unorm.blit_compatible_with(Format::R16G16B16A16Sfloat));
+    /// assert!(!Format::R8G8B8A8Uint.blit_compatible_with(Format::R16G16B16A16Sfloat));
+    /// ```
+    pub fn blit_compatible_with(&self, other: Format) -> bool {
+        match (self.ty(), other.ty()) {
+            (FormatTy::Uint, FormatTy::Uint) | (FormatTy::Sint, FormatTy::Sint) => true,
+            (FormatTy::Uint, _) | (_, FormatTy::Uint) => false,
+            (FormatTy::Sint, _) | (_, FormatTy::Sint) => false,
+            (a, b) if a.is_depth_and_or_stencil() || b.is_depth_and_or_stencil() => *self == other,
+            _ => true,
+        }
+    }
+
+    /// Returns true if an image of this format with linear tiling can have the given usage on
+    /// `device`.
+    ///
+    /// Linear tiling, which is needed to map an image in CPU memory, supports far fewer features
+    /// than optimal tiling. For example, many implementations can't use a linear image as a color
+    /// attachment.
+    pub fn linear_tiling_supports(&self, usage: ImageUsage, device: &Device) -> bool {
+        let features = self
+            .properties(device.physical_device())
+            .linear_tiling_features;
+        features.supports_image_usage(usage, device.loaded_extensions().khr_maintenance1)
+    }
+
+    /// Returns true if an image of this format can be the destination of a multisample resolve
+    /// on `device`. If `depth` is true, checks whether the image can be a depth/stencil resolve
+    /// attachment, otherwise whether it can be a color resolve attachment.
+    ///
+    /// A resolve attachment must support being used as an attachment with optimal tiling.
+    /// Depth/stencil resolve additionally requires the `khr_depth_stencil_resolve` extension or
+    /// Vulkan 1.2, see `Device::supported_depth_resolve_modes`.
+    ///
+    /// `RenderPass::new` uses this to check the resolve attachments of each subpass. These are
+    /// always color resolve attachments, as render pass descriptions can't contain a
+    /// depth/stencil resolve attachment yet.
+    pub fn supports_resolve(&self, device: &Device, depth: bool) -> bool {
+        let features = self
+            .properties(device.physical_device())
+            .optimal_tiling_features;
+
+        if depth {
+            let aspects = self.aspects();
+            (aspects.depth || aspects.stencil)
+                && features.depth_stencil_attachment
+                && (!aspects.depth || device.supported_depth_resolve_modes().is_some())
+                && (!aspects.stencil || device.supported_stencil_resolve_modes().is_some())
+        } else {
+            features.color_attachment
+        }
+    }
+
+    /// Returns true if an image of this format with optimal tiling can have the given usage on
+    /// `device`.
+    ///
+    /// This is mostly useful for compressed formats, whose support varies a lot between
+    /// implementations. See also `supported_or_fallback`.
+    pub fn is_supported(&self, usage: ImageUsage, device: &Device) -> bool {
+        let features = self
+            .properties(device.physical_device())
+            .optimal_tiling_features;
+        features.supports_image_usage(usage, device.loaded_extensions().khr_maintenance1)
+    }
+
+    /// Returns this format if an image with optimal tiling can have the given usage with it on
+    /// `device`, and `transcode_fallback(self)` otherwise.
+    ///
+    /// The returned boolean is true if the fallback was chosen and the data must therefore be
+    /// decompressed on the CPU before being uploaded. The fallback itself isn't checked, but it is
+    /// always one of the widely supported uncompressed formats.
+    pub fn supported_or_fallback(&self, usage: ImageUsage, device: &Device) -> (Format, bool) {
+        if self.is_supported(usage, device) {
+            return (*self, false);
+        }
+
+        let fallback = transcode_fallback(*self);
+        (fallback, fallback != *self)
+    }
+
+    /// Returns every format known to vulkano, in the order of declaration.
+    ///
+    /// Not all of them are necessarily supported by a given device. Use
+    /// `Device::supported_formats` to only get the ones that can be used for a given usage.
+    #[inline]
+    pub fn all() -> impl Iterator<Item = Format> {
+        Format::ALL.iter().cloned()
+    }
+
+    /// Returns the formats that views of a `mutable_format` image of this format may use, in
+    /// other words the formats of the same compatibility class. The format itself is included.
+    ///
+    /// Uncompressed color formats are compatible with all the uncompressed color formats whose
+    /// texels have the same size. Compressed formats are compatible with the formats that use the
+    /// same compression scheme, whatever their numeric format. Depth/stencil and YCbCr formats
+    /// are only compatible with themselves.
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// let formats: Vec<_> = Format::BC1_RGBUnormBlock.compatible_view_formats().collect();
+    /// assert_eq!(formats, [Format::BC1_RGBUnormBlock, Format::BC1_RGBSrgbBlock]);
+    /// ```
+    pub fn compatible_view_formats(&self) -> impl Iterator<Item = Format> {
+        let class = self.compatibility_class();
+        Format::all().filter(move |format| format.compatibility_class() == class)
+    }
+
+    /// Returns the `image` crate color type whose pixels have the same memory layout as this
+    /// format, if any.
+    ///
+    /// Only the 8-bit normalized formats (linear or sRGB) and the 16-bit unsigned normalized
+    /// formats have an equivalent. One and two-component formats map to the luminance color
+    /// types.
+    #[cfg(feature = "image-interop")]
+    pub fn to_image_color_type(&self) -> Option<image::ColorType> {
+        use image::ColorType;
+
+        match *self {
+            Format::R8Unorm | Format::R8Srgb => Some(ColorType::L8),
+            Format::R8G8Unorm | Format::R8G8Srgb => Some(ColorType::La8),
+            Format::R8G8B8Unorm | Format::R8G8B8Srgb => Some(ColorType::Rgb8),
+            Format::R8G8B8A8Unorm | Format::R8G8B8A8Srgb => Some(ColorType::Rgba8),
+            Format::B8G8R8Unorm | Format::B8G8R8Srgb => Some(ColorType::Bgr8),
+            Format::B8G8R8A8Unorm | Format::B8G8R8A8Srgb => Some(ColorType::Bgra8),
+            Format::R16Unorm => Some(ColorType::L16),
+            Format::R16G16Unorm => Some(ColorType::La16),
+            Format::R16G16B16Unorm => Some(ColorType::Rgb16),
+            Format::R16G16B16A16Unorm => Some(ColorType::Rgba16),
+            _ => None,
+        }
+    }
+
+    /// Returns the format whose texels have the same memory layout as pixels of the given `image`
+    /// crate color type.
+    ///
+    /// If `srgb` is true, the sRGB variant of the format is returned. Vulkan has no 16-bit sRGB
+    /// formats, so `None` is returned for 16-bit color types in that case.
+    #[cfg(feature = "image-interop")]
+    pub fn from_image_color_type(color_type: image::ColorType, srgb: bool) -> Option<Format> {
+        use image::ColorType;
+
+        let format = match color_type {
+            ColorType::L8 => Format::R8Unorm,
+            ColorType::La8 => Format::R8G8Unorm,
+            ColorType::Rgb8 => Format::R8G8B8Unorm,
+            ColorType::Rgba8 => Format::R8G8B8A8Unorm,
+            ColorType::Bgr8 => Format::B8G8R8Unorm,
+            ColorType::Bgra8 => Format::B8G8R8A8Unorm,
+            ColorType::L16 => Format::R16Unorm,
+            ColorType::La16 => Format::R16G16Unorm,
+            ColorType::Rgb16 => Format::R16G16B16Unorm,
+            ColorType::Rgba16 => Format::R16G16B16A16Unorm,
+            _ => return None,
+        };
+
+        if srgb {
+            Format::all().find(|f| f.linear_sibling() == Some(format))
+        } else {
+            Some(format)
+        }
+    }
+
+    // Returns a value that is the same for all the formats of a compatibility class.
+    fn compatibility_class(&self) -> CompatibilityClass {
+        match self.ty() {
+            FormatTy::Float | FormatTy::Uint | FormatTy::Sint => match self.size() {
+                Some(size) => CompatibilityClass::Color(size),
+                None => CompatibilityClass::Unique(*self),
+            },
+            FormatTy::Compressed => {
+                CompatibilityClass::Compressed(match self.linear_sibling().unwrap_or(*self) {
+                    Format::BC4SnormBlock => Format::BC4UnormBlock,
+                    Format::BC5SnormBlock => Format::BC5UnormBlock,
+                    Format::BC6HSfloatBlock => Format::BC6HUfloatBlock,
+                    Format::EAC_R11SnormBlock => Format::EAC_R11UnormBlock,
+                    Format::EAC_R11G11SnormBlock => Format::EAC_R11G11UnormBlock,
+                    format => format,
+                })
+            }
+            FormatTy::Depth | FormatTy::Stencil | FormatTy::DepthStencil | FormatTy::Ycbcr => {
+                CompatibilityClass::Unique(*self)
+            }
+        }
+    }
+
+    /// Returns the range of values that the color, depth or stencil components of this format
+    /// can represent, as seen by shaders.
+    ///
+    /// For formats whose components don't all have the same number of bits, the range of the
+    /// largest color component is returned. `integer` is true for `Uint` and `Sint` formats,
+    /// whose components are read as integers rather than floating-point values.
+    ///
+    /// Returns `None` for combined depth/stencil formats, whose two aspects have different
+    /// ranges.
+    ///
+    /// ```
+    /// use vulkano::format::{ChannelRange, Format};
+    /// assert_eq!(
+    ///     Format::R8G8B8A8Snorm.channel_range(),
+    ///     Some(ChannelRange { min: -1.0, max: 1.0, integer: false }),
+    /// );
+    /// ```
+    pub fn channel_range(&self) -> Option<ChannelRange> {
+        match *self {
+            Format::R4G4UnormPack8
+            | Format::R4G4B4A4UnormPack16
+            | Format::B4G4R4A4UnormPack16
+            | Format::R5G6B5UnormPack16
+            | Format::B5G6R5UnormPack16
+            | Format::R5G5B5A1UnormPack16
+            | Format::B5G5R5A1UnormPack16
+            | Format::A1R5G5B5UnormPack16
+            | Format::R8Unorm
+            | Format::R8Srgb
+            | Format::R8G8Unorm
+            | Format::R8G8Srgb
+            | Format::R8G8B8Unorm
+            | Format::R8G8B8Srgb
+            | Format::B8G8R8Unorm
+            | Format::B8G8R8Srgb
+            | Format::R8G8B8A8Unorm
+            | Format::R8G8B8A8Srgb
+            | Format::B8G8R8A8Unorm
+            | Format::B8G8R8A8Srgb
+            | Format::A8B8G8R8UnormPack32
+            | Format::A8B8G8R8SrgbPack32
+            | Format::A2R10G10B10UnormPack32
+            | Format::A2B10G10R10UnormPack32
+            | Format::R16Unorm
+            | Format::R16G16Unorm
+            | Format::R16G16B16Unorm
+            | Format::R16G16B16A16Unorm
+            | Format::D16Unorm
+            | Format::X8_D24UnormPack32
+            | Format::BC1_RGBUnormBlock
+            | Format::BC1_RGBSrgbBlock
+            | Format::BC1_RGBAUnormBlock
+            | Format::BC1_RGBASrgbBlock
+            | Format::BC2UnormBlock
+            | Format::BC2SrgbBlock
+            | Format::BC3UnormBlock
+            | Format::BC3SrgbBlock
+            | Format::BC4UnormBlock
+            | Format::BC5UnormBlock
+            | Format::BC7UnormBlock
+            | Format::BC7SrgbBlock
+            | Format::ETC2_R8G8B8UnormBlock
+            | Format::ETC2_R8G8B8SrgbBlock
+            | Format::ETC2_R8G8B8A1UnormBlock
+            | Format::ETC2_R8G8B8A1SrgbBlock
+            | Format::ETC2_R8G8B8A8UnormBlock
+            | Format::ETC2_R8G8B8A8SrgbBlock
+            | Format::EAC_R11UnormBlock
+            | Format::EAC_R11G11UnormBlock
+            | Format::ASTC_4x4UnormBlock
+            | Format::ASTC_4x4SrgbBlock
+            | Format::ASTC_5x4UnormBlock
+            | Format::ASTC_5x4SrgbBlock
+            | Format::ASTC_5x5UnormBlock
+            | Format::ASTC_5x5SrgbBlock
+            | Format::ASTC_6x5UnormBlock
+            | Format::ASTC_6x5SrgbBlock
+            | Format::ASTC_6x6UnormBlock
+            | Format::ASTC_6x6SrgbBlock
+            | Format::ASTC_8x5UnormBlock
+            | Format::ASTC_8x5SrgbBlock
+            | Format::ASTC_8x6UnormBlock
+            | Format::ASTC_8x6SrgbBlock
+            | Format::ASTC_8x8UnormBlock
+            | Format::ASTC_8x8SrgbBlock
+            | Format::ASTC_10x5UnormBlock
+            | Format::ASTC_10x5SrgbBlock
+            | Format::ASTC_10x6UnormBlock
+            | Format::ASTC_10x6SrgbBlock
+            | Format::ASTC_10x8UnormBlock
+            | Format::ASTC_10x8SrgbBlock
+            | Format::ASTC_10x10UnormBlock
+            | Format::ASTC_10x10SrgbBlock
+            | Format::ASTC_12x10UnormBlock
+            | Format::ASTC_12x10SrgbBlock
+            | Format::ASTC_12x12UnormBlock
+            | Format::ASTC_12x12SrgbBlock
+            | Format::G8B8R8_3PLANE420Unorm
+            | Format::G8B8R8_2PLANE420Unorm => Some(ChannelRange {
+                min: 0.0,
+                max: 1.0,
+                integer: false,
+            }),
+            Format::R8Snorm
+            | Format::R8G8Snorm
+            | Format::R8G8B8Snorm
+            | Format::B8G8R8Snorm
+            | Format::R8G8B8A8Snorm
+            | Format::B8G8R8A8Snorm
+            | Format::A8B8G8R8SnormPack32
+            | Format::A2R10G10B10SnormPack32
+            | Format::A2B10G10R10SnormPack32
+            | Format::R16Snorm
+            | Format::R16G16Snorm
+            | Format::R16G16B16Snorm
+            | Format::R16G16B16A16Snorm
+            | Format::BC4SnormBlock
+            | Format::BC5SnormBlock
+            | Format::EAC_R11SnormBlock
+            | Format::EAC_R11G11SnormBlock => Some(ChannelRange {
+                min: -1.0,
+                max: 1.0,
+                integer: false,
+            }),
+            Format::R8Uscaled
+            | Format::R8G8Uscaled
+            | Format::R8G8B8Uscaled
+            | Format::B8G8R8Uscaled
+            | Format::R8G8B8A8Uscaled
+            | Format::B8G8R8A8Uscaled
+            | Format::A8B8G8R8UscaledPack32 => Some(ChannelRange {
+                min: 0.0,
+                max: 255.0,
+                integer: false,
+            }),
+            Format::R8Sscaled
+            | Format::R8G8Sscaled
+            | Format::R8G8B8Sscaled
+            | Format::B8G8R8Sscaled
+            | Format::R8G8B8A8Sscaled
+            | Format::B8G8R8A8Sscaled
+            | Format::A8B8G8R8SscaledPack32 => Some(ChannelRange {
+                min: -128.0,
+                max: 127.0,
+                integer: false,
+            }),
+            Format::R8Uint
+            | Format::R8G8Uint
+            | Format::R8G8B8Uint
+            | Format::B8G8R8Uint
+            | Format::R8G8B8A8Uint
+            | Format::B8G8R8A8Uint
+            | Format::A8B8G8R8UintPack32
+            | Format::S8Uint => Some(ChannelRange {
+                min: 0.0,
+                max: 255.0,
+                integer: true,
+            }),
+            Format::R8Sint
+            | Format::R8G8Sint
+            | Format::R8G8B8Sint
+            | Format::B8G8R8Sint
+            | Format::R8G8B8A8Sint
+            | Format::B8G8R8A8Sint
+            | Format::A8B8G8R8SintPack32 => Some(ChannelRange {
+                min: -128.0,
+                max: 127.0,
+                integer: true,
+            }),
+            Format::A2R10G10B10UscaledPack32 | Format::A2B10G10R10UscaledPack32 => {
+                Some(ChannelRange {
+                    min: 0.0,
+                    max: 1023.0,
+                    integer: false,
+                })
+            }
+            Format::A2R10G10B10SscaledPack32 | Format::A2B10G10R10SscaledPack32 => {
+                Some(ChannelRange {
+                    min: -512.0,
+                    max: 511.0,
+                    integer: false,
+                })
+            }
+            Format::A2R10G10B10UintPack32 | Format::A2B10G10R10UintPack32 => Some(ChannelRange {
+                min: 0.0,
+                max: 1023.0,
+                integer: true,
+            }),
+            Format::A2R10G10B10SintPack32 | Format::A2B10G10R10SintPack32 => Some(ChannelRange {
+                min: -512.0,
+                max: 511.0,
+                integer: true,
+            }),
+            Format::R16Uscaled
+            | Format::R16G16Uscaled
+            | Format::R16G16B16Uscaled
+            | Format::R16G16B16A16Uscaled => Some(ChannelRange {
+                min: 0.0,
+                max: 65535.0,
+                integer: false,
+            }),
+            Format::R16Sscaled
+            | Format::R16G16Sscaled
+            | Format::R16G16B16Sscaled
+            | Format::R16G16B16A16Sscaled => Some(ChannelRange {
+                min: -32768.0,
+                max: 32767.0,
+                integer: false,
+            }),
+            Format::R16Uint
+            | Format::R16G16Uint
+            | Format::R16G16B16Uint
+            | Format::R16G16B16A16Uint => Some(ChannelRange {
+                min: 0.0,
+                max: 65535.0,
+                integer: true,
+            }),
+            Format::R16Sint
+            | Format::R16G16Sint
+            | Format::R16G16B16Sint
+            | Format::R16G16B16A16Sint => Some(ChannelRange {
+                min: -32768.0,
+                max: 32767.0,
+                integer: true,
+            }),
+            Format::R16Sfloat
+            | Format::R16G16Sfloat
+            | Format::R16G16B16Sfloat
+            | Format::R16G16B16A16Sfloat => Some(ChannelRange {
+                min: -65504.0,
+                max: 65504.0,
+                integer: false,
+            }),
+            Format::R32Uint
+            | Format::R32G32Uint
+            | Format::R32G32B32Uint
+            | Format::R32G32B32A32Uint => Some(ChannelRange {
+                min: 0.0,
+                max: 4294967295.0,
+                integer: true,
+            }),
+            Format::R32Sint
+            | Format::R32G32Sint
+            | Format::R32G32B32Sint
+            | Format::R32G32B32A32Sint => Some(ChannelRange {
+                min: -2147483648.0,
+                max: 2147483647.0,
+                integer: true,
+            }),
+            Format::R32Sfloat
+            | Format::R32G32Sfloat
+            | Format::R32G32B32Sfloat
+            | Format::R32G32B32A32Sfloat
+            | Format::D32Sfloat => Some(ChannelRange {
+                min: -f32::MAX as f64,
+                max: f32::MAX as f64,
+                integer: false,
+            }),
+            Format::R64Uint
+            | Format::R64G64Uint
+            | Format::R64G64B64Uint
+            | Format::R64G64B64A64Uint => Some(ChannelRange {
+                min: 0.0,
+                max: u64::MAX as f64,
+                integer: true,
+            }),
+            Format::R64Sint
+            | Format::R64G64Sint
+            | Format::R64G64B64Sint
+            | Format::R64G64B64A64Sint => Some(ChannelRange {
+                min: i64::MIN as f64,
+                max: i64::MAX as f64,
+                integer: true,
+            }),
+            Format::R64Sfloat
+            | Format::R64G64Sfloat
+            | Format::R64G64B64Sfloat
+            | Format::R64G64B64A64Sfloat => Some(ChannelRange {
+                min: -f64::MAX,
+                max: f64::MAX,
+                integer: false,
+            }),
+            Format::B10G11R11UfloatPack32 => Some(ChannelRange {
+                min: 0.0,
+                max: 65024.0,
+                integer: false,
+            }),
+            Format::E5B9G9R9UfloatPack32 => Some(ChannelRange {
+                min: 0.0,
+                max: 65408.0,
+                integer: false,
+            }),
+            Format::D16Unorm_S8Uint | Format::D24Unorm_S8Uint | Format::D32Sfloat_S8Uint => None,
+            Format::BC6HUfloatBlock => Some(ChannelRange {
+                min: 0.0,
+                max: 65504.0,
+                integer: false,
+            }),
+            Format::BC6HSfloatBlock => Some(ChannelRange {
+                min: -65504.0,
+                max: 65504.0,
+                integer: false,
+            }),
+        }
+    }
+}
+
+/// Returns the uncompressed format that data of the `compressed` format should be decompressed to
+/// when the device doesn't support it.
+///
+/// The fallback keeps the number of components, the precision and the color space of the
+/// compressed format: for example BC7 falls back to `R8G8B8A8Unorm`, its sRGB variant to
+/// `R8G8B8A8Srgb`, BC5 to `R8G8Unorm` and BC6H to `R16G16B16A16Sfloat`. Three-component formats
+/// fall back to four-component ones, because three-component formats are rarely supported.
+///
+/// Formats that aren't compressed are returned as-is.
+///
+/// ```
+/// use vulkano::format::transcode_fallback;
+/// use vulkano::format::Format;
+/// assert_eq!(transcode_fallback(Format::BC7UnormBlock), Format::R8G8B8A8Unorm);
+/// assert_eq!(transcode_fallback(Format::R8G8B8A8Unorm), Format::R8G8B8A8Unorm);
+/// ```
+pub fn transcode_fallback(compressed: Format) -> Format {
+    match compressed {
+        Format::BC4UnormBlock => Format::R8Unorm,
+        Format::BC4SnormBlock => Format::R8Snorm,
+        Format::BC5UnormBlock => Format::R8G8Unorm,
+        Format::BC5SnormBlock => Format::R8G8Snorm,
+        Format::EAC_R11UnormBlock => Format::R16Unorm,
+        Format::EAC_R11SnormBlock => Format::R16Snorm,
+        Format::EAC_R11G11UnormBlock => Format::R16G16Unorm,
+        Format::EAC_R11G11SnormBlock => Format::R16G16Snorm,
+        Format::BC6HUfloatBlock | Format::BC6HSfloatBlock => Format::R16G16B16A16Sfloat,
+        format if format.ty() == FormatTy::Compressed => {
+            if format.is_srgb() {
+                Format::R8G8B8A8Srgb
+            } else {
+                Format::R8G8B8A8Unorm
+            }
+        }
+        format => format,
+    }
+}
+
 pub unsafe trait FormatDesc {
     type ClearValue;
 
@@ -691,16 +1951,11 @@ unsafe impl FormatDesc for Format {
     }
 
     fn decode_clear_value(&self, value: Self::ClearValue) -> ClearValue {
-        match (self.ty(), value) {
-            (FormatTy::Float, f @ ClearValue::Float(_)) => f,
-            (FormatTy::Compressed, f @ ClearValue::Float(_)) => f,
-            (FormatTy::Sint, f @ ClearValue::Int(_)) => f,
-            (FormatTy::Uint, f @ ClearValue::Uint(_)) => f,
-            (FormatTy::Depth, f @ ClearValue::Depth(_)) => f,
-            (FormatTy::Stencil, f @ ClearValue::Stencil(_)) => f,
-            (FormatTy::DepthStencil, f @ ClearValue::DepthStencil(_)) => f,
-            _ => panic!("Wrong clear value"),
+        if !value.matches_format(*self) {
+            panic!("Wrong clear value");
         }
+
+        value
     }
 }
 
@@ -809,6 +2064,49 @@ unsafe impl PossibleYcbcrFormatDesc for Format {
     }
 }
 
+/// A custom type that holds the data of a single texel block, or of a single texel for
+/// uncompressed formats.
+///
+/// Implementing this trait lets the type be used as a source of pixels for all the formats whose
+/// texel blocks have the same size as the type, for example to upload the contents of a
+/// `Vec<Rgba8>` with `ImmutableImage::from_iter`. The size is checked at runtime by
+/// `AcceptsPixels::ensure_accepts`.
+///
+/// ```
+/// use vulkano::format::{AcceptsPixels, Format, Pixel};
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Rgba8 {
+///     r: u8,
+///     g: u8,
+///     b: u8,
+///     a: u8,
+/// }
+///
+/// unsafe impl Pixel for Rgba8 {}
+///
+/// assert!(<Format as AcceptsPixels<Rgba8>>::ensure_accepts(&Format::R8G8B8A8Unorm).is_ok());
+/// assert!(<Format as AcceptsPixels<Rgba8>>::ensure_accepts(&Format::R8G8Unorm).is_err());
+/// ```
+///
+/// # Safety
+///
+/// The type must be `#[repr(C)]` or `#[repr(transparent)]` and must not contain any padding,
+/// since its bytes are copied as-is into the image.
+pub unsafe trait Pixel {}
+
+unsafe impl<T: Pixel> AcceptsPixels<T> for Format {
+    #[inline]
+    fn ensure_accepts(&self) -> Result<(), IncompatiblePixelsType> {
+        if self.size() == Some(mem::size_of::<T>()) {
+            Ok(())
+        } else {
+            Err(IncompatiblePixelsType)
+        }
+    }
+}
+
 macro_rules! impl_pixel {
     {$($ty:ty;)+} => {
         $(impl_pixel!(inner $ty);)*
@@ -816,6 +2114,8 @@ macro_rules! impl_pixel {
         $(impl_pixel!(inner [$ty; 2]);)*
         $(impl_pixel!(inner [$ty; 3]);)*
         $(impl_pixel!(inner [$ty; 4]);)*
+        $(impl_pixel!(inner [$ty; 8]);)*
+        $(impl_pixel!(inner [$ty; 16]);)*
         $(impl_pixel!(inner ($ty,));)*
         $(impl_pixel!(inner ($ty, $ty));)*
         $(impl_pixel!(inner ($ty, $ty, $ty));)*
@@ -869,6 +2169,88 @@ impl FormatTy {
     }
 }
 
+/// The order in which the color components of a format are stored.
+///
+/// Returned by `Format::channel_order`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ChannelOrder {
+    /// A single red component.
+    R,
+    /// Red, then green.
+    Rg,
+    /// Red, green, then blue.
+    Rgb,
+    /// Blue, green, then red.
+    Bgr,
+    /// Red, green, blue, then alpha.
+    Rgba,
+    /// Blue, green, red, then alpha.
+    Bgra,
+    /// Alpha, red, green, then blue.
+    Argb,
+    /// Alpha, blue, green, then red.
+    Abgr,
+    /// The format doesn't store color components in one of the orders above. This is the case of
+    /// depth, stencil, shared-exponent and YCbCr formats.
+    Other,
+}
+
+// Compatibility class of a format. See `Format::compatible_view_formats`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum CompatibilityClass {
+    // Uncompressed color formats, identified by the size of a texel.
+    Color(usize),
+    // Compressed formats, identified by one of the formats of the class.
+    Compressed(Format),
+    // Formats that are only compatible with themselves.
+    Unique(Format),
+}
+
+/// Range of values that the components of a format can represent.
+///
+/// See `Format::channel_range`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChannelRange {
+    /// The smallest representable value.
+    pub min: f64,
+    /// The largest representable value.
+    pub max: f64,
+    /// True if the components are read as integers by shaders.
+    pub integer: bool,
+}
+
+/// Mapping between the texel blocks of two formats whose data can be copied as-is from one to
+/// the other.
+///
+/// One texel block of the source format corresponds to one texel block of the destination
+/// format. See `Format::copy_compatible_with`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BlockRatio {
+    /// Width and height in texels of a texel block of the source format.
+    pub src_block: (u32, u32),
+    /// Width and height in texels of a texel block of the destination format.
+    pub dst_block: (u32, u32),
+}
+
+impl BlockRatio {
+    /// Converts an extent in texels of the source format to the extent in texels of the
+    /// destination format that covers the same texel blocks.
+    ///
+    /// Partial blocks at the edge of the source extent are counted as full blocks.
+    #[inline]
+    pub fn convert_extent(&self, extent: [u32; 3]) -> [u32; 3] {
+        let convert = |value: u32, src_block: u32, dst_block: u32| {
+            (value + src_block - 1) / src_block * dst_block
+        };
+
+        [
+            convert(extent[0], self.src_block.0, self.dst_block.0),
+            convert(extent[1], self.src_block.1, self.dst_block.1),
+            extent[2],
+        ]
+    }
+}
+
 /// Describes a uniform value that will be used to fill an image.
 // TODO: should have the same layout as `vk::ClearValue` for performance
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -889,6 +2271,71 @@ pub enum ClearValue {
     DepthStencil((f32, u32)),
 }
 
+impl ClearValue {
+    /// Returns true if this value can be used to clear an image or attachment of the given
+    /// format, in other words if the variant corresponds to the numeric type of the format.
+    ///
+    /// `ClearValue::None` doesn't match any format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::format::ClearValue;
+    /// use vulkano::format::Format;
+    ///
+    /// assert!(ClearValue::Float([0.0; 4]).matches_format(Format::R8G8B8A8Unorm));
+    /// assert!(!ClearValue::Uint([0; 4]).matches_format(Format::R8G8B8A8Unorm));
+    /// ```
+    #[inline]
+    pub fn matches_format(&self, format: Format) -> bool {
+        matches!(
+            (format.ty(), *self),
+            (FormatTy::Float, ClearValue::Float(_))
+                | (FormatTy::Compressed, ClearValue::Float(_))
+                | (FormatTy::Sint, ClearValue::Int(_))
+                | (FormatTy::Uint, ClearValue::Uint(_))
+                | (FormatTy::Depth, ClearValue::Depth(_))
+                | (FormatTy::Stencil, ClearValue::Stencil(_))
+                | (FormatTy::DepthStencil, ClearValue::DepthStencil(_))
+        )
+    }
+}
+
+impl From<ClearValue> for vk::ClearValue {
+    #[inline]
+    fn from(val: ClearValue) -> vk::ClearValue {
+        match val {
+            ClearValue::None => vk::ClearValue {
+                color: vk::ClearColorValue { float32: [0.0; 4] },
+            },
+            ClearValue::Float(val) => vk::ClearValue {
+                color: vk::ClearColorValue { float32: val },
+            },
+            ClearValue::Int(val) => vk::ClearValue {
+                color: vk::ClearColorValue { int32: val },
+            },
+            ClearValue::Uint(val) => vk::ClearValue {
+                color: vk::ClearColorValue { uint32: val },
+            },
+            ClearValue::Depth(val) => vk::ClearValue {
+                depthStencil: vk::ClearDepthStencilValue {
+                    depth: val,
+                    stencil: 0,
+                },
+            },
+            ClearValue::Stencil(val) => vk::ClearValue {
+                depthStencil: vk::ClearDepthStencilValue {
+                    depth: 0.0,
+                    stencil: val,
+                },
+            },
+            ClearValue::DepthStencil((depth, stencil)) => vk::ClearValue {
+                depthStencil: vk::ClearDepthStencilValue { depth, stencil },
+            },
+        }
+    }
+}
+
 // TODO: remove all these From implementations once they are no longer needed
 
 impl From<[f32; 1]> for ClearValue {
@@ -1082,6 +2529,24 @@ pub struct FormatFeatures {
 }
 
 impl FormatFeatures {
+    /// Returns true if an image whose format has these features can have the given usage.
+    ///
+    /// The transfer usages are only checked if `check_transfer` is true, since the corresponding
+    /// features only exist with `VK_KHR_maintenance1`.
+    pub(crate) fn supports_image_usage(&self, usage: ImageUsage, check_transfer: bool) -> bool {
+        if *self == FormatFeatures::default() {
+            return false;
+        }
+
+        !(usage.sampled && !self.sampled_image
+            || usage.storage && !self.storage_image
+            || usage.color_attachment && !self.color_attachment
+            || usage.depth_stencil_attachment && !self.depth_stencil_attachment
+            || usage.input_attachment && !(self.color_attachment || self.depth_stencil_attachment)
+            || check_transfer && usage.transfer_source && !self.transfer_src
+            || check_transfer && usage.transfer_destination && !self.transfer_dst)
+    }
+
     #[inline]
     #[rustfmt::skip]
     pub(crate) fn from_bits(val: u32) -> FormatFeatures {
@@ -1115,3 +2580,412 @@ impl FormatFeatures {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::format::AcceptsPixels;
+    use crate::format::BlockRatio;
+    use crate::format::ChannelRange;
+    use crate::format::ClearValue;
+    use crate::format::Format;
+    use crate::format::FormatTy;
+    use crate::format::Pixel;
+    use crate::format::R16G16B16A16Unorm;
+    use crate::format::R8G8B8A8Unorm;
+    use crate::image::ImageUsage;
+
+    #[cfg(feature = "image-interop")]
+    #[test]
+    fn image_color_type_rgba8() {
+        use image::ColorType;
+
+        assert_eq!(
+            Format::R8G8B8A8Unorm.to_image_color_type(),
+            Some(ColorType::Rgba8)
+        );
+        assert_eq!(
+            Format::R8G8B8A8Srgb.to_image_color_type(),
+            Some(ColorType::Rgba8)
+        );
+        assert_eq!(
+            Format::from_image_color_type(ColorType::Rgba8, false),
+            Some(Format::R8G8B8A8Unorm)
+        );
+        assert_eq!(
+            Format::from_image_color_type(ColorType::Rgba8, true),
+            Some(Format::R8G8B8A8Srgb)
+        );
+    }
+
+    #[cfg(feature = "image-interop")]
+    #[test]
+    fn image_color_type_rgb8() {
+        use image::ColorType;
+
+        assert_eq!(
+            Format::R8G8B8Unorm.to_image_color_type(),
+            Some(ColorType::Rgb8)
+        );
+        assert_eq!(
+            Format::R8G8B8Srgb.to_image_color_type(),
+            Some(ColorType::Rgb8)
+        );
+        assert_eq!(
+            Format::from_image_color_type(ColorType::Rgb8, false),
+            Some(Format::R8G8B8Unorm)
+        );
+        assert_eq!(
+            Format::from_image_color_type(ColorType::Rgb8, true),
+            Some(Format::R8G8B8Srgb)
+        );
+    }
+
+    #[cfg(feature = "image-interop")]
+    #[test]
+    fn image_color_type_unsupported() {
+        use image::ColorType;
+
+        assert_eq!(Format::R32G32B32A32Sfloat.to_image_color_type(), None);
+        assert_eq!(Format::from_image_color_type(ColorType::Rgba16, true), None);
+    }
+
+    #[test]
+    fn transcode_fallback() {
+        use crate::format::transcode_fallback;
+
+        let cases = [
+            (Format::BC1_RGBUnormBlock, Format::R8G8B8A8Unorm),
+            (Format::BC1_RGBASrgbBlock, Format::R8G8B8A8Srgb),
+            (Format::BC3UnormBlock, Format::R8G8B8A8Unorm),
+            (Format::BC4UnormBlock, Format::R8Unorm),
+            (Format::BC5SnormBlock, Format::R8G8Snorm),
+            (Format::BC6HUfloatBlock, Format::R16G16B16A16Sfloat),
+            (Format::BC7SrgbBlock, Format::R8G8B8A8Srgb),
+            (Format::ETC2_R8G8B8UnormBlock, Format::R8G8B8A8Unorm),
+            (Format::EAC_R11G11UnormBlock, Format::R16G16Unorm),
+            (Format::ASTC_4x4UnormBlock, Format::R8G8B8A8Unorm),
+            (Format::ASTC_12x12SrgbBlock, Format::R8G8B8A8Srgb),
+            (Format::R16G16B16A16Sfloat, Format::R16G16B16A16Sfloat),
+        ];
+        for &(compressed, fallback) in cases.iter() {
+            assert_eq!(transcode_fallback(compressed), fallback, "{:?}", compressed);
+        }
+
+        for format in Format::all() {
+            assert_ne!(transcode_fallback(format).ty(), FormatTy::Compressed);
+        }
+    }
+
+    #[test]
+    fn supports_anisotropy() {
+        assert!(Format::R8G8B8A8Unorm.supports_anisotropy());
+        assert!(Format::BC7SrgbBlock.supports_anisotropy());
+        assert!(!Format::D24Unorm_S8Uint.supports_anisotropy());
+        assert!(!Format::D16Unorm.supports_anisotropy());
+        assert!(!Format::R32Uint.supports_anisotropy());
+        assert!(!Format::R8G8B8A8Sint.supports_anisotropy());
+    }
+
+    #[test]
+    fn is_hdr_packed() {
+        assert!(Format::B10G11R11UfloatPack32.is_hdr_packed());
+        assert!(Format::E5B9G9R9UfloatPack32.is_hdr_packed());
+        assert!(!Format::R16G16B16A16Sfloat.is_hdr_packed());
+        assert!(!Format::A2B10G10R10UnormPack32.is_hdr_packed());
+    }
+
+    #[test]
+    fn supports_resolve() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // Color attachments are mandatory for this format.
+        assert!(Format::R8G8B8A8Unorm.supports_resolve(&device, false));
+        assert!(!Format::R8G8B8A8Unorm.supports_resolve(&device, true));
+
+        // Depth attachments are mandatory for this format.
+        assert!(!Format::D16Unorm.supports_resolve(&device, false));
+        assert_eq!(
+            Format::D16Unorm.supports_resolve(&device, true),
+            device.supported_depth_resolve_modes().is_some()
+        );
+    }
+
+    #[test]
+    fn total_texel_size_depth_stencil() {
+        assert_eq!(Format::D16Unorm_S8Uint.total_texel_size(), Some(3));
+        assert_eq!(Format::D24Unorm_S8Uint.total_texel_size(), Some(4));
+        assert_eq!(Format::D32Sfloat_S8Uint.total_texel_size(), Some(5));
+        assert_eq!(Format::D16Unorm_S8Uint.size(), None);
+
+        assert_eq!(Format::D16Unorm.total_texel_size(), Some(2));
+        assert_eq!(Format::X8_D24UnormPack32.total_texel_size(), Some(4));
+        assert_eq!(Format::D32Sfloat.total_texel_size(), Some(4));
+        assert_eq!(Format::S8Uint.total_texel_size(), Some(1));
+        assert_eq!(Format::BC1_RGBUnormBlock.total_texel_size(), Some(8));
+        assert_eq!(Format::G8B8R8_3PLANE420Unorm.total_texel_size(), None);
+    }
+
+    #[test]
+    fn custom_pixel_type() {
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        struct Rgba8 {
+            r: u8,
+            g: u8,
+            b: u8,
+            a: u8,
+        }
+
+        unsafe impl Pixel for Rgba8 {}
+
+        let accepts = |format: Format| <Format as AcceptsPixels<Rgba8>>::ensure_accepts(&format);
+        assert!(accepts(Format::R8G8B8A8Unorm).is_ok());
+        assert!(accepts(Format::B8G8R8A8Srgb).is_ok());
+        assert!(accepts(Format::R32Sfloat).is_ok());
+        assert!(accepts(Format::R8G8Unorm).is_err());
+        assert!(accepts(Format::BC1_RGBUnormBlock).is_err());
+        assert!(accepts(Format::G8B8R8_3PLANE420Unorm).is_err());
+        assert_eq!(
+            <Format as AcceptsPixels<Rgba8>>::rate(&Format::R8G8B8A8Unorm),
+            1
+        );
+
+        assert!(<R8G8B8A8Unorm as AcceptsPixels<Rgba8>>::ensure_accepts(&R8G8B8A8Unorm).is_ok());
+        assert!(
+            <R16G16B16A16Unorm as AcceptsPixels<Rgba8>>::ensure_accepts(&R16G16B16A16Unorm)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn compressed_block_arrays() {
+        assert!(
+            <Format as AcceptsPixels<[u8; 8]>>::ensure_accepts(&Format::BC1_RGBUnormBlock).is_ok()
+        );
+        assert!(
+            <Format as AcceptsPixels<[u8; 16]>>::ensure_accepts(&Format::BC7UnormBlock).is_ok()
+        );
+        assert_eq!(
+            <Format as AcceptsPixels<[u8; 16]>>::rate(&Format::BC7UnormBlock),
+            1
+        );
+    }
+
+    #[test]
+    fn from_dxgi() {
+        assert_eq!(Format::from_dxgi(28), Some(Format::R8G8B8A8Unorm));
+        assert_eq!(Format::from_dxgi(71), Some(Format::BC1_RGBAUnormBlock));
+        assert_eq!(Format::from_dxgi(77), Some(Format::BC3UnormBlock));
+        assert_eq!(Format::from_dxgi(98), Some(Format::BC7UnormBlock));
+        assert_eq!(Format::from_dxgi(99), Some(Format::BC7SrgbBlock));
+        assert_eq!(Format::from_dxgi(0), None);
+        assert_eq!(Format::from_dxgi(27), None);
+    }
+
+    #[test]
+    fn from_vk_format() {
+        for &format in Format::ALL {
+            assert_eq!(Format::from_vk_format(format as u32), Some(format));
+        }
+        assert_eq!(Format::from_vk_format(0), None);
+    }
+
+    #[test]
+    fn linear_tiling_supports() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let format = Format::R8G8B8A8Unorm;
+        let properties = format.properties(device.physical_device());
+        let usage = ImageUsage {
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+
+        assert_eq!(
+            format.linear_tiling_supports(usage, &device),
+            properties.linear_tiling_features.color_attachment
+        );
+        // R8G8B8A8Unorm is required to support color attachments with optimal tiling.
+        assert!(properties
+            .optimal_tiling_features
+            .supports_image_usage(usage, false));
+    }
+
+    #[test]
+    fn blit_compatible_with() {
+        assert!(Format::R8G8B8A8Unorm.blit_compatible_with(Format::B8G8R8A8Srgb));
+        assert!(Format::R8G8B8A8Snorm.blit_compatible_with(Format::R32G32B32A32Sfloat));
+        assert!(Format::R8Uint.blit_compatible_with(Format::R32G32B32A32Uint));
+        assert!(Format::R16Sint.blit_compatible_with(Format::R8G8Sint));
+        assert!(Format::D32Sfloat.blit_compatible_with(Format::D32Sfloat));
+
+        assert!(!Format::R8G8B8A8Uint.blit_compatible_with(Format::R32G32B32A32Sfloat));
+        assert!(!Format::R32G32B32A32Sfloat.blit_compatible_with(Format::R8G8B8A8Uint));
+        assert!(!Format::R8G8B8A8Sint.blit_compatible_with(Format::R8G8B8A8Uint));
+        assert!(!Format::R8G8B8A8Unorm.blit_compatible_with(Format::R8G8B8A8Sint));
+        assert!(!Format::D32Sfloat.blit_compatible_with(Format::R32Sfloat));
+        assert!(!Format::D16Unorm.blit_compatible_with(Format::D32Sfloat));
+    }
+
+    #[test]
+    fn clear_value_matches_format() {
+        let float = ClearValue::Float([0.0, 0.0, 0.0, 1.0]);
+        assert!(float.matches_format(Format::R8G8B8A8Unorm));
+        assert!(float.matches_format(Format::R32G32B32A32Sfloat));
+        assert!(!float.matches_format(Format::R8G8B8A8Uint));
+        assert!(!float.matches_format(Format::D32Sfloat));
+
+        let int = ClearValue::Int([0; 4]);
+        assert!(int.matches_format(Format::R32G32B32A32Sint));
+        assert!(!int.matches_format(Format::R32G32B32A32Uint));
+
+        let uint = ClearValue::Uint([0; 4]);
+        assert!(uint.matches_format(Format::R8G8B8A8Uint));
+        assert!(!uint.matches_format(Format::R8G8B8A8Sint));
+
+        let depth = ClearValue::Depth(1.0);
+        assert!(depth.matches_format(Format::D32Sfloat));
+        assert!(!depth.matches_format(Format::D24Unorm_S8Uint));
+
+        let stencil = ClearValue::Stencil(0);
+        assert!(stencil.matches_format(Format::S8Uint));
+        assert!(!stencil.matches_format(Format::R8Uint));
+
+        let depth_stencil = ClearValue::DepthStencil((1.0, 0));
+        assert!(depth_stencil.matches_format(Format::D24Unorm_S8Uint));
+        assert!(!depth_stencil.matches_format(Format::D16Unorm));
+
+        assert!(!ClearValue::None.matches_format(Format::R8G8B8A8Unorm));
+    }
+
+    #[test]
+    fn linear_sibling() {
+        assert!(Format::B8G8R8A8Srgb.is_srgb());
+        assert_eq!(
+            Format::B8G8R8A8Srgb.linear_sibling(),
+            Some(Format::B8G8R8A8Unorm)
+        );
+        assert_eq!(
+            Format::BC7SrgbBlock.linear_sibling(),
+            Some(Format::BC7UnormBlock)
+        );
+
+        assert!(!Format::R8G8B8A8Unorm.is_srgb());
+        assert_eq!(Format::R8G8B8A8Unorm.linear_sibling(), None);
+        assert_eq!(Format::D32Sfloat.linear_sibling(), None);
+    }
+
+    #[test]
+    fn channel_range() {
+        assert_eq!(
+            Format::R8Unorm.channel_range(),
+            Some(ChannelRange {
+                min: 0.0,
+                max: 1.0,
+                integer: false,
+            })
+        );
+        assert_eq!(
+            Format::R8Snorm.channel_range(),
+            Some(ChannelRange {
+                min: -1.0,
+                max: 1.0,
+                integer: false,
+            })
+        );
+        assert_eq!(
+            Format::R16Sfloat.channel_range(),
+            Some(ChannelRange {
+                min: -65504.0,
+                max: 65504.0,
+                integer: false,
+            })
+        );
+        assert_eq!(
+            Format::A2B10G10R10UintPack32.channel_range(),
+            Some(ChannelRange {
+                min: 0.0,
+                max: 1023.0,
+                integer: true,
+            })
+        );
+        assert_eq!(
+            Format::R16G16Sint.channel_range(),
+            Some(ChannelRange {
+                min: -32768.0,
+                max: 32767.0,
+                integer: true,
+            })
+        );
+        assert_eq!(
+            Format::BC6HSfloatBlock.channel_range(),
+            Format::R16Sfloat.channel_range()
+        );
+        assert_eq!(Format::D24Unorm_S8Uint.channel_range(), None);
+    }
+
+    #[test]
+    fn component_bits() {
+        let bits = |format: Format| format.component_bits().into_vec();
+
+        assert_eq!(bits(Format::R5G6B5UnormPack16), [5, 6, 5]);
+        assert_eq!(bits(Format::A2B10G10R10UnormPack32), [2, 10, 10, 10]);
+        assert_eq!(bits(Format::B10G11R11UfloatPack32), [10, 11, 11]);
+        assert_eq!(bits(Format::R8G8B8A8Srgb), [8, 8, 8, 8]);
+        assert_eq!(bits(Format::R16G16Sfloat), [16, 16]);
+        assert_eq!(bits(Format::R32Sfloat), [32]);
+        assert_eq!(bits(Format::D24Unorm_S8Uint), [24, 8]);
+        assert_eq!(bits(Format::X8_D24UnormPack32), [24]);
+        assert!(bits(Format::BC7UnormBlock).is_empty());
+    }
+
+    #[test]
+    fn copy_compatible_with() {
+        assert_eq!(
+            Format::BC1_RGBUnormBlock.copy_compatible_with(Format::R16G16B16A16Uint),
+            Some(BlockRatio {
+                src_block: (4, 4),
+                dst_block: (1, 1),
+            })
+        );
+        assert_eq!(
+            Format::R32G32Sfloat.copy_compatible_with(Format::BC1_RGBSrgbBlock),
+            Some(BlockRatio {
+                src_block: (1, 1),
+                dst_block: (4, 4),
+            })
+        );
+        assert!(Format::BC1_RGBUnormBlock
+            .copy_compatible_with(Format::BC1_RGBSrgbBlock)
+            .is_some());
+        assert_eq!(
+            Format::BC1_RGBUnormBlock.copy_compatible_with(Format::R8G8B8A8Unorm),
+            None
+        );
+        assert_eq!(
+            Format::BC1_RGBUnormBlock.copy_compatible_with(Format::BC4UnormBlock),
+            None
+        );
+        assert_eq!(
+            Format::D32Sfloat.copy_compatible_with(Format::R32Sfloat),
+            None
+        );
+    }
+
+    #[test]
+    fn compatible_view_formats() {
+        let formats: Vec<_> = Format::R8G8B8A8Unorm.compatible_view_formats().collect();
+        assert!(formats.contains(&Format::R8G8B8A8Unorm));
+        assert!(formats.contains(&Format::R8G8B8A8Srgb));
+        assert!(formats.contains(&Format::R8G8B8A8Uint));
+        assert!(formats.contains(&Format::R32Sfloat));
+        assert!(!formats.contains(&Format::R16G16B16A16Unorm));
+        assert!(!formats.contains(&Format::D32Sfloat));
+
+        let formats: Vec<_> = Format::BC4SnormBlock.compatible_view_formats().collect();
+        assert_eq!(formats, [Format::BC4UnormBlock, Format::BC4SnormBlock]);
+
+        let formats: Vec<_> = Format::D24Unorm_S8Uint.compatible_view_formats().collect();
+        assert_eq!(formats, [Format::D24Unorm_S8Uint]);
+    }
+}
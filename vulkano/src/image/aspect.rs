@@ -8,6 +8,7 @@
 // according to those terms.
 
 use crate::vk;
+use std::ops::BitAnd;
 use std::ops::BitOr;
 
 /// Describes how an aspect of the image that be used to query Vulkan.  This is **not** just a suggestion.
@@ -59,6 +60,23 @@ impl ImageAspect {
         }
     }
 
+    /// Returns true if all the aspects of `self` are also present in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use vulkano::image::ImageAspect;
+    ///
+    /// let depth = ImageAspect { depth: true, .. ImageAspect::none() };
+    /// let depth_stencil = ImageAspect { depth: true, stencil: true, .. ImageAspect::none() };
+    /// assert!(depth.is_subset_of(depth_stencil));
+    /// assert!(!depth_stencil.is_subset_of(depth));
+    /// ```
+    #[inline]
+    pub fn is_subset_of(&self, other: ImageAspect) -> bool {
+        (*self | other) == other
+    }
+
     #[inline]
     pub(crate) fn to_aspect_bits(&self) -> vk::ImageAspectFlagBits {
         let mut result = 0;
@@ -111,6 +129,26 @@ impl ImageAspect {
     }
 }
 
+impl BitAnd for ImageAspect {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        ImageAspect {
+            color: self.color && rhs.color,
+            depth: self.depth && rhs.depth,
+            stencil: self.stencil && rhs.stencil,
+            metadata: self.metadata && rhs.metadata,
+            plane0: self.plane0 && rhs.plane0,
+            plane1: self.plane1 && rhs.plane1,
+            plane2: self.plane2 && rhs.plane2,
+            memory_plane0: self.memory_plane0 && rhs.memory_plane0,
+            memory_plane1: self.memory_plane1 && rhs.memory_plane1,
+            memory_plane2: self.memory_plane2 && rhs.memory_plane2,
+        }
+    }
+}
+
 impl BitOr for ImageAspect {
     type Output = Self;
 
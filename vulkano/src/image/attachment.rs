@@ -23,6 +23,8 @@ use crate::format::FormatDesc;
 use crate::format::FormatTy;
 use crate::image::sys::ImageCreationError;
 use crate::image::sys::UnsafeImage;
+use crate::image::traits::AccessState;
+use crate::image::traits::ConflictGranularity;
 use crate::image::traits::ImageAccess;
 use crate::image::traits::ImageClearValue;
 use crate::image::traits::ImageContent;
@@ -80,6 +82,9 @@ pub struct AttachmentImage<F = Format, A = PotentialDedicatedAllocation<StdMemor
     // Memory used to back the image.
     memory: A,
 
+    // Index of the memory type of `memory`.
+    memory_type_index: u32,
+
     // Format.
     format: F,
 
@@ -93,6 +98,9 @@ pub struct AttachmentImage<F = Format, A = PotentialDedicatedAllocation<StdMemor
 
     // Number of times this image is locked on the GPU side.
     gpu_lock: AtomicUsize,
+
+    // True if the last successful call to `try_gpu_lock` requested exclusive access.
+    gpu_lock_exclusive: AtomicBool,
 }
 
 impl<F> AttachmentImage<F> {
@@ -387,7 +395,7 @@ impl<F> AttachmentImage<F> {
         AttachmentImage::new_impl(device, dimensions, format, base_usage, samples)
     }
 
-    // All constructors dispatch to this one.
+    // All the constructors that use the standard pool of the device dispatch to this one.
     fn new_impl(
         device: Arc<Device>,
         dimensions: [u32; 2],
@@ -398,6 +406,53 @@ impl<F> AttachmentImage<F> {
     where
         F: FormatDesc,
     {
+        AttachmentImage::with_pool_impl(
+            &Device::standard_pool(&device),
+            dimensions,
+            format,
+            base_usage,
+            samples,
+        )
+    }
+}
+
+impl<F, A> AttachmentImage<F, PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc,
+{
+    /// Same as `multisampled_with_usage`, but allocates the memory of the image from `pool`
+    /// instead of the standard pool of the device.
+    ///
+    /// This is the integration point for custom allocators: implement the `MemoryPool` trait,
+    /// for example on top of a sub-allocator, to control where the memory of the image comes from.
+    #[inline]
+    pub fn with_pool<P>(
+        pool: &P,
+        dimensions: [u32; 2],
+        samples: u32,
+        format: F,
+        usage: ImageUsage,
+    ) -> Result<Arc<Self>, ImageCreationError>
+    where
+        F: FormatDesc,
+        P: MemoryPool<Alloc = A>,
+    {
+        AttachmentImage::with_pool_impl(pool, dimensions, format, usage, samples)
+    }
+
+    // All constructors dispatch to this one.
+    fn with_pool_impl<P>(
+        pool: &P,
+        dimensions: [u32; 2],
+        format: F,
+        base_usage: ImageUsage,
+        samples: u32,
+    ) -> Result<Arc<Self>, ImageCreationError>
+    where
+        F: FormatDesc,
+        P: MemoryPool<Alloc = A>,
+    {
+        let device = pool.device();
         // TODO: check dimensions against the max_framebuffer_width/height/layers limits
 
         let is_depth = match format.format().ty() {
@@ -436,7 +491,7 @@ impl<F> AttachmentImage<F> {
         };
 
         let memory = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
+            pool,
             &mem_reqs,
             AllocLayout::Optimal,
             MappingRequirement::DoNotMap,
@@ -451,11 +506,14 @@ impl<F> AttachmentImage<F> {
         )?;
         debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
         unsafe {
-            image.bind_memory(memory.memory(), memory.offset())?;
+            image
+                .bind_memory(memory.memory(), memory.offset())
+                .map_err(ImageCreationError::BindFailed)?;
         }
 
         Ok(Arc::new(AttachmentImage {
             image,
+            memory_type_index: memory.memory().memory_type().id(),
             memory,
             format,
             attachment_layout: if is_depth {
@@ -465,6 +523,7 @@ impl<F> AttachmentImage<F> {
             },
             initialized: AtomicBool::new(false),
             gpu_lock: AtomicUsize::new(0),
+            gpu_lock_exclusive: AtomicBool::new(false),
         }))
     }
 }
@@ -493,6 +552,11 @@ where
         }
     }
 
+    #[inline]
+    fn memory_type_index(&self) -> Option<u32> {
+        Some(self.memory_type_index)
+    }
+
     #[inline]
     fn initial_layout_requirement(&self) -> ImageLayout {
         self.attachment_layout
@@ -529,7 +593,16 @@ where
     }
 
     #[inline]
-    fn try_gpu_lock(&self, _: bool, expected_layout: ImageLayout) -> Result<(), AccessError> {
+    fn conflict_granularity(&self) -> ConflictGranularity {
+        ConflictGranularity::PerSubresource
+    }
+
+    #[inline]
+    fn try_gpu_lock(
+        &self,
+        exclusive_access: bool,
+        expected_layout: ImageLayout,
+    ) -> Result<(), AccessError> {
         if expected_layout != self.attachment_layout && expected_layout != ImageLayout::Undefined {
             if self.initialized.load(Ordering::SeqCst) {
                 return Err(AccessError::UnexpectedImageLayout {
@@ -558,6 +631,8 @@ where
             .unwrap_or_else(|e| e)
             == 0
         {
+            self.gpu_lock_exclusive
+                .store(exclusive_access, Ordering::SeqCst);
             Ok(())
         } else {
             Err(AccessError::AlreadyInUse)
@@ -581,6 +656,21 @@ where
         debug_assert!(prev_val >= 1);
     }
 
+    #[inline]
+    fn current_access_state(&self) -> Option<AccessState> {
+        let layout = if self.initialized.load(Ordering::SeqCst) {
+            self.attachment_layout
+        } else {
+            ImageLayout::Undefined
+        };
+
+        Some(AccessState {
+            layout,
+            gpu_locks: self.gpu_lock.load(Ordering::SeqCst),
+            exclusive: self.gpu_lock_exclusive.load(Ordering::SeqCst),
+        })
+    }
+
     #[inline]
     unsafe fn layout_initialized(&self) {
         self.initialized.store(true, Ordering::SeqCst);
@@ -647,7 +737,15 @@ where
 #[cfg(test)]
 mod tests {
     use super::AttachmentImage;
+    use crate::device::Device;
     use crate::format::Format;
+    use crate::image::sys::ImageCreationError;
+    use crate::image::ImageAccess;
+    use crate::image::ImageLayout;
+    use crate::image::ImageUsage;
+    use crate::tests::CountingPool;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
 
     #[test]
     fn create_regular() {
@@ -661,9 +759,76 @@ mod tests {
         let _img = AttachmentImage::transient(device, [32, 32], Format::R8G8B8A8Unorm).unwrap();
     }
 
+    #[test]
+    fn current_access_state() {
+        let (device, _) = gfx_dev_and_queue!();
+        let img = AttachmentImage::new(device, [32, 32], Format::R8G8B8A8Unorm).unwrap();
+
+        let state = img.current_access_state().unwrap();
+        assert_eq!(state.layout, ImageLayout::Undefined);
+        assert_eq!(state.gpu_locks, 0);
+
+        img.try_gpu_lock(true, ImageLayout::Undefined).unwrap();
+        let state = img.current_access_state().unwrap();
+        assert_eq!(state.gpu_locks, 1);
+        assert!(state.exclusive);
+
+        unsafe {
+            img.unlock(Some(ImageLayout::ColorAttachmentOptimal));
+        }
+        let state = img.current_access_state().unwrap();
+        assert_eq!(state.layout, ImageLayout::ColorAttachmentOptimal);
+        assert_eq!(state.gpu_locks, 0);
+    }
+
     #[test]
     fn d16_unorm_always_supported() {
         let (device, _) = gfx_dev_and_queue!();
         let _img = AttachmentImage::new(device, [32, 32], Format::D16Unorm).unwrap();
     }
+
+    #[test]
+    fn hdr_packed_color_attachment() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // Sampling is mandatory for both formats, attaching isn't.
+        for &format in &[Format::B10G11R11UfloatPack32, Format::E5B9G9R9UfloatPack32] {
+            let features = format
+                .properties(device.physical_device())
+                .optimal_tiling_features;
+            assert!(features.sampled_image);
+
+            let res = AttachmentImage::sampled(device.clone(), [32, 32], format);
+            match (format, features.color_attachment) {
+                (_, true) => assert!(res.is_ok()),
+                (Format::E5B9G9R9UfloatPack32, false) => match res {
+                    Err(ImageCreationError::UnsupportedHdrPackedUsage { format: f, usage }) => {
+                        assert_eq!(f, format);
+                        assert!(usage.color_attachment);
+                    }
+                    _ => panic!(),
+                },
+                (_, false) => assert!(res.is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn with_pool() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = CountingPool {
+            inner: Device::standard_pool(&device),
+            allocations: AtomicUsize::new(0),
+        };
+
+        let _img = AttachmentImage::with_pool(
+            &pool,
+            [32, 32],
+            1,
+            Format::R8G8B8A8Unorm,
+            ImageUsage::none(),
+        )
+        .unwrap();
+        assert_eq!(pool.allocations.load(Ordering::SeqCst), 1);
+    }
 }
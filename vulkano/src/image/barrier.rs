@@ -0,0 +1,268 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::image::ImageLayout;
+use crate::image::ImageUsage;
+use crate::sync::AccessFlagBits;
+use crate::sync::PipelineStages;
+
+/// Describes how an image is used by a command or a group of commands.
+///
+/// See `barrier_between`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ImageUsageState {
+    /// The ways the image is accessed.
+    pub usage: ImageUsage,
+    /// The layout the image is in while being accessed.
+    pub layout: ImageLayout,
+    /// The pipeline stages that access the image.
+    pub stages: PipelineStages,
+}
+
+impl ImageUsageState {
+    /// Builds an `ImageUsageState` whose layout is the optimal layout for `usage`, as returned by
+    /// `ImageLayout::optimal_for_usage`.
+    #[inline]
+    pub fn new(usage: ImageUsage, stages: PipelineStages) -> ImageUsageState {
+        ImageUsageState {
+            usage,
+            layout: ImageLayout::optimal_for_usage(usage),
+            stages,
+        }
+    }
+
+    /// Returns the memory accesses that reading the image with this usage performs.
+    pub fn read_access(&self) -> AccessFlagBits {
+        AccessFlagBits {
+            transfer_read: self.usage.transfer_source,
+            shader_read: self.usage.sampled || self.usage.storage,
+            color_attachment_read: self.usage.color_attachment,
+            depth_stencil_attachment_read: self.usage.depth_stencil_attachment,
+            input_attachment_read: self.usage.input_attachment,
+            ..AccessFlagBits::none()
+        }
+    }
+
+    /// Returns the memory accesses that writing the image with this usage performs.
+    pub fn write_access(&self) -> AccessFlagBits {
+        AccessFlagBits {
+            transfer_write: self.usage.transfer_destination,
+            shader_write: self.usage.storage,
+            color_attachment_write: self.usage.color_attachment,
+            depth_stencil_attachment_write: self.usage.depth_stencil_attachment,
+            ..AccessFlagBits::none()
+        }
+    }
+}
+
+/// Parameters of a pipeline barrier on an image, as returned by `barrier_between`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ImageMemoryBarrierInfo {
+    /// The stages that must finish before the barrier.
+    pub source_stages: PipelineStages,
+    /// The memory accesses that must be made available by the barrier.
+    pub source_access: AccessFlagBits,
+    /// The stages that must wait for the barrier.
+    pub destination_stages: PipelineStages,
+    /// The memory accesses that the barrier makes the source accesses visible to.
+    pub destination_access: AccessFlagBits,
+    /// The layout of the image before the barrier.
+    pub old_layout: ImageLayout,
+    /// The layout of the image after the barrier.
+    pub new_layout: ImageLayout,
+}
+
+/// Returns the minimal pipeline barrier needed between two successive uses of an image.
+///
+/// The writes of `prev` are made available and visible to all the accesses of `next`. If `prev`
+/// only reads the image and the layout doesn't change, only an execution dependency is needed
+/// and the returned access masks are empty. A layout transition counts as a write, so it always
+/// makes the accesses of `next` wait.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::image::barrier_between;
+/// use vulkano::image::ImageLayout;
+/// use vulkano::image::ImageUsage;
+/// use vulkano::image::ImageUsageState;
+/// use vulkano::sync::PipelineStages;
+///
+/// let prev = ImageUsageState::new(
+///     ImageUsage { transfer_destination: true, .. ImageUsage::none() },
+///     PipelineStages { transfer: true, .. PipelineStages::none() },
+/// );
+/// let next = ImageUsageState::new(
+///     ImageUsage { sampled: true, .. ImageUsage::none() },
+///     PipelineStages { fragment_shader: true, .. PipelineStages::none() },
+/// );
+///
+/// let barrier = barrier_between(prev, next);
+/// assert!(barrier.source_access.transfer_write);
+/// assert!(barrier.destination_access.shader_read);
+/// assert_eq!(barrier.old_layout, ImageLayout::TransferDstOptimal);
+/// assert_eq!(barrier.new_layout, ImageLayout::ShaderReadOnlyOptimal);
+/// ```
+pub fn barrier_between(prev: ImageUsageState, next: ImageUsageState) -> ImageMemoryBarrierInfo {
+    let source_access = prev.write_access();
+    let needs_memory_dependency =
+        source_access != AccessFlagBits::none() || prev.layout != next.layout;
+
+    let destination_access = if needs_memory_dependency {
+        next.read_access() | next.write_access()
+    } else {
+        AccessFlagBits::none()
+    };
+
+    ImageMemoryBarrierInfo {
+        source_stages: prev.stages,
+        source_access,
+        destination_stages: next.stages,
+        destination_access,
+        old_layout: prev.layout,
+        new_layout: next.layout,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::barrier_between;
+    use crate::image::ImageLayout;
+    use crate::image::ImageUsage;
+    use crate::image::ImageUsageState;
+    use crate::sync::AccessFlagBits;
+    use crate::sync::PipelineStages;
+
+    fn sampled_in_fragment_shader() -> ImageUsageState {
+        ImageUsageState::new(
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            PipelineStages {
+                fragment_shader: true,
+                ..PipelineStages::none()
+            },
+        )
+    }
+
+    #[test]
+    fn color_attachment_to_sampled() {
+        let prev = ImageUsageState::new(
+            ImageUsage {
+                color_attachment: true,
+                ..ImageUsage::none()
+            },
+            PipelineStages {
+                color_attachment_output: true,
+                ..PipelineStages::none()
+            },
+        );
+        let next = sampled_in_fragment_shader();
+
+        let barrier = barrier_between(prev, next);
+        assert_eq!(barrier.source_stages, prev.stages);
+        assert_eq!(
+            barrier.source_access,
+            AccessFlagBits {
+                color_attachment_write: true,
+                ..AccessFlagBits::none()
+            }
+        );
+        assert_eq!(barrier.destination_stages, next.stages);
+        assert_eq!(
+            barrier.destination_access,
+            AccessFlagBits {
+                shader_read: true,
+                ..AccessFlagBits::none()
+            }
+        );
+        assert_eq!(barrier.old_layout, ImageLayout::ColorAttachmentOptimal);
+        assert_eq!(barrier.new_layout, ImageLayout::ShaderReadOnlyOptimal);
+    }
+
+    #[test]
+    fn transfer_destination_to_sampled() {
+        let prev = ImageUsageState::new(
+            ImageUsage {
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+            PipelineStages {
+                transfer: true,
+                ..PipelineStages::none()
+            },
+        );
+        let next = sampled_in_fragment_shader();
+
+        let barrier = barrier_between(prev, next);
+        assert_eq!(
+            barrier.source_access,
+            AccessFlagBits {
+                transfer_write: true,
+                ..AccessFlagBits::none()
+            }
+        );
+        assert_eq!(
+            barrier.destination_access,
+            AccessFlagBits {
+                shader_read: true,
+                ..AccessFlagBits::none()
+            }
+        );
+        assert_eq!(barrier.old_layout, ImageLayout::TransferDstOptimal);
+        assert_eq!(barrier.new_layout, ImageLayout::ShaderReadOnlyOptimal);
+    }
+
+    #[test]
+    fn read_to_read_same_layout() {
+        let prev = sampled_in_fragment_shader();
+        let next = ImageUsageState::new(
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            PipelineStages {
+                compute_shader: true,
+                ..PipelineStages::none()
+            },
+        );
+
+        let barrier = barrier_between(prev, next);
+        assert_eq!(barrier.source_access, AccessFlagBits::none());
+        assert_eq!(barrier.destination_access, AccessFlagBits::none());
+        assert_eq!(barrier.old_layout, barrier.new_layout);
+    }
+
+    #[test]
+    fn optimal_for_usage() {
+        assert_eq!(
+            ImageLayout::optimal_for_usage(ImageUsage {
+                transfer_destination: true,
+                ..ImageUsage::none()
+            }),
+            ImageLayout::TransferDstOptimal
+        );
+        assert_eq!(
+            ImageLayout::optimal_for_usage(ImageUsage {
+                storage: true,
+                ..ImageUsage::none()
+            }),
+            ImageLayout::General
+        );
+        assert_eq!(
+            ImageLayout::optimal_for_usage(ImageUsage {
+                sampled: true,
+                transfer_destination: true,
+                ..ImageUsage::none()
+            }),
+            ImageLayout::General
+        );
+    }
+}
@@ -11,8 +11,12 @@ use crate::buffer::BufferAccess;
 use crate::buffer::BufferUsage;
 use crate::buffer::CpuAccessibleBuffer;
 use crate::buffer::TypedBufferAccess;
+use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
+use crate::command_buffer::validity::required_len_for_format;
+use crate::command_buffer::validity::CheckCopyBufferImageError;
 use crate::command_buffer::AutoCommandBufferBuilder;
 use crate::command_buffer::CommandBufferExecFuture;
+use crate::command_buffer::CopyBufferImageError;
 use crate::command_buffer::PrimaryAutoCommandBuffer;
 use crate::command_buffer::PrimaryCommandBuffer;
 use crate::device::Device;
@@ -22,8 +26,10 @@ use crate::format::Format;
 use crate::format::FormatDesc;
 use crate::image::sys::ImageCreationError;
 use crate::image::sys::UnsafeImage;
+use crate::image::traits::ConflictGranularity;
 use crate::image::traits::ImageAccess;
 use crate::image::traits::ImageContent;
+use crate::image::ImageAspect;
 use crate::image::ImageCreateFlags;
 use crate::image::ImageDescriptorLayouts;
 use crate::image::ImageDimensions;
@@ -40,6 +46,7 @@ use crate::memory::pool::MemoryPoolAlloc;
 use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPoolAlloc;
 use crate::memory::DedicatedAlloc;
+use crate::memory::MemoryRequirements;
 use crate::sampler::Filter;
 use crate::sync::AccessError;
 use crate::sync::NowFuture;
@@ -47,6 +54,7 @@ use crate::sync::Sharing;
 use smallvec::SmallVec;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter::Empty;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -59,6 +67,7 @@ pub struct ImmutableImage<F, A = PotentialDedicatedAllocation<StdMemoryPoolAlloc
     image: UnsafeImage,
     dimensions: ImageDimensions,
     memory: A,
+    memory_type_index: u32,
     format: F,
     initialized: AtomicBool,
     layout: ImageLayout,
@@ -128,15 +137,21 @@ fn generate_mipmaps<L, Img>(
 ) where
     Img: ImageAccess + Send + Sync + 'static,
 {
+    let color = ImageAspect {
+        color: true,
+        ..ImageAspect::none()
+    };
+
     for level in 1..image.mipmap_levels() {
-        let [xs, ys, ds] = dimensions
-            .mipmap_dimensions(level - 1)
-            .unwrap()
-            .width_height_depth();
-        let [xd, yd, dd] = dimensions
-            .mipmap_dimensions(level)
-            .unwrap()
-            .width_height_depth();
+        let region = UnsafeCommandBufferBuilderImageBlit::full_region(
+            &dimensions,
+            level - 1,
+            &dimensions,
+            level,
+            image.format(),
+            color,
+        )
+        .unwrap();
 
         let src = SubImage::new(
             image.clone(),
@@ -157,18 +172,18 @@ fn generate_mipmaps<L, Img>(
         );
 
         cbb.blit_image(
-            src,                               //source
-            [0, 0, 0],                         //source_top_left
-            [xs as i32, ys as i32, ds as i32], //source_bottom_right
-            0,                                 //source_base_array_layer
-            level - 1,                         //source_mip_level
-            dst,                               //destination
-            [0, 0, 0],                         //destination_top_left
-            [xd as i32, yd as i32, dd as i32], //destination_bottom_right
-            0,                                 //destination_base_array_layer
-            level,                             //destination_mip_level
-            1,                                 //layer_count
-            Filter::Linear,                    //filter
+            src,
+            region.source_top_left,
+            region.source_bottom_right,
+            region.source_base_array_layer,
+            region.source_mip_level,
+            dst,
+            region.destination_top_left,
+            region.destination_bottom_right,
+            region.destination_base_array_layer,
+            region.destination_mip_level,
+            region.layer_count,
+            Filter::Linear,
         )
         .expect("failed to blit a mip map to image!");
     }
@@ -237,6 +252,9 @@ impl<F> ImmutableImage<F> {
     /// Builds an uninitialized immutable image.
     ///
     /// Returns two things: the image, and a special access that should be used for the initial upload to the image.
+    ///
+    /// If `usage` contains `sampled`, returns `ImageCreationError::FormatNotSampleable` if the
+    /// format can't be sampled with optimal tiling.
     pub fn uninitialized<'a, I, M>(
         device: Arc<Device>,
         dimensions: ImageDimensions,
@@ -252,6 +270,18 @@ impl<F> ImmutableImage<F> {
         I: IntoIterator<Item = QueueFamily<'a>>,
         M: Into<MipmapsCount>,
     {
+        if usage.sampled
+            && !format
+                .format()
+                .properties(device.physical_device())
+                .optimal_tiling_features
+                .sampled_image
+        {
+            return Err(ImageCreationError::FormatNotSampleable {
+                format: format.format(),
+            });
+        }
+
         let queue_families = queue_families
             .into_iter()
             .map(|f| f.id())
@@ -294,11 +324,14 @@ impl<F> ImmutableImage<F> {
         )?;
         debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
         unsafe {
-            image.bind_memory(memory.memory(), memory.offset())?;
+            image
+                .bind_memory(memory.memory(), memory.offset())
+                .map_err(ImageCreationError::BindFailed)?;
         }
 
         let image = Arc::new(ImmutableImage {
             image,
+            memory_type_index: memory.memory().memory_type().id(),
             memory,
             dimensions,
             format,
@@ -316,7 +349,47 @@ impl<F> ImmutableImage<F> {
         Ok((image, init))
     }
 
+    /// Returns the memory requirements of an image created with `uninitialized` and the same
+    /// parameters, without allocating any memory.
+    ///
+    /// This creates a temporary image that is destroyed before returning. It is useful to plan
+    /// allocations with a custom allocator.
+    pub fn memory_requirements_for<M>(
+        device: Arc<Device>,
+        dimensions: ImageDimensions,
+        format: F,
+        mipmaps: M,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+    ) -> Result<MemoryRequirements, ImageCreationError>
+    where
+        F: FormatDesc,
+        M: Into<MipmapsCount>,
+    {
+        let (_image, mem_reqs) = unsafe {
+            UnsafeImage::new(
+                device,
+                usage,
+                format.format(),
+                flags,
+                dimensions,
+                1,
+                mipmaps,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                false,
+            )?
+        };
+
+        Ok(mem_reqs)
+    }
+
     /// Construct an ImmutableImage from the contents of `iter`.
+    ///
+    /// The data is copied to the image as-is, without any color space conversion. If `format` is
+    /// an sRGB format, the data must therefore already be sRGB-encoded, as the GPU will convert
+    /// it from sRGB to linear when sampling the image. To store linear data, use the `Unorm`
+    /// format returned by `Format::linear_sibling` instead.
     #[inline]
     pub fn from_iter<P, I>(
         iter: I,
@@ -346,7 +419,97 @@ impl<F> ImmutableImage<F> {
         ImmutableImage::from_buffer(source, dimensions, mipmaps, format, queue)
     }
 
+    /// Same as `from_iter`, but also returns the staging buffer that the data was copied from.
+    ///
+    /// This lets the caller reuse the staging buffer for a later upload instead of allocating a
+    /// new one, for example when streaming textures in and out. The buffer is still read by the
+    /// GPU until the copy completes, so the returned future must be kept alive and waited on
+    /// before the buffer is written to again.
+    pub fn from_iter_keep_staging<P, I>(
+        iter: I,
+        dimensions: ImageDimensions,
+        mipmaps: MipmapsCount,
+        format: F,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            Arc<CpuAccessibleBuffer<[P]>>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        P: Send + Sync + Clone + 'static,
+        F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
+        I: ExactSizeIterator<Item = P>,
+        Format: AcceptsPixels<P>,
+    {
+        let source = CpuAccessibleBuffer::from_iter(
+            queue.device().clone(),
+            BufferUsage::transfer_source(),
+            false,
+            iter,
+        )?;
+        let (image, future) =
+            ImmutableImage::from_buffer(source.clone(), dimensions, mipmaps, format, queue)?;
+        Ok((image, source, future))
+    }
+
+    /// Same as `from_iter`, but copies the data from a slice.
+    ///
+    /// The slice is copied into the staging buffer in a single operation instead of element by
+    /// element, which makes uploading large textures noticeably faster. `from_iter` remains
+    /// useful for data that is produced on the fly.
+    ///
+    /// Returns `ImageCreationError::SourceDataTooSmall` if `data` is smaller than what
+    /// `dimensions` and `format` require.
+    pub fn from_slice<P>(
+        data: &[P],
+        dimensions: ImageDimensions,
+        mipmaps: MipmapsCount,
+        format: F,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        P: Send + Sync + Copy + 'static,
+        F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
+        Format: AcceptsPixels<P>,
+    {
+        let required_len = required_len_for_format::<P>(
+            format.format(),
+            dimensions.width_height_depth(),
+            dimensions.array_layers(),
+        );
+        if data.len() < required_len {
+            return Err(ImageCreationError::SourceDataTooSmall {
+                required_len,
+                obtained_len: data.len(),
+            });
+        }
+
+        let source = unsafe {
+            CpuAccessibleBuffer::uninitialized_array(
+                queue.device().clone(),
+                data.len(),
+                BufferUsage::transfer_source(),
+                false,
+            )?
+        };
+        source.write().unwrap().copy_from_slice(data);
+
+        ImmutableImage::from_buffer(source, dimensions, mipmaps, format, queue)
+    }
+
     /// Construct an ImmutableImage containing a copy of the data in `source`.
+    ///
+    /// As with `from_iter`, the data must already be sRGB-encoded if `format` is an sRGB format.
     pub fn from_buffer<B, P>(
         source: B,
         dimensions: ImageDimensions,
@@ -428,6 +591,325 @@ impl<F> ImmutableImage<F> {
 
         Ok((image, future))
     }
+
+    /// Construct an ImmutableImage whose mipmap levels are all copied from `source`, instead of
+    /// being generated from the first level.
+    ///
+    /// The data of the levels must be laid out in `source` as described by
+    /// `ImageDimensions::packed_mip_layout`, for example as produced by an offline texture
+    /// compressor. All the levels are uploaded with a single copy command.
+    pub fn from_buffer_mip_chain<B, P>(
+        source: B,
+        dimensions: ImageDimensions,
+        mipmaps: MipmapsCount,
+        format: F,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        B: BufferAccess + TypedBufferAccess<Content = [P]> + 'static + Clone + Send + Sync,
+        P: Send + Sync + Clone + 'static,
+        F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
+        Format: AcceptsPixels<P>,
+    {
+        let usage = ImageUsage {
+            transfer_destination: true,
+            sampled: true,
+            ..ImageUsage::none()
+        };
+        let flags = ImageCreateFlags::none();
+        let layout = ImageLayout::ShaderReadOnlyOptimal;
+
+        let (image, initializer) = ImmutableImage::uninitialized(
+            source.device().clone(),
+            dimensions,
+            format,
+            mipmaps,
+            usage,
+            flags,
+            layout,
+            source.device().active_queue_families(),
+        )?;
+
+        let levels = image.mipmap_levels();
+        let init = SubImage::new(
+            Arc::new(initializer),
+            0,
+            levels,
+            0,
+            dimensions.array_layers(),
+            ImageLayout::ShaderReadOnlyOptimal,
+        );
+
+        let mut cbb = AutoCommandBufferBuilder::new(source.device().clone(), queue.family())?;
+        match cbb.copy_buffer_to_image_mip_chain(source, init, levels) {
+            Ok(_) => (),
+            Err(CopyBufferImageError::CheckCopyBufferImageError(
+                CheckCopyBufferImageError::BufferTooSmall {
+                    required_len,
+                    actual_len,
+                },
+            )) => {
+                return Err(ImageCreationError::SourceDataTooSmall {
+                    required_len,
+                    obtained_len: actual_len,
+                })
+            }
+            Err(CopyBufferImageError::CheckCopyBufferImageError(err)) => {
+                return Err(ImageCreationError::InvalidSourceData(err))
+            }
+            // The builder is new and outside of a render pass, and the image was just created so
+            // it can't conflict with the buffer.
+            Err(e @ CopyBufferImageError::AutoCommandBufferBuilderContextError(_))
+            | Err(e @ CopyBufferImageError::SyncCommandBufferBuilderError(_)) => {
+                unreachable!("{:?}", e)
+            }
+        }
+
+        let cb = cbb.build().unwrap();
+        let future = cb
+            .execute(queue)
+            .map_err(ImageCreationError::UploadFailed)?;
+
+        image.initialized.store(true, Ordering::Relaxed);
+
+        Ok((image, future))
+    }
+
+    /// Construct an ImmutableImage from the data of each of its mipmap levels, for example the
+    /// levels of a BC or ASTC compressed texture loaded from a KTX file.
+    ///
+    /// The number of mipmap levels of the image is the number of elements of `levels`, and
+    /// `levels[0]` is the largest level, whatever the order of the levels in the file. The data
+    /// of each level must contain all the array layers of the level, and its length must be
+    /// exactly the number of texel blocks of the level times the size of a block. The levels are
+    /// gathered into a single staging buffer, laid out as described by
+    /// `ImageDimensions::packed_mip_layout`, and uploaded with a single copy command.
+    ///
+    /// Returns `ImageCreationError::WrongMipDataLength` for the first level whose data doesn't
+    /// have the right length.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `format` has no texel block size, which is the case of YCbCr formats.
+    ///
+    pub fn from_compressed_mips(
+        levels: Vec<Vec<u8>>,
+        dimensions: ImageDimensions,
+        format: F,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        F: FormatDesc + AcceptsPixels<u8> + 'static + Send + Sync,
+        Format: AcceptsPixels<u8>,
+    {
+        let num_levels = levels.len() as u32;
+        let max_mipmaps = dimensions.max_mipmaps();
+        if num_levels == 0 || num_levels > max_mipmaps {
+            return Err(ImageCreationError::InvalidMipmapsCount {
+                obtained: num_levels,
+                valid_range: 1..max_mipmaps + 1,
+            });
+        }
+
+        let layout: Vec<_> = match dimensions.packed_mip_layout(num_levels, format.format()) {
+            Some(layout) => layout.collect(),
+            None => return Err(ImageCreationError::FormatNotSupported),
+        };
+        for (level, data) in layout.iter().zip(levels.iter()) {
+            if data.len() as u64 != level.byte_size {
+                return Err(ImageCreationError::WrongMipDataLength {
+                    level: level.level,
+                    expected: level.byte_size as usize,
+                    actual: data.len(),
+                });
+            }
+        }
+
+        let total_len = {
+            let last = layout.last().unwrap();
+            (last.buffer_offset + last.byte_size) as usize
+        };
+        let mut data = vec![0u8; total_len];
+        for (level, level_data) in layout.iter().zip(levels.iter()) {
+            let start = level.buffer_offset as usize;
+            data[start..start + level_data.len()].copy_from_slice(level_data);
+        }
+
+        let source = CpuAccessibleBuffer::from_iter(
+            queue.device().clone(),
+            BufferUsage::transfer_source(),
+            false,
+            data.into_iter(),
+        )?;
+
+        ImmutableImage::from_buffer_mip_chain(
+            source,
+            dimensions,
+            MipmapsCount::Specific(num_levels),
+            format,
+            queue,
+        )
+    }
+
+    /// Constructs several `ImmutableImage`s at once, each one containing a copy of the data of
+    /// its source.
+    ///
+    /// The data of all the sources is gathered into a single staging buffer, and all the copies
+    /// are recorded into a single command buffer that is submitted once to `queue`. This is much
+    /// cheaper than calling `from_iter` for each image when loading many small textures.
+    ///
+    /// Each source is validated individually, and the returned list contains one result per
+    /// source in the same order. A source that fails, for example because its data is too small
+    /// for its dimensions and format, doesn't prevent the other images from being loaded. An
+    /// error is only returned for the whole batch if the staging buffer or the command buffer
+    /// can't be created.
+    ///
+    /// As with `from_iter`, the data must already be sRGB-encoded if a format is an sRGB format.
+    pub fn from_many<P, I>(
+        sources: Vec<(I, ImageDimensions, F)>,
+        mipmaps: MipmapsCount,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Vec<Result<Arc<Self>, ImageCreationError>>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        P: Send + Sync + Clone + 'static,
+        F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
+        I: ExactSizeIterator<Item = P>,
+        Format: AcceptsPixels<P>,
+    {
+        let device = queue.device().clone();
+        let need_to_generate_mipmaps = has_mipmaps(mipmaps);
+        let usage = ImageUsage {
+            transfer_destination: true,
+            transfer_source: need_to_generate_mipmaps,
+            sampled: true,
+            ..ImageUsage::none()
+        };
+        let flags = ImageCreateFlags::none();
+        let layout = ImageLayout::ShaderReadOnlyOptimal;
+
+        let mut results = Vec::with_capacity(sources.len());
+        let mut uploads = Vec::with_capacity(sources.len());
+        let mut data: Vec<P> = Vec::new();
+
+        for (iter, dimensions, format) in sources {
+            let required_len = required_len_for_format::<P>(
+                format.format(),
+                dimensions.width_height_depth(),
+                dimensions.array_layers(),
+            );
+            if iter.len() < required_len {
+                results.push(Err(ImageCreationError::SourceDataTooSmall {
+                    required_len,
+                    obtained_len: iter.len(),
+                }));
+                continue;
+            }
+
+            // The offset of each region in the staging buffer must be a multiple of both 4 and
+            // the size of a texel block.
+            let alignment = 4 * format.rate() as usize;
+
+            let (image, initializer) = match ImmutableImage::uninitialized(
+                device.clone(),
+                dimensions,
+                format,
+                mipmaps,
+                usage,
+                flags,
+                layout,
+                device.active_queue_families(),
+            ) {
+                Ok(r) => r,
+                Err(err) => {
+                    results.push(Err(err));
+                    continue;
+                }
+            };
+
+            if let Some(padding) = data.last().cloned() {
+                let start = (data.len() + alignment - 1) / alignment * alignment;
+                data.resize(start, padding);
+            }
+            let start = data.len();
+            data.extend(iter);
+            uploads.push((image.clone(), initializer, start..data.len()));
+            results.push(Ok(image));
+        }
+
+        let mut cbb = AutoCommandBufferBuilder::new(device.clone(), queue.family())?;
+
+        if !data.is_empty() {
+            let source = CpuAccessibleBuffer::from_iter(
+                device,
+                BufferUsage::transfer_source(),
+                false,
+                data.into_iter(),
+            )?;
+
+            for (image, initializer, range) in uploads {
+                let init = SubImage::new(
+                    Arc::new(initializer),
+                    0,
+                    1,
+                    0,
+                    1,
+                    ImageLayout::ShaderReadOnlyOptimal,
+                );
+                let region = source.clone().into_buffer_slice().slice(range).unwrap();
+
+                cbb.copy_buffer_to_image_dimensions(
+                    region,
+                    init,
+                    [0, 0, 0],
+                    image.dimensions.width_height_depth(),
+                    0,
+                    image.dimensions.array_layers(),
+                    0,
+                )
+                .unwrap();
+
+                if need_to_generate_mipmaps {
+                    generate_mipmaps(
+                        &mut cbb,
+                        image.clone(),
+                        image.dimensions,
+                        ImageLayout::ShaderReadOnlyOptimal,
+                    );
+                }
+            }
+        }
+
+        let cb = cbb.build().unwrap();
+
+        let future = match cb.execute(queue) {
+            Ok(f) => f,
+            Err(e) => unreachable!("{:?}", e),
+        };
+
+        for image in results.iter().filter_map(|r| r.as_ref().ok()) {
+            image.initialized.store(true, Ordering::Relaxed);
+        }
+
+        Ok((results, future))
+    }
 }
 
 impl<F, A> ImmutableImage<F, A> {
@@ -459,6 +941,11 @@ where
         }
     }
 
+    #[inline]
+    fn memory_type_index(&self) -> Option<u32> {
+        Some(self.memory_type_index)
+    }
+
     #[inline]
     fn initial_layout_requirement(&self) -> ImageLayout {
         self.layout
@@ -494,6 +981,11 @@ where
         self.image.key()
     }
 
+    #[inline]
+    fn conflict_granularity(&self) -> ConflictGranularity {
+        ConflictGranularity::PerSubresource
+    }
+
     #[inline]
     fn try_gpu_lock(
         &self,
@@ -749,3 +1241,432 @@ where
         ImageAccess::inner(self).hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::BufferAccess;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use crate::buffer::TypedBufferAccess;
+    use crate::command_buffer::validity::CheckCopyBufferImageError;
+    use crate::command_buffer::AutoCommandBufferBuilder;
+    use crate::format::Format;
+    use crate::format::R8G8B8A8Unorm;
+    use crate::image::sys::ImageCreationError;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageLayout;
+    use crate::image::ImageUsage;
+    use crate::image::ImmutableImage;
+    use crate::image::MipmapsCount;
+    use crate::sync::GpuFuture;
+
+    #[test]
+    fn format_not_sampleable() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let dimensions = ImageDimensions::Dim2d {
+            width: 4,
+            height: 4,
+            array_layers: 1,
+        };
+        let usage = ImageUsage {
+            sampled: true,
+            transfer_destination: true,
+            ..ImageUsage::none()
+        };
+
+        ImmutableImage::uninitialized(
+            device.clone(),
+            dimensions,
+            Format::R8G8B8A8Unorm,
+            MipmapsCount::One,
+            usage,
+            ImageCreateFlags::none(),
+            ImageLayout::ShaderReadOnlyOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        // Packed and scaled formats are rarely sampleable.
+        let candidates = [
+            Format::R8G8B8Uscaled,
+            Format::R16G16B16Sscaled,
+            Format::B8G8R8Sscaled,
+            Format::R64G64B64A64Sfloat,
+        ];
+        let not_sampleable = candidates.iter().cloned().find(|format| {
+            !format
+                .properties(device.physical_device())
+                .optimal_tiling_features
+                .sampled_image
+        });
+
+        if let Some(format) = not_sampleable {
+            match ImmutableImage::uninitialized(
+                device,
+                dimensions,
+                format,
+                MipmapsCount::One,
+                usage,
+                ImageCreateFlags::none(),
+                ImageLayout::ShaderReadOnlyOptimal,
+                Some(queue.family()),
+            ) {
+                Err(ImageCreationError::FormatNotSampleable { format: f }) if f == format => (),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn memory_requirements_for() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_reqs = ImmutableImage::memory_requirements_for(
+            device,
+            ImageDimensions::Dim2d {
+                width: 64,
+                height: 64,
+                array_layers: 1,
+            },
+            R8G8B8A8Unorm,
+            MipmapsCount::Log2,
+            ImageUsage {
+                sampled: true,
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+        )
+        .unwrap();
+
+        assert!(mem_reqs.size > 0);
+        assert!(mem_reqs.alignment.is_power_of_two());
+    }
+
+    #[test]
+    fn from_many() {
+        let (_device, queue) = gfx_dev_and_queue!();
+
+        let sources = vec![
+            (
+                vec![0u8; 2 * 2 * 4].into_iter(),
+                ImageDimensions::Dim2d {
+                    width: 2,
+                    height: 2,
+                    array_layers: 1,
+                },
+                R8G8B8A8Unorm,
+            ),
+            (
+                vec![1u8; 3 * 4].into_iter(),
+                ImageDimensions::Dim2d {
+                    width: 3,
+                    height: 1,
+                    array_layers: 1,
+                },
+                R8G8B8A8Unorm,
+            ),
+            (
+                vec![2u8; 4 * 4 * 2 * 4].into_iter(),
+                ImageDimensions::Dim2d {
+                    width: 4,
+                    height: 4,
+                    array_layers: 2,
+                },
+                R8G8B8A8Unorm,
+            ),
+        ];
+
+        let (images, future) =
+            ImmutableImage::from_many(sources, MipmapsCount::One, queue).unwrap();
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(images.len(), 3);
+        assert_eq!(
+            images[1].as_ref().unwrap().dimensions().width_height(),
+            [3, 1]
+        );
+        assert_eq!(images[2].as_ref().unwrap().dimensions().array_layers(), 2);
+    }
+
+    #[test]
+    fn from_many_source_too_small() {
+        let (_device, queue) = gfx_dev_and_queue!();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: 2,
+            height: 2,
+            array_layers: 1,
+        };
+        let sources = vec![
+            (vec![0u8; 2 * 2 * 4].into_iter(), dimensions, R8G8B8A8Unorm),
+            (vec![0u8; 3].into_iter(), dimensions, R8G8B8A8Unorm),
+        ];
+
+        let (images, future) =
+            ImmutableImage::from_many(sources, MipmapsCount::One, queue).unwrap();
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert!(images[0].is_ok());
+        match images[1] {
+            Err(ImageCreationError::SourceDataTooSmall {
+                required_len: 16,
+                obtained_len: 3,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn from_slice_matches_from_iter() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: 4,
+            height: 4,
+            array_layers: 1,
+        };
+        let data: Vec<u8> = (0..4 * 4 * 4).map(|i| i as u8).collect();
+
+        // Generating mipmaps gives the images the `transfer_source` usage, needed to read them.
+        let (from_slice, future1) = ImmutableImage::from_slice(
+            &data,
+            dimensions,
+            MipmapsCount::Log2,
+            R8G8B8A8Unorm,
+            queue.clone(),
+        )
+        .unwrap();
+        let (from_iter, future2) = ImmutableImage::from_iter(
+            data.iter().cloned(),
+            dimensions,
+            MipmapsCount::Log2,
+            R8G8B8A8Unorm,
+            queue.clone(),
+        )
+        .unwrap();
+
+        let readback = || unsafe {
+            CpuAccessibleBuffer::<[u8]>::uninitialized_array(
+                device.clone(),
+                data.len(),
+                BufferUsage::transfer_destination(),
+                true,
+            )
+            .unwrap()
+        };
+        let (buffer1, buffer2) = (readback(), readback());
+
+        let mut cbb = AutoCommandBufferBuilder::new(device.clone(), queue.family()).unwrap();
+        cbb.copy_image_to_buffer(from_slice, buffer1.clone())
+            .unwrap()
+            .copy_image_to_buffer(from_iter, buffer2.clone())
+            .unwrap();
+        let cb = cbb.build().unwrap();
+
+        future1
+            .join(future2)
+            .then_execute(queue, cb)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(&*buffer1.read().unwrap(), &data[..]);
+        assert_eq!(&*buffer1.read().unwrap(), &*buffer2.read().unwrap());
+    }
+
+    #[test]
+    fn from_iter_keep_staging() {
+        let (_device, queue) = gfx_dev_and_queue!();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: 4,
+            height: 4,
+            array_layers: 1,
+        };
+
+        let (image, staging, future) = ImmutableImage::from_iter_keep_staging(
+            vec![0u8; 4 * 4 * 4].into_iter(),
+            dimensions,
+            MipmapsCount::One,
+            R8G8B8A8Unorm,
+            queue,
+        )
+        .unwrap();
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(image.dimensions().width_height(), [4, 4]);
+        assert_eq!(staging.len(), 4 * 4 * 4);
+        staging.write().unwrap();
+    }
+
+    #[test]
+    fn from_buffer_mip_chain() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: 8,
+            height: 8,
+            array_layers: 1,
+        };
+        // 8x8, 4x4, 2x2 and 1x1 levels.
+        let len = (64 + 16 + 4 + 1) * 4;
+        let source = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage::transfer_source(),
+            false,
+            vec![0u8; len].into_iter(),
+        )
+        .unwrap();
+
+        let (image, future) = ImmutableImage::from_buffer_mip_chain(
+            source.clone(),
+            dimensions,
+            MipmapsCount::Log2,
+            R8G8B8A8Unorm,
+            queue.clone(),
+        )
+        .unwrap();
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+        assert_eq!(image.mipmap_levels(), 4);
+
+        let too_small = source.into_buffer_slice().slice(0..len - 4).unwrap();
+        match ImmutableImage::from_buffer_mip_chain(
+            too_small,
+            dimensions,
+            MipmapsCount::Log2,
+            R8G8B8A8Unorm,
+            queue.clone(),
+        ) {
+            Err(ImageCreationError::SourceDataTooSmall {
+                required_len,
+                obtained_len,
+            }) => {
+                assert_eq!(required_len, len);
+                assert_eq!(obtained_len, len - 4);
+            }
+            _ => panic!(),
+        }
+
+        let no_transfer = CpuAccessibleBuffer::from_iter(
+            queue.device().clone(),
+            BufferUsage::uniform_buffer(),
+            false,
+            vec![0u8; len].into_iter(),
+        )
+        .unwrap();
+        match ImmutableImage::from_buffer_mip_chain(
+            no_transfer,
+            dimensions,
+            MipmapsCount::Log2,
+            R8G8B8A8Unorm,
+            queue,
+        ) {
+            Err(ImageCreationError::InvalidSourceData(
+                CheckCopyBufferImageError::SourceMissingTransferUsage,
+            )) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn from_compressed_mips_bc3() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: 16,
+            height: 16,
+            array_layers: 1,
+        };
+        // 16x16, 8x8, 4x4, 2x2 and 1x1 levels, in blocks of 4x4 texels and 16 bytes.
+        let lengths = [16 * 16, 4 * 16, 16, 16, 16];
+        let levels = || {
+            lengths
+                .iter()
+                .map(|&len| vec![0u8; len])
+                .collect::<Vec<_>>()
+        };
+
+        let mut wrong = levels();
+        wrong[2].truncate(8);
+        match ImmutableImage::from_compressed_mips(
+            wrong,
+            dimensions,
+            Format::BC3UnormBlock,
+            queue.clone(),
+        ) {
+            Err(ImageCreationError::WrongMipDataLength {
+                level: 2,
+                expected: 16,
+                actual: 8,
+            }) => (),
+            _ => panic!(),
+        }
+
+        let usage = ImageUsage {
+            sampled: true,
+            transfer_destination: true,
+            ..ImageUsage::none()
+        };
+        if !Format::BC3UnormBlock.is_supported(usage, &device) {
+            return;
+        }
+
+        let (image, future) = ImmutableImage::from_compressed_mips(
+            levels(),
+            dimensions,
+            Format::BC3UnormBlock,
+            queue,
+        )
+        .unwrap();
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+        assert_eq!(image.mipmap_levels(), 5);
+    }
+
+    #[test]
+    fn from_slice_too_small() {
+        let (_device, queue) = gfx_dev_and_queue!();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: 4,
+            height: 4,
+            array_layers: 1,
+        };
+
+        match ImmutableImage::from_slice(
+            &[0u8; 15],
+            dimensions,
+            MipmapsCount::One,
+            R8G8B8A8Unorm,
+            queue,
+        ) {
+            Err(ImageCreationError::SourceDataTooSmall {
+                required_len: 64,
+                obtained_len: 15,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use crate::image::ImageUsage;
 use crate::vk;
 
 /// Layout of an image.
@@ -41,6 +42,48 @@ pub enum ImageLayout {
     PresentSrc = vk::IMAGE_LAYOUT_PRESENT_SRC_KHR,
 }
 
+impl ImageLayout {
+    /// Returns the optimal layout for an image that is accessed with the given usage.
+    ///
+    /// Each usage has a matching optimal layout, except `storage` which requires `General`.
+    /// `General` is returned if `usage` contains several usages whose layouts differ, or if it
+    /// is empty. `transient_attachment` is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::image::ImageLayout;
+    /// use vulkano::image::ImageUsage;
+    ///
+    /// let usage = ImageUsage { sampled: true, input_attachment: true, .. ImageUsage::none() };
+    /// assert_eq!(ImageLayout::optimal_for_usage(usage), ImageLayout::ShaderReadOnlyOptimal);
+    /// ```
+    pub fn optimal_for_usage(usage: ImageUsage) -> ImageLayout {
+        let candidates = [
+            (usage.transfer_source, ImageLayout::TransferSrcOptimal),
+            (usage.transfer_destination, ImageLayout::TransferDstOptimal),
+            (usage.sampled, ImageLayout::ShaderReadOnlyOptimal),
+            (usage.storage, ImageLayout::General),
+            (usage.color_attachment, ImageLayout::ColorAttachmentOptimal),
+            (
+                usage.depth_stencil_attachment,
+                ImageLayout::DepthStencilAttachmentOptimal,
+            ),
+            (usage.input_attachment, ImageLayout::ShaderReadOnlyOptimal),
+        ];
+
+        let mut layouts = candidates
+            .iter()
+            .filter(|&&(used, _)| used)
+            .map(|&(_, layout)| layout);
+
+        match layouts.next() {
+            Some(first) if layouts.all(|layout| layout == first) => first,
+            _ => ImageLayout::General,
+        }
+    }
+}
+
 /// The set of layouts to use for an image when used in descriptor of various kinds.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ImageDescriptorLayouts {
@@ -49,24 +49,38 @@
 use std::cmp;
 use std::convert::TryFrom;
 
+use crate::device::Device;
+use crate::format::Format;
+use crate::format::FormatFeatures;
+
 pub use self::aspect::ImageAspect;
 pub use self::attachment::AttachmentImage;
+pub use self::barrier::barrier_between;
+pub use self::barrier::ImageMemoryBarrierInfo;
+pub use self::barrier::ImageUsageState;
 pub use self::immutable::ImmutableImage;
 pub use self::layout::ImageDescriptorLayouts;
 pub use self::layout::ImageLayout;
 pub use self::storage::StorageImage;
+pub use self::subresource::ImageSubresourceRange;
+pub use self::swapchain::ScreenshotError;
 pub use self::swapchain::SwapchainImage;
 pub use self::sys::ImageCreationError;
+pub use self::traits::AccessState;
+pub use self::traits::ConflictGranularity;
 pub use self::traits::ImageAccess;
+pub use self::traits::ImageDescription;
 pub use self::traits::ImageInner;
 pub use self::usage::ImageUsage;
 pub use self::view::ImageViewAbstract;
 
 mod aspect;
 pub mod attachment; // TODO: make private
+mod barrier;
 pub mod immutable; // TODO: make private
 mod layout;
 mod storage;
+mod subresource;
 pub mod swapchain; // TODO: make private
 pub mod sys;
 pub mod traits;
@@ -101,6 +115,7 @@ impl From<u32> for MipmapsCount {
 }
 
 /// Helper type for creating extents
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Extent {
     E1D([u32; 1]),
     E2D([u32; 2]),
@@ -150,7 +165,7 @@ impl TryFrom<Extent> for vk::Extent3D {
 /// Helper type returned from Device's `fn image_format_properties()`
 pub struct ImageFormatProperties {
     pub max_extent: Extent,
-    pub max_mip_levels: MipmapsCount,
+    pub max_mip_levels: u32,
     pub max_array_layers: u32,
     pub sample_counts: u32,
     pub max_resource_size: usize,
@@ -160,7 +175,7 @@ impl From<vk::ImageFormatProperties> for ImageFormatProperties {
     fn from(props: vk::ImageFormatProperties) -> Self {
         Self {
             max_extent: props.maxExtent.into(),
-            max_mip_levels: props.maxMipLevels.into(),
+            max_mip_levels: props.maxMipLevels,
             max_array_layers: props.maxArrayLayers,
             sample_counts: props.sampleCounts,
             max_resource_size: props.maxResourceSize as usize,
@@ -168,6 +183,79 @@ impl From<vk::ImageFormatProperties> for ImageFormatProperties {
     }
 }
 
+/// Everything that a device supports for a given combination of image attributes, as returned
+/// by Device's `fn image_capabilities()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ImageCapabilities {
+    /// The features of the format with the requested tiling.
+    pub format_features: FormatFeatures,
+    pub max_extent: Extent,
+    pub max_mip_levels: u32,
+    pub max_array_layers: u32,
+    /// The sample counts that an image with these attributes can have.
+    pub sample_counts: SampleCounts,
+    pub max_resource_size: usize,
+}
+
+/// The location of a mipmap level in a buffer, as returned by
+/// `ImageDimensions::packed_mip_layout`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MipLayout {
+    /// The index of the mipmap level.
+    pub level: u32,
+    /// The dimensions of the mipmap level.
+    pub dimensions: ImageDimensions,
+    /// The offset in bytes of the level from the start of the buffer.
+    pub buffer_offset: u64,
+    /// The size in bytes of the level, including every array layer.
+    pub byte_size: u64,
+}
+
+// Returns the greatest common divisor of `a` and `b`.
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// Helper type returned from Device's `fn sparse_image_format_properties()`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SparseImageFormatProperties {
+    /// The aspects of the image that these properties apply to.
+    pub aspects: ImageAspect,
+    /// The width, height and depth in texels of a sparse image block, or tile.
+    pub image_granularity: [u32; 3],
+    /// If true, the image uses a single mip tail region for all array layers.
+    pub single_miptail: bool,
+    /// If true, the first mipmap level whose dimensions are not multiples of the granularity
+    /// is the first level of the mip tail.
+    pub aligned_mip_size: bool,
+    /// If true, the image uses a non-standard block size, and `image_granularity` doesn't match
+    /// the standard sparse image block shape of the format.
+    pub nonstandard_block_size: bool,
+}
+
+impl From<vk::SparseImageFormatProperties> for SparseImageFormatProperties {
+    fn from(props: vk::SparseImageFormatProperties) -> Self {
+        Self {
+            aspects: ImageAspect::from_bits(props.aspectMask),
+            image_granularity: [
+                props.imageGranularity.width,
+                props.imageGranularity.height,
+                props.imageGranularity.depth,
+            ],
+            single_miptail: (props.flags & vk::SPARSE_IMAGE_FORMAT_SINGLE_MIPTAIL_BIT) != 0,
+            aligned_mip_size: (props.flags & vk::SPARSE_IMAGE_FORMAT_ALIGNED_MIP_SIZE_BIT) != 0,
+            nonstandard_block_size: (props.flags
+                & vk::SPARSE_IMAGE_FORMAT_NONSTANDARD_BLOCK_SIZE_BIT)
+                != 0,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub struct ImageCreateFlags {
     pub sparse_binding: bool,
@@ -220,6 +308,89 @@ impl From<ImageCreateFlags> for vk::ImageCreateFlags {
     }
 }
 
+/// A set of numbers of samples per pixel.
+///
+/// Used to describe which sample counts an image configuration supports.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct SampleCounts {
+    pub sample1: bool,
+    pub sample2: bool,
+    pub sample4: bool,
+    pub sample8: bool,
+    pub sample16: bool,
+    pub sample32: bool,
+    pub sample64: bool,
+}
+
+impl SampleCounts {
+    /// Builds a `SampleCounts` with all values set to false.
+    #[inline]
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if `samples` is part of this set.
+    ///
+    /// Always returns false if `samples` is not a power of two between 1 and 64.
+    #[inline]
+    pub fn contains(&self, samples: u32) -> bool {
+        match samples {
+            1 => self.sample1,
+            2 => self.sample2,
+            4 => self.sample4,
+            8 => self.sample8,
+            16 => self.sample16,
+            32 => self.sample32,
+            64 => self.sample64,
+            _ => false,
+        }
+    }
+}
+
+impl From<vk::SampleCountFlags> for SampleCounts {
+    #[inline]
+    fn from(flags: vk::SampleCountFlags) -> Self {
+        Self {
+            sample1: (flags & vk::SAMPLE_COUNT_1_BIT) != 0,
+            sample2: (flags & vk::SAMPLE_COUNT_2_BIT) != 0,
+            sample4: (flags & vk::SAMPLE_COUNT_4_BIT) != 0,
+            sample8: (flags & vk::SAMPLE_COUNT_8_BIT) != 0,
+            sample16: (flags & vk::SAMPLE_COUNT_16_BIT) != 0,
+            sample32: (flags & vk::SAMPLE_COUNT_32_BIT) != 0,
+            sample64: (flags & vk::SAMPLE_COUNT_64_BIT) != 0,
+        }
+    }
+}
+
+impl From<SampleCounts> for vk::SampleCountFlags {
+    #[inline]
+    fn from(counts: SampleCounts) -> Self {
+        let mut flags = Self::default();
+        if counts.sample1 {
+            flags |= vk::SAMPLE_COUNT_1_BIT;
+        }
+        if counts.sample2 {
+            flags |= vk::SAMPLE_COUNT_2_BIT;
+        }
+        if counts.sample4 {
+            flags |= vk::SAMPLE_COUNT_4_BIT;
+        }
+        if counts.sample8 {
+            flags |= vk::SAMPLE_COUNT_8_BIT;
+        }
+        if counts.sample16 {
+            flags |= vk::SAMPLE_COUNT_16_BIT;
+        }
+        if counts.sample32 {
+            flags |= vk::SAMPLE_COUNT_32_BIT;
+        }
+        if counts.sample64 {
+            flags |= vk::SAMPLE_COUNT_64_BIT;
+        }
+        flags
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ImageType {
     Dim1d,
@@ -251,6 +422,28 @@ impl From<ImageTiling> for vk::ImageTiling {
     }
 }
 
+/// A face of a cubemap, in the order of the array layers that back it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CubeFace {
+    PositiveX,
+    NegativeX,
+    PositiveY,
+    NegativeY,
+    PositiveZ,
+    NegativeZ,
+}
+
+impl CubeFace {
+    const ALL: [CubeFace; 6] = [
+        CubeFace::PositiveX,
+        CubeFace::NegativeX,
+        CubeFace::PositiveY,
+        CubeFace::NegativeY,
+        CubeFace::PositiveZ,
+        CubeFace::NegativeZ,
+    ];
+}
+
 /// The dimensions of an image.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ImageDimensions {
@@ -271,6 +464,16 @@ pub enum ImageDimensions {
 }
 
 impl ImageDimensions {
+    /// Returns the `ImageType` of an image with these dimensions.
+    #[inline]
+    pub fn image_type(&self) -> ImageType {
+        match *self {
+            ImageDimensions::Dim1d { .. } => ImageType::Dim1d,
+            ImageDimensions::Dim2d { .. } => ImageType::Dim2d,
+            ImageDimensions::Dim3d { .. } => ImageType::Dim3d,
+        }
+    }
+
     #[inline]
     pub fn width(&self) -> u32 {
         match *self {
@@ -317,12 +520,317 @@ impl ImageDimensions {
         }
     }
 
+    /// Returns the number of array layers needed to pack `sub_count` sub-images of these
+    /// dimensions into a single array image, for example a texture atlas.
+    ///
+    /// Each sub-image takes as many layers as these dimensions have, so this is `sub_count` for
+    /// dimensions with a single array layer. The result saturates at `u32::MAX`. Use
+    /// `validate_array_layers` to check it against the limits of the device.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let tile = ImageDimensions::Dim2d { width: 64, height: 64, array_layers: 1 };
+    /// assert_eq!(tile.atlas_layers(100), 100);
+    /// ```
+    ///
+    #[inline]
+    pub fn atlas_layers(&self, sub_count: u32) -> u32 {
+        self.array_layers().saturating_mul(sub_count)
+    }
+
+    /// Checks that an image with `layers` array layers doesn't exceed the
+    /// `max_image_array_layers` limit of `device`.
+    ///
+    /// Returns `ImageCreationError::TooManyArrayLayers` otherwise. This makes it possible to
+    /// detect that an array texture, for example an atlas that grows over time, has become too
+    /// large before trying to create it.
+    pub fn validate_array_layers(layers: u32, device: &Device) -> Result<(), ImageCreationError> {
+        let max = device.physical_device().limits().max_image_array_layers();
+        if layers > max {
+            return Err(ImageCreationError::TooManyArrayLayers {
+                requested: layers,
+                max,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if both the width and the height are even.
+    ///
+    /// Images whose format subsamples the chroma both horizontally and vertically, such as the
+    /// 4:2:0 YCbCr formats, must have even dimensions.
+    #[inline]
+    pub fn is_even(&self) -> bool {
+        self.width() % 2 == 0 && self.height() % 2 == 0
+    }
+
     /// Returns the total number of texels for an image of these dimensions.
     #[inline]
     pub fn num_texels(&self) -> u32 {
         self.width() * self.height() * self.depth() * self.array_layers()
     }
 
+    /// Returns the number of texels of the `level`th mipmap level, including all of its array
+    /// layers.
+    ///
+    /// Returns `None` if `level` is superior or equal to `max_mipmaps()`.
+    #[inline]
+    pub fn mipmap_texels(&self, level: u32) -> Option<u64> {
+        self.mipmap_dimensions(level).map(|dims| {
+            dims.width() as u64
+                * dims.height() as u64
+                * dims.depth() as u64
+                * dims.array_layers() as u64
+        })
+    }
+
+    /// Returns the number of bytes of a row of texels of the base mipmap level, for tightly
+    /// packed data of format `format`.
+    ///
+    /// For compressed formats, this is the size of a row of blocks. This is the stride to use
+    /// when reading an image that was copied to a buffer row by row.
+    ///
+    /// Returns `None` if `format` has no texel block size, which is the case of depth/stencil and
+    /// YCbCr formats.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 256, height: 256, array_layers: 1 };
+    /// assert_eq!(dims.bytes_per_row(Format::R8G8B8A8Unorm), Some(1024));
+    /// assert_eq!(dims.bytes_per_row(Format::BC1_RGBUnormBlock), Some(512));
+    /// ```
+    ///
+    pub fn bytes_per_row(&self, format: Format) -> Option<u64> {
+        let block_size = format.size()? as u64;
+        let (block_width, _) = format.block_dimensions();
+        let blocks = (self.width() + block_width - 1) / block_width;
+        Some(blocks as u64 * block_size)
+    }
+
+    /// Returns the layout of the first `levels` mipmap levels of an image of these dimensions and
+    /// of format `format`, when they are packed one after the other in a single buffer.
+    ///
+    /// The levels are stored in increasing order of index, starting with the largest one. Each
+    /// level contains all the array layers of `self`, tightly packed, which is why the number of
+    /// layers isn't a parameter. The offset of each level is aligned to the least common multiple
+    /// of the texel block size and 4, as required for buffer-image copies. The iterator stops
+    /// early if `levels` is greater than the number of mipmap levels that the dimensions allow.
+    ///
+    /// > **Note**: KTX2 files store the smallest level first, so their levels must be reordered
+    /// > to follow this layout.
+    ///
+    /// Returns `None` if `format` has no texel block size, which is the case of depth/stencil and
+    /// YCbCr formats.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 8, height: 8, array_layers: 1 };
+    /// let layout: Vec<_> = dims.packed_mip_layout(4, Format::R8Unorm).unwrap().collect();
+    /// assert_eq!(layout[1].buffer_offset, 64);
+    /// assert_eq!(layout[1].byte_size, 16);
+    /// assert_eq!(layout[3].buffer_offset, 84);
+    /// ```
+    ///
+    pub fn packed_mip_layout(
+        &self,
+        levels: u32,
+        format: Format,
+    ) -> Option<impl Iterator<Item = MipLayout>> {
+        let block_size = format.size()? as u64;
+        let (block_width, block_height) = format.block_dimensions();
+        let alignment = block_size * 4 / gcd(block_size, 4);
+        let dimensions = *self;
+
+        Some((0..levels).scan(0, move |buffer_offset, level| {
+            let dimensions = dimensions.mipmap_dimensions(level)?;
+            let blocks_x = (dimensions.width() + block_width - 1) / block_width;
+            let blocks_y = (dimensions.height() + block_height - 1) / block_height;
+            let byte_size = blocks_x as u64
+                * blocks_y as u64
+                * dimensions.depth() as u64
+                * dimensions.array_layers() as u64
+                * block_size;
+
+            let layout = MipLayout {
+                level,
+                dimensions,
+                buffer_offset: (*buffer_offset + alignment - 1) / alignment * alignment,
+                byte_size,
+            };
+            *buffer_offset = layout.buffer_offset + byte_size;
+
+            Some(layout)
+        }))
+    }
+
+    /// Returns the number of sparse image blocks, or tiles, needed to cover mipmap level `level`
+    /// of every array layer, using the tile granularity of `properties`.
+    ///
+    /// Partial tiles at the edges of the level count as whole tiles. Levels that are part of the
+    /// mip tail are not bound tile by tile, so the returned number only makes sense for levels
+    /// before the first level of the mip tail.
+    ///
+    /// Returns `None` if `level` is out of range, if the granularity or the dimensions contain a
+    /// zero, or if the number of tiles doesn't fit in a `u32`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::image::ImageAspect;
+    /// use vulkano::image::ImageDimensions;
+    /// use vulkano::image::SparseImageFormatProperties;
+    ///
+    /// let properties = SparseImageFormatProperties {
+    ///     aspects: ImageAspect { color: true, ..ImageAspect::none() },
+    ///     image_granularity: [128, 128, 1],
+    ///     single_miptail: false,
+    ///     aligned_mip_size: false,
+    ///     nonstandard_block_size: false,
+    /// };
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 300, height: 200, array_layers: 2 };
+    /// assert_eq!(dims.sparse_tile_count(&properties, 0), Some(12));
+    /// ```
+    ///
+    pub fn sparse_tile_count(
+        &self,
+        properties: &SparseImageFormatProperties,
+        level: u32,
+    ) -> Option<u32> {
+        let [granularity_width, granularity_height, granularity_depth] =
+            properties.image_granularity;
+        if granularity_width == 0 || granularity_height == 0 || granularity_depth == 0 {
+            return None;
+        }
+
+        if self.width() == 0 || self.height() == 0 || self.depth() == 0 || self.array_layers() == 0
+        {
+            return None;
+        }
+
+        let dimensions = self.mipmap_dimensions(level)?;
+
+        // The dimensions aren't 0, so this rounds up without overflowing.
+        let tiles_x = (dimensions.width() - 1) / granularity_width + 1;
+        let tiles_y = (dimensions.height() - 1) / granularity_height + 1;
+        let tiles_z = (dimensions.depth() - 1) / granularity_depth + 1;
+
+        tiles_x
+            .checked_mul(tiles_y)?
+            .checked_mul(tiles_z)?
+            .checked_mul(dimensions.array_layers())
+    }
+
+    /// Returns the number of workgroups to dispatch so that a compute shader with a local size
+    /// of `local_size` covers every texel of an image of these dimensions.
+    ///
+    /// The array layers are used as the last dimension of the dispatch: the `y` dimension for
+    /// 1D images and the `z` dimension for 2D images. Each count is at least 1.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the values of `local_size` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 1920, height: 1080, array_layers: 1 };
+    /// assert_eq!(dims.dispatch_groups([8, 8, 1]), [240, 135, 1]);
+    /// ```
+    ///
+    pub fn dispatch_groups(&self, local_size: [u32; 3]) -> [u32; 3] {
+        assert!(local_size.iter().all(|&s| s != 0));
+
+        let extent = match *self {
+            ImageDimensions::Dim1d {
+                width,
+                array_layers,
+            } => [width, array_layers, 1],
+            ImageDimensions::Dim2d {
+                width,
+                height,
+                array_layers,
+            } => [width, height, array_layers],
+            ImageDimensions::Dim3d {
+                width,
+                height,
+                depth,
+            } => [width, height, depth],
+        };
+
+        let groups = |value: u32, size: u32| ((value + size - 1) / size).max(1);
+
+        [
+            groups(extent[0], local_size[0]),
+            groups(extent[1], local_size[1]),
+            groups(extent[2], local_size[2]),
+        ]
+    }
+
+    /// Returns the dimensions of an image of format `dst_format` that contains the same texel
+    /// blocks as an image of these dimensions and of format `src_format`.
+    ///
+    /// This is the extent to use when copying between images whose formats have different block
+    /// dimensions, for example when copying a compressed image to an uncompressed one whose
+    /// texels have the same size as the compressed blocks.
+    ///
+    /// Returns `None` if an image of `src_format` can't be copied to an image of `dst_format`, as
+    /// determined by `Format::copy_compatible_with`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 256, height: 256, array_layers: 1 };
+    ///
+    /// assert_eq!(
+    ///     dims.reinterpret_extent(Format::BC1_RGBUnormBlock, Format::R16G16B16A16Uint),
+    ///     Some(ImageDimensions::Dim2d { width: 64, height: 64, array_layers: 1 }),
+    /// );
+    /// ```
+    ///
+    pub fn reinterpret_extent(
+        &self,
+        src_format: Format,
+        dst_format: Format,
+    ) -> Option<ImageDimensions> {
+        let ratio = src_format.copy_compatible_with(dst_format)?;
+        let [width, height, depth] = ratio.convert_extent(self.width_height_depth());
+
+        Some(match *self {
+            ImageDimensions::Dim1d { array_layers, .. } => ImageDimensions::Dim1d {
+                width,
+                array_layers,
+            },
+            ImageDimensions::Dim2d { array_layers, .. } => ImageDimensions::Dim2d {
+                width,
+                height,
+                array_layers,
+            },
+            ImageDimensions::Dim3d { .. } => ImageDimensions::Dim3d {
+                width,
+                height,
+                depth,
+            },
+        })
+    }
+
     /// Returns the maximum number of mipmaps for these image dimensions.
     ///
     /// The returned value is always at least superior or equal to 1.
@@ -345,9 +853,169 @@ impl ImageDimensions {
         32 - (self.width() | self.height() | self.depth()).leading_zeros()
     }
 
+    /// Returns true if an image of these dimensions can have more than one mipmap level.
+    ///
+    /// A 1x1 image only ever has its base level, so there is no point in allocating or
+    /// generating a mip chain for it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 1, height: 1, array_layers: 1 };
+    /// assert!(!dims.supports_meaningful_mipmaps());
+    /// ```
+    ///
+    #[inline]
+    pub fn supports_meaningful_mipmaps(&self) -> bool {
+        self.max_mipmaps() > 1
+    }
+
+    /// Enumerates the faces of a cubemap or cube array image of these dimensions, along with the
+    /// array layer that holds each face. For cube arrays, the six faces of each cube are yielded
+    /// in turn.
+    ///
+    /// The layer indices are the same at every mipmap level, `mip` is only checked for validity.
+    ///
+    /// Returns `None` if these dimensions can't be those of a cube-compatible image, in other
+    /// words if they aren't 2D and square with a multiple of 6 array layers, or if `mip` is out
+    /// of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::image::CubeFace;
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 256, height: 256, array_layers: 12 };
+    /// let mut faces = dims.cube_face_layers(3).unwrap();
+    ///
+    /// assert_eq!(faces.next(), Some((CubeFace::PositiveX, 0)));
+    /// assert_eq!(faces.nth(6), Some((CubeFace::NegativeX, 7)));
+    /// ```
+    ///
+    pub fn cube_face_layers(&self, mip: u32) -> Option<impl Iterator<Item = (CubeFace, u32)>> {
+        match *self {
+            ImageDimensions::Dim2d {
+                width,
+                height,
+                array_layers,
+            } if width == height
+                && array_layers != 0
+                && array_layers % 6 == 0
+                && mip < self.max_mipmaps() =>
+            {
+                Some((0..array_layers).map(|layer| (CubeFace::ALL[(layer % 6) as usize], layer)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if a render area starting at `offset` and of size `extent` fits within the
+    /// width and height of these dimensions.
+    ///
+    /// The render area of a render pass must fit within every attachment of the framebuffer, see
+    /// `FramebufferAbstract::check_render_area`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let dims = ImageDimensions::Dim2d { width: 800, height: 600, array_layers: 1 };
+    ///
+    /// assert!(dims.contains_render_area([0, 0], [800, 600]));
+    /// assert!(!dims.contains_render_area([400, 0], [800, 600]));
+    /// ```
+    ///
+    pub fn contains_render_area(&self, offset: [u32; 2], extent: [u32; 2]) -> bool {
+        offset[0] as u64 + extent[0] as u64 <= self.width() as u64
+            && offset[1] as u64 + extent[1] as u64 <= self.height() as u64
+    }
+
+    /// Returns the intersection of these dimensions with `other`, in other words the largest
+    /// dimensions that fit in both. This is for example the extent of a framebuffer whose
+    /// attachments have these two dimensions.
+    ///
+    /// Returns `None` if the two dimensions are not of the same type (1D, 2D or 3D).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::image::ImageDimensions;
+    ///
+    /// let a = ImageDimensions::Dim2d { width: 800, height: 600, array_layers: 2 };
+    /// let b = ImageDimensions::Dim2d { width: 1024, height: 512, array_layers: 1 };
+    ///
+    /// assert_eq!(a.intersect(&b), Some(ImageDimensions::Dim2d {
+    ///     width: 800,
+    ///     height: 512,
+    ///     array_layers: 1,
+    /// }));
+    /// ```
+    ///
+    pub fn intersect(&self, other: &ImageDimensions) -> Option<ImageDimensions> {
+        Some(match (*self, *other) {
+            (
+                ImageDimensions::Dim1d {
+                    width,
+                    array_layers,
+                },
+                ImageDimensions::Dim1d {
+                    width: other_width,
+                    array_layers: other_array_layers,
+                },
+            ) => ImageDimensions::Dim1d {
+                width: cmp::min(width, other_width),
+                array_layers: cmp::min(array_layers, other_array_layers),
+            },
+
+            (
+                ImageDimensions::Dim2d {
+                    width,
+                    height,
+                    array_layers,
+                },
+                ImageDimensions::Dim2d {
+                    width: other_width,
+                    height: other_height,
+                    array_layers: other_array_layers,
+                },
+            ) => ImageDimensions::Dim2d {
+                width: cmp::min(width, other_width),
+                height: cmp::min(height, other_height),
+                array_layers: cmp::min(array_layers, other_array_layers),
+            },
+
+            (
+                ImageDimensions::Dim3d {
+                    width,
+                    height,
+                    depth,
+                },
+                ImageDimensions::Dim3d {
+                    width: other_width,
+                    height: other_height,
+                    depth: other_depth,
+                },
+            ) => ImageDimensions::Dim3d {
+                width: cmp::min(width, other_width),
+                height: cmp::min(height, other_height),
+                depth: cmp::min(depth, other_depth),
+            },
+
+            _ => return None,
+        })
+    }
+
     /// Returns the dimensions of the `level`th mipmap level. If `level` is 0, then the dimensions
     /// are left unchanged.
     ///
+    /// The returned dimensions are always of the same variant as `self` and have the same number
+    /// of array layers. In particular, the mipmaps of a `Dim3d` image are `Dim3d` and never have
+    /// array layers, as the depth is reduced like the other dimensions.
+    ///
     /// Returns `None` if `level` is superior or equal to `max_mipmaps()`.
     ///
     /// # Example
@@ -394,7 +1062,7 @@ impl ImageDimensions {
             return None;
         }
 
-        Some(match *self {
+        let dimensions = match *self {
             ImageDimensions::Dim1d {
                 width,
                 array_layers,
@@ -433,16 +1101,34 @@ impl ImageDimensions {
                     depth: cmp::max(1, depth >> level),
                 }
             }
-        })
+        };
+
+        debug_assert_eq!(dimensions.image_type(), self.image_type());
+        debug_assert_eq!(dimensions.array_layers(), self.array_layers());
+
+        Some(dimensions)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::format;
+    use crate::format::Format;
+    use crate::image::AttachmentImage;
+    use crate::image::CubeFace;
+    use crate::image::ImageAccess;
+    use crate::image::ImageAspect;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageCreationError;
     use crate::image::ImageDimensions;
+    use crate::image::ImageLayout;
+    use crate::image::ImageUsage;
     use crate::image::ImmutableImage;
     use crate::image::MipmapsCount;
+    use crate::image::SparseImageFormatProperties;
+    use crate::image::StorageImage;
+    use crate::sync::PipelineStages;
+    use std::cmp;
 
     #[test]
     fn max_mipmaps() {
@@ -468,6 +1154,158 @@ mod tests {
         assert_eq!(dims.max_mipmaps(), 10);
     }
 
+    #[test]
+    fn atlas_layers() {
+        let tile = ImageDimensions::Dim2d {
+            width: 32,
+            height: 32,
+            array_layers: 1,
+        };
+        assert_eq!(tile.atlas_layers(0), 0);
+        assert_eq!(tile.atlas_layers(37), 37);
+
+        let tile = ImageDimensions::Dim2d {
+            width: 32,
+            height: 32,
+            array_layers: 6,
+        };
+        assert_eq!(tile.atlas_layers(10), 60);
+        assert_eq!(tile.atlas_layers(u32::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn validate_array_layers() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let max = device.physical_device().limits().max_image_array_layers();
+        ImageDimensions::validate_array_layers(max, &device).unwrap();
+
+        match ImageDimensions::validate_array_layers(max + 1, &device) {
+            Err(ImageCreationError::TooManyArrayLayers { requested, max: m }) => {
+                assert_eq!(requested, max + 1);
+                assert_eq!(m, max);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn cube_face_layers() {
+        let dims = ImageDimensions::Dim2d {
+            width: 64,
+            height: 64,
+            array_layers: 6,
+        };
+        let faces: Vec<_> = dims.cube_face_layers(0).unwrap().collect();
+        assert_eq!(
+            faces,
+            vec![
+                (CubeFace::PositiveX, 0),
+                (CubeFace::NegativeX, 1),
+                (CubeFace::PositiveY, 2),
+                (CubeFace::NegativeY, 3),
+                (CubeFace::PositiveZ, 4),
+                (CubeFace::NegativeZ, 5),
+            ]
+        );
+        assert_eq!(dims.cube_face_layers(6).unwrap().count(), 6);
+        assert!(dims.cube_face_layers(7).is_none());
+
+        let array = ImageDimensions::Dim2d {
+            width: 64,
+            height: 64,
+            array_layers: 18,
+        };
+        let faces: Vec<_> = array.cube_face_layers(2).unwrap().collect();
+        assert_eq!(faces.len(), 18);
+        assert_eq!(faces[13], (CubeFace::NegativeX, 13));
+
+        let not_square = ImageDimensions::Dim2d {
+            width: 64,
+            height: 32,
+            array_layers: 6,
+        };
+        assert!(not_square.cube_face_layers(0).is_none());
+        let not_six = ImageDimensions::Dim2d {
+            width: 64,
+            height: 64,
+            array_layers: 4,
+        };
+        assert!(not_six.cube_face_layers(0).is_none());
+        let dim3d = ImageDimensions::Dim3d {
+            width: 64,
+            height: 64,
+            depth: 6,
+        };
+        assert!(dim3d.cube_face_layers(0).is_none());
+    }
+
+    #[test]
+    fn supports_meaningful_mipmaps() {
+        let dims = ImageDimensions::Dim2d {
+            width: 1,
+            height: 1,
+            array_layers: 1,
+        };
+        assert!(!dims.supports_meaningful_mipmaps());
+
+        let dims = ImageDimensions::Dim2d {
+            width: 512,
+            height: 512,
+            array_layers: 1,
+        };
+        assert!(dims.supports_meaningful_mipmaps());
+    }
+
+    #[test]
+    fn contains_render_area() {
+        let dims = ImageDimensions::Dim2d {
+            width: 512,
+            height: 256,
+            array_layers: 1,
+        };
+
+        assert!(dims.contains_render_area([0, 0], [512, 256]));
+        assert!(dims.contains_render_area([256, 128], [256, 128]));
+        assert!(!dims.contains_render_area([0, 0], [512, 257]));
+        assert!(!dims.contains_render_area([1, 0], [512, 256]));
+        assert!(!dims.contains_render_area([u32::MAX, 0], [1, 1]));
+    }
+
+    #[test]
+    fn mipmap_dimensions_preserve_variant() {
+        let all_dims = [
+            ImageDimensions::Dim1d {
+                width: 300,
+                array_layers: 5,
+            },
+            ImageDimensions::Dim2d {
+                width: 283,
+                height: 175,
+                array_layers: 6,
+            },
+            ImageDimensions::Dim3d {
+                width: 64,
+                height: 17,
+                depth: 200,
+            },
+        ];
+
+        for dims in all_dims.iter() {
+            for level in 0..dims.max_mipmaps() {
+                let mip = dims.mipmap_dimensions(level).unwrap();
+                assert_eq!(mip.image_type(), dims.image_type());
+                assert_eq!(mip.array_layers(), dims.array_layers());
+
+                if let ImageDimensions::Dim3d { .. } = mip {
+                    assert_eq!(mip.array_layers(), 1);
+                    assert_eq!(mip.depth(), cmp::max(1, dims.depth() >> level));
+                }
+            }
+            assert_eq!(dims.mipmap_dimensions(dims.max_mipmaps()), None);
+        }
+    }
+
     #[test]
     fn mipmap_dimensions() {
         let dims = ImageDimensions::Dim2d {
@@ -544,6 +1382,228 @@ mod tests {
         assert_eq!(dims.mipmap_dimensions(9), None);
     }
 
+    #[test]
+    fn mipmap_texels() {
+        let dims = ImageDimensions::Dim2d {
+            width: 512,
+            height: 512,
+            array_layers: 1,
+        };
+        assert_eq!(dims.mipmap_texels(0), Some(512 * 512));
+        assert_eq!(dims.mipmap_texels(1), Some(256 * 256));
+        assert_eq!(dims.mipmap_texels(9), Some(1));
+        assert_eq!(dims.mipmap_texels(10), None);
+    }
+
+    #[test]
+    fn dispatch_groups() {
+        let dims = ImageDimensions::Dim2d {
+            width: 1920,
+            height: 1080,
+            array_layers: 1,
+        };
+        assert_eq!(dims.dispatch_groups([8, 8, 1]), [240, 135, 1]);
+        assert_eq!(dims.dispatch_groups([16, 16, 1]), [120, 68, 1]);
+
+        let dims = ImageDimensions::Dim2d {
+            width: 100,
+            height: 1,
+            array_layers: 6,
+        };
+        assert_eq!(dims.dispatch_groups([64, 4, 4]), [2, 1, 2]);
+
+        let dims = ImageDimensions::Dim3d {
+            width: 32,
+            height: 32,
+            depth: 9,
+        };
+        assert_eq!(dims.dispatch_groups([4, 4, 4]), [8, 8, 3]);
+    }
+
+    #[test]
+    fn reinterpret_extent() {
+        let dims = ImageDimensions::Dim2d {
+            width: 130,
+            height: 64,
+            array_layers: 2,
+        };
+        assert_eq!(
+            dims.reinterpret_extent(Format::BC1_RGBUnormBlock, Format::R16G16B16A16Unorm),
+            Some(ImageDimensions::Dim2d {
+                width: 33,
+                height: 16,
+                array_layers: 2,
+            })
+        );
+
+        let dims = ImageDimensions::Dim2d {
+            width: 33,
+            height: 16,
+            array_layers: 2,
+        };
+        assert_eq!(
+            dims.reinterpret_extent(Format::R16G16B16A16Unorm, Format::BC1_RGBUnormBlock),
+            Some(ImageDimensions::Dim2d {
+                width: 132,
+                height: 64,
+                array_layers: 2,
+            })
+        );
+
+        assert_eq!(
+            dims.reinterpret_extent(Format::BC1_RGBUnormBlock, Format::R8G8B8A8Unorm),
+            None
+        );
+    }
+
+    #[test]
+    fn intersect() {
+        let a = ImageDimensions::Dim2d {
+            width: 1920,
+            height: 1080,
+            array_layers: 1,
+        };
+        let b = ImageDimensions::Dim2d {
+            width: 1280,
+            height: 1440,
+            array_layers: 1,
+        };
+        let expected = ImageDimensions::Dim2d {
+            width: 1280,
+            height: 1080,
+            array_layers: 1,
+        };
+        assert_eq!(a.intersect(&b), Some(expected));
+        assert_eq!(b.intersect(&a), Some(expected));
+
+        let c = ImageDimensions::Dim1d {
+            width: 1280,
+            array_layers: 1,
+        };
+        assert_eq!(a.intersect(&c), None);
+    }
+
+    #[test]
+    fn bytes_per_row() {
+        let dims = ImageDimensions::Dim2d {
+            width: 30,
+            height: 30,
+            array_layers: 1,
+        };
+        assert_eq!(dims.bytes_per_row(Format::R8G8B8A8Unorm), Some(30 * 4));
+        assert_eq!(dims.bytes_per_row(Format::BC1_RGBUnormBlock), Some(8 * 8));
+        assert_eq!(dims.bytes_per_row(Format::G8B8R8_3PLANE420Unorm), None);
+        assert!(dims
+            .packed_mip_layout(1, Format::G8B8R8_3PLANE420Unorm)
+            .is_none());
+
+        let dims = ImageDimensions::Dim1d {
+            width: 64,
+            array_layers: 2,
+        };
+        assert_eq!(
+            dims.bytes_per_row(Format::R32G32B32A32Sfloat),
+            Some(64 * 16)
+        );
+        assert_eq!(
+            dims.bytes_per_row(Format::BC1_RGBUnormBlock),
+            Some(64 / 4 * 8)
+        );
+    }
+
+    #[test]
+    fn sparse_tile_count_overflow() {
+        let properties = |image_granularity| SparseImageFormatProperties {
+            aspects: ImageAspect {
+                color: true,
+                ..ImageAspect::none()
+            },
+            image_granularity,
+            single_miptail: false,
+            aligned_mip_size: false,
+            nonstandard_block_size: false,
+        };
+        let dims = ImageDimensions::Dim2d {
+            width: u32::MAX,
+            height: u32::MAX,
+            array_layers: 1,
+        };
+
+        assert_eq!(dims.sparse_tile_count(&properties([1, 1, 1]), 0), None);
+        assert_eq!(
+            dims.sparse_tile_count(&properties([u32::MAX, u32::MAX, 1]), 0),
+            Some(1)
+        );
+        assert_eq!(
+            dims.sparse_tile_count(&properties([128, u32::MAX, 1]), 0),
+            Some(33554432)
+        );
+
+        let empty = ImageDimensions::Dim2d {
+            width: 0,
+            height: 32,
+            array_layers: 1,
+        };
+        assert_eq!(empty.sparse_tile_count(&properties([1, 1, 1]), 0), None);
+        assert_eq!(empty.sparse_tile_count(&properties([1, 1, 1]), 1), None);
+    }
+
+    #[test]
+    fn packed_mip_layout_bc3() {
+        let dims = ImageDimensions::Dim2d {
+            width: 256,
+            height: 256,
+            array_layers: 1,
+        };
+        let layout: Vec<_> = dims
+            .packed_mip_layout(9, Format::BC3UnormBlock)
+            .unwrap()
+            .collect();
+        assert_eq!(layout.len(), 9);
+
+        let sizes: Vec<_> = layout.iter().map(|l| l.byte_size).collect();
+        assert_eq!(sizes, [65536, 16384, 4096, 1024, 256, 64, 16, 16, 16]);
+        let offsets: Vec<_> = layout.iter().map(|l| l.buffer_offset).collect();
+        assert_eq!(
+            offsets,
+            [0, 65536, 81920, 86016, 87040, 87296, 87360, 87376, 87392]
+        );
+
+        for (level, l) in layout.iter().enumerate() {
+            assert_eq!(l.level, level as u32);
+            assert_eq!(l.dimensions, dims.mipmap_dimensions(level as u32).unwrap());
+            assert_eq!(l.buffer_offset % 16, 0);
+        }
+
+        // Requesting more levels than the image can have stops at the last one.
+        assert_eq!(
+            dims.packed_mip_layout(20, Format::BC3UnormBlock)
+                .unwrap()
+                .count(),
+            9
+        );
+    }
+
+    #[test]
+    fn packed_mip_layout_alignment() {
+        let dims = ImageDimensions::Dim2d {
+            width: 5,
+            height: 5,
+            array_layers: 2,
+        };
+        let layout: Vec<_> = dims
+            .packed_mip_layout(3, Format::R8G8B8Unorm)
+            .unwrap()
+            .collect();
+
+        // 5x5x2 texels of 3 bytes, then the offsets are aligned to 12 bytes.
+        assert_eq!(layout[0].byte_size, 150);
+        assert_eq!(layout[1].buffer_offset, 156);
+        assert_eq!(layout[1].byte_size, 24);
+        assert_eq!(layout[2].buffer_offset, 180);
+        assert_eq!(layout[2].byte_size, 6);
+    }
+
     #[test]
     fn mipmap_working_immutable_image() {
         let (device, queue) = gfx_dev_and_queue!();
@@ -584,4 +1644,171 @@ mod tests {
             assert_eq!(image.mipmap_levels(), 10);
         }
     }
+
+    #[test]
+    fn full_subresource_range_color() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = StorageImage::new(
+            device,
+            ImageDimensions::Dim2d {
+                width: 16,
+                height: 16,
+                array_layers: 3,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let color = ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        };
+        let depth = ImageAspect {
+            depth: true,
+            ..ImageAspect::none()
+        };
+
+        let full = image.full_subresource_range();
+        assert_eq!(full.aspects, color);
+        assert_eq!(full.mipmap_levels, 0..1);
+        assert_eq!(full.array_layers, 0..3);
+
+        assert_eq!(image.subresource_range_for_aspect(color), full);
+        assert_eq!(
+            image.subresource_range_for_aspect(color | depth).aspects,
+            color
+        );
+        assert_eq!(
+            image.subresource_range_for_aspect(depth).aspects,
+            ImageAspect::none()
+        );
+    }
+
+    #[test]
+    fn full_subresource_range_depth_stencil() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // One of these two formats is always supported as a depth/stencil attachment.
+        let image = AttachmentImage::new(device.clone(), [16, 16], Format::D24Unorm_S8Uint)
+            .or_else(|_| AttachmentImage::new(device, [16, 16], Format::D32Sfloat_S8Uint))
+            .unwrap();
+
+        let depth = ImageAspect {
+            depth: true,
+            ..ImageAspect::none()
+        };
+        let stencil = ImageAspect {
+            stencil: true,
+            ..ImageAspect::none()
+        };
+
+        let full = image.full_subresource_range();
+        assert_eq!(full.aspects, depth | stencil);
+        assert_eq!(full.mipmap_levels, 0..1);
+        assert_eq!(full.array_layers, 0..1);
+
+        let depth_range = image.subresource_range_for_aspect(depth);
+        assert_eq!(depth_range.aspects, depth);
+        assert_eq!(depth_range.mipmap_levels, full.mipmap_levels);
+        assert_eq!(depth_range.array_layers, full.array_layers);
+    }
+
+    #[test]
+    fn whole_image_barrier() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (image, _) = ImmutableImage::uninitialized(
+            device,
+            ImageDimensions::Dim2d {
+                width: 64,
+                height: 64,
+                array_layers: 4,
+            },
+            Format::R8G8B8A8Unorm,
+            MipmapsCount::Log2,
+            ImageUsage {
+                sampled: true,
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            ImageLayout::ShaderReadOnlyOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let transfer = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+        let fragment = PipelineStages {
+            fragment_shader: true,
+            ..PipelineStages::none()
+        };
+
+        let (range, barrier) = image.whole_image_barrier(
+            ImageLayout::TransferDstOptimal,
+            ImageLayout::ShaderReadOnlyOptimal,
+            transfer,
+            fragment,
+        );
+        assert_eq!(range.mipmap_levels, 0..7);
+        assert_eq!(range.array_layers, 0..4);
+        assert!(range.aspects.color);
+        assert_eq!(barrier.source_stages, transfer);
+        assert_eq!(barrier.destination_stages, fragment);
+        assert!(barrier.source_access.is_compatible_with(&transfer));
+        assert!(barrier.destination_access.is_compatible_with(&fragment));
+        assert_eq!(barrier.old_layout, ImageLayout::TransferDstOptimal);
+        assert_eq!(barrier.new_layout, ImageLayout::ShaderReadOnlyOptimal);
+    }
+
+    #[test]
+    fn whole_image_barrier_multi_planar() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = match StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim2d {
+                width: 64,
+                height: 64,
+                array_layers: 1,
+            },
+            Format::G8B8R8_3PLANE420Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            Some(queue.family()),
+        ) {
+            Ok(image) => image,
+            // Multi-planar formats are optional.
+            Err(_) => return,
+        };
+
+        assert!(image.full_subresource_range().aspects.plane2);
+
+        let (range, _) = image.whole_image_barrier(
+            ImageLayout::Undefined,
+            ImageLayout::ShaderReadOnlyOptimal,
+            PipelineStages {
+                top_of_pipe: true,
+                ..PipelineStages::none()
+            },
+            PipelineStages {
+                fragment_shader: true,
+                ..PipelineStages::none()
+            },
+        );
+        assert_eq!(
+            range.aspects,
+            ImageAspect {
+                color: true,
+                ..ImageAspect::none()
+            }
+        );
+    }
 }
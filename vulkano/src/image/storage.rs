@@ -10,6 +10,8 @@
 use smallvec::SmallVec;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter::Empty;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -17,10 +19,13 @@ use std::sync::Arc;
 use crate::buffer::BufferAccess;
 use crate::device::Device;
 use crate::format::ClearValue;
+use crate::format::Format;
 use crate::format::FormatDesc;
 use crate::format::FormatTy;
 use crate::image::sys::ImageCreationError;
 use crate::image::sys::UnsafeImage;
+use crate::image::traits::AccessState;
+use crate::image::traits::ConflictGranularity;
 use crate::image::traits::ImageAccess;
 use crate::image::traits::ImageClearValue;
 use crate::image::traits::ImageContent;
@@ -37,23 +42,24 @@ use crate::memory::pool::MappingRequirement;
 use crate::memory::pool::MemoryPool;
 use crate::memory::pool::MemoryPoolAlloc;
 use crate::memory::pool::PotentialDedicatedAllocation;
-use crate::memory::pool::StdMemoryPool;
+use crate::memory::pool::StdMemoryPoolAlloc;
 use crate::memory::DedicatedAlloc;
+use crate::memory::MemoryRequirements;
 use crate::sync::AccessError;
 use crate::sync::Sharing;
 
 /// General-purpose image in device memory. Can be used for any usage, but will be slower than a
 /// specialized image.
 #[derive(Debug)]
-pub struct StorageImage<F, A = Arc<StdMemoryPool>>
-where
-    A: MemoryPool,
-{
+pub struct StorageImage<F, A = PotentialDedicatedAllocation<StdMemoryPoolAlloc>> {
     // Inner implementation.
     image: UnsafeImage,
 
     // Memory used to back the image.
-    memory: PotentialDedicatedAllocation<A::Alloc>,
+    memory: A,
+
+    // Index of the memory type of `memory`.
+    memory_type_index: u32,
 
     // Dimensions of the image.
     dimensions: ImageDimensions,
@@ -66,6 +72,9 @@ where
 
     // Number of times this image is locked on the GPU side.
     gpu_lock: AtomicUsize,
+
+    // True if the last successful call to `try_gpu_lock` requested exclusive access.
+    gpu_lock_exclusive: AtomicBool,
 }
 
 impl<F> StorageImage<F> {
@@ -104,6 +113,43 @@ impl<F> StorageImage<F> {
         StorageImage::with_usage(device, dimensions, format, usage, flags, queue_families)
     }
 
+    /// Creates a new storage image on which compute shaders can perform atomic operations, for
+    /// example to build a histogram in an `R32Uint` image.
+    ///
+    /// The image has the `storage`, `transfer_source` and `transfer_destination` usages. Returns
+    /// `ImageCreationError::FormatNotSupportedForAtomics` if the format doesn't support atomic
+    /// operations on storage images with optimal tiling.
+    pub fn new_atomic<'a, I>(
+        device: Arc<Device>,
+        dimensions: ImageDimensions,
+        format: F,
+        queue_families: I,
+    ) -> Result<Arc<StorageImage<F>>, ImageCreationError>
+    where
+        F: FormatDesc,
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        let features = format
+            .format()
+            .properties(device.physical_device())
+            .optimal_tiling_features;
+        if !features.storage_image_atomic {
+            return Err(ImageCreationError::FormatNotSupportedForAtomics {
+                format: format.format(),
+            });
+        }
+
+        let usage = ImageUsage {
+            transfer_source: true,
+            transfer_destination: true,
+            storage: true,
+            ..ImageUsage::none()
+        };
+        let flags = ImageCreateFlags::none();
+
+        StorageImage::with_usage(device, dimensions, format, usage, flags, queue_families)
+    }
+
     /// Same as `new`, but allows specifying the usage.
     pub fn with_usage<'a, I>(
         device: Arc<Device>,
@@ -117,6 +163,103 @@ impl<F> StorageImage<F> {
         F: FormatDesc,
         I: IntoIterator<Item = QueueFamily<'a>>,
     {
+        StorageImage::with_view_formats(
+            device,
+            dimensions,
+            format,
+            usage,
+            flags,
+            &[],
+            queue_families,
+        )
+    }
+
+    /// Same as `with_usage`, but also specifies the formats that views of the image can have.
+    ///
+    /// `flags.mutable_format` must be set if `view_formats` is not empty, and each format must be
+    /// compatible with `format`.
+    pub fn with_view_formats<'a, I>(
+        device: Arc<Device>,
+        dimensions: ImageDimensions,
+        format: F,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+        view_formats: &[Format],
+        queue_families: I,
+    ) -> Result<Arc<StorageImage<F>>, ImageCreationError>
+    where
+        F: FormatDesc,
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        StorageImage::with_pool(
+            &Device::standard_pool(&device),
+            dimensions,
+            format,
+            usage,
+            flags,
+            view_formats,
+            queue_families,
+        )
+    }
+
+    /// Returns the memory requirements of an image created with `with_usage` and the same
+    /// parameters, without allocating any memory.
+    ///
+    /// This creates a temporary image that is destroyed before returning. It is useful to plan
+    /// allocations with a custom allocator.
+    pub fn memory_requirements_for(
+        device: Arc<Device>,
+        dimensions: ImageDimensions,
+        format: F,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+    ) -> Result<MemoryRequirements, ImageCreationError>
+    where
+        F: FormatDesc,
+    {
+        let (_image, mem_reqs) = unsafe {
+            UnsafeImage::new(
+                device,
+                usage,
+                format.format(),
+                flags,
+                dimensions,
+                1,
+                1,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                false,
+            )?
+        };
+
+        Ok(mem_reqs)
+    }
+}
+
+impl<F, A> StorageImage<F, PotentialDedicatedAllocation<A>>
+where
+    A: MemoryPoolAlloc,
+{
+    /// Same as `with_view_formats`, but allocates the memory of the image from `pool` instead of
+    /// the standard pool of the device.
+    ///
+    /// This is the integration point for custom allocators: implement the `MemoryPool` trait,
+    /// for example on top of a sub-allocator, to control where the memory of the image comes from.
+    pub fn with_pool<'a, P, I>(
+        pool: &P,
+        dimensions: ImageDimensions,
+        format: F,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+        view_formats: &[Format],
+        queue_families: I,
+    ) -> Result<Arc<Self>, ImageCreationError>
+    where
+        F: FormatDesc,
+        P: MemoryPool<Alloc = A>,
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        let device = pool.device();
         let queue_families = queue_families
             .into_iter()
             .map(|f| f.id())
@@ -129,7 +272,7 @@ impl<F> StorageImage<F> {
                 Sharing::Exclusive
             };
 
-            UnsafeImage::new(
+            UnsafeImage::with_view_formats(
                 device.clone(),
                 usage,
                 format.format(),
@@ -140,11 +283,12 @@ impl<F> StorageImage<F> {
                 sharing,
                 false,
                 false,
+                view_formats,
             )?
         };
 
         let memory = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
+            pool,
             &mem_reqs,
             AllocLayout::Optimal,
             MappingRequirement::DoNotMap,
@@ -159,24 +303,25 @@ impl<F> StorageImage<F> {
         )?;
         debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
         unsafe {
-            image.bind_memory(memory.memory(), memory.offset())?;
+            image
+                .bind_memory(memory.memory(), memory.offset())
+                .map_err(ImageCreationError::BindFailed)?;
         }
 
         Ok(Arc::new(StorageImage {
             image,
+            memory_type_index: memory.memory().memory_type().id(),
             memory,
             dimensions,
             format,
             queue_families,
             gpu_lock: AtomicUsize::new(0),
+            gpu_lock_exclusive: AtomicBool::new(false),
         }))
     }
 }
 
-impl<F, A> StorageImage<F, A>
-where
-    A: MemoryPool,
-{
+impl<F, A> StorageImage<F, A> {
     /// Returns the dimensions of the image.
     #[inline]
     pub fn dimensions(&self) -> ImageDimensions {
@@ -187,7 +332,6 @@ where
 unsafe impl<F, A> ImageAccess for StorageImage<F, A>
 where
     F: 'static + Send + Sync,
-    A: MemoryPool,
 {
     #[inline]
     fn inner(&self) -> ImageInner {
@@ -200,6 +344,11 @@ where
         }
     }
 
+    #[inline]
+    fn memory_type_index(&self) -> Option<u32> {
+        Some(self.memory_type_index)
+    }
+
     #[inline]
     fn initial_layout_requirement(&self) -> ImageLayout {
         ImageLayout::General
@@ -236,7 +385,16 @@ where
     }
 
     #[inline]
-    fn try_gpu_lock(&self, _: bool, expected_layout: ImageLayout) -> Result<(), AccessError> {
+    fn conflict_granularity(&self) -> ConflictGranularity {
+        ConflictGranularity::PerSubresource
+    }
+
+    #[inline]
+    fn try_gpu_lock(
+        &self,
+        exclusive_access: bool,
+        expected_layout: ImageLayout,
+    ) -> Result<(), AccessError> {
         // TODO: handle initial layout transition
         if expected_layout != ImageLayout::General && expected_layout != ImageLayout::Undefined {
             return Err(AccessError::UnexpectedImageLayout {
@@ -250,6 +408,8 @@ where
             .compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst)
             .unwrap_or_else(|e| e);
         if val == 0 {
+            self.gpu_lock_exclusive
+                .store(exclusive_access, Ordering::SeqCst);
             Ok(())
         } else {
             Err(AccessError::AlreadyInUse)
@@ -268,6 +428,15 @@ where
         self.gpu_lock.fetch_sub(1, Ordering::SeqCst);
     }
 
+    #[inline]
+    fn current_access_state(&self) -> Option<AccessState> {
+        Some(AccessState {
+            layout: ImageLayout::General,
+            gpu_locks: self.gpu_lock.load(Ordering::SeqCst),
+            exclusive: self.gpu_lock_exclusive.load(Ordering::SeqCst),
+        })
+    }
+
     #[inline]
     fn current_miplevels_access(&self) -> std::ops::Range<u32> {
         0..self.mipmap_levels()
@@ -282,7 +451,6 @@ where
 unsafe impl<F, A> ImageClearValue<F::ClearValue> for StorageImage<F, A>
 where
     F: FormatDesc + 'static + Send + Sync,
-    A: MemoryPool,
 {
     #[inline]
     fn decode(&self, value: F::ClearValue) -> Option<ClearValue> {
@@ -293,7 +461,6 @@ where
 unsafe impl<P, F, A> ImageContent<P> for StorageImage<F, A>
 where
     F: 'static + Send + Sync,
-    A: MemoryPool,
 {
     #[inline]
     fn matches_format(&self) -> bool {
@@ -304,7 +471,6 @@ where
 impl<F, A> PartialEq for StorageImage<F, A>
 where
     F: 'static + Send + Sync,
-    A: MemoryPool,
 {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
@@ -312,17 +478,11 @@ where
     }
 }
 
-impl<F, A> Eq for StorageImage<F, A>
-where
-    F: 'static + Send + Sync,
-    A: MemoryPool,
-{
-}
+impl<F, A> Eq for StorageImage<F, A> where F: 'static + Send + Sync {}
 
 impl<F, A> Hash for StorageImage<F, A>
 where
     F: 'static + Send + Sync,
-    A: MemoryPool,
 {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -333,8 +493,43 @@ where
 #[cfg(test)]
 mod tests {
     use super::StorageImage;
+    use crate::device::Device;
     use crate::format::Format;
+    use crate::image::sys::ImageCreationError;
+    use crate::image::ConflictGranularity;
+    use crate::image::ImageAccess;
+    use crate::image::ImageCreateFlags;
     use crate::image::ImageDimensions;
+    use crate::image::ImageLayout;
+    use crate::image::ImageTiling;
+    use crate::image::ImageUsage;
+    use crate::sync::AccessError;
+    use crate::tests::CountingPool;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn memory_requirements_for() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_reqs = StorageImage::memory_requirements_for(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                storage: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+        )
+        .unwrap();
+
+        assert!(mem_reqs.size >= 32 * 32 * 4);
+        assert!(mem_reqs.alignment.is_power_of_two());
+    }
 
     #[test]
     fn create() {
@@ -351,4 +546,326 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn hdr_packed_storage() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let dimensions = ImageDimensions::Dim2d {
+            width: 32,
+            height: 32,
+            array_layers: 1,
+        };
+
+        for &format in &[Format::B10G11R11UfloatPack32, Format::E5B9G9R9UfloatPack32] {
+            let features = format
+                .properties(device.physical_device())
+                .optimal_tiling_features;
+            let res = StorageImage::new(device.clone(), dimensions, format, Some(queue.family()));
+            match (format, features.storage_image) {
+                (_, true) => assert!(res.is_ok()),
+                (Format::E5B9G9R9UfloatPack32, false) => match res {
+                    Err(ImageCreationError::UnsupportedHdrPackedUsage { format: f, usage }) => {
+                        assert_eq!(f, format);
+                        assert!(usage.storage);
+                    }
+                    _ => panic!(),
+                },
+                (_, false) => assert!(res.is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn conflict_granularity() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let img = StorageImage::new(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 3,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            img.conflict_granularity(),
+            ConflictGranularity::PerSubresource
+        );
+        let range = img.subresource_range();
+        assert!(range.aspects.color);
+        assert_eq!(range.mipmap_levels, 0..1);
+        assert_eq!(range.array_layers, 0..3);
+        assert_eq!(range.subresources().count(), 3);
+    }
+
+    #[test]
+    fn current_access_state() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let img = StorageImage::new(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let state = img.current_access_state().unwrap();
+        assert_eq!(state.layout, ImageLayout::General);
+        assert_eq!(state.gpu_locks, 0);
+
+        img.try_gpu_lock(true, ImageLayout::General).unwrap();
+        match img.try_gpu_lock(false, ImageLayout::General) {
+            Err(AccessError::AlreadyInUse) => (),
+            _ => panic!(),
+        }
+        let state = img.current_access_state().unwrap();
+        assert_eq!(state.gpu_locks, 1);
+        assert!(state.exclusive);
+
+        unsafe {
+            img.unlock(None);
+        }
+        assert_eq!(img.current_access_state().unwrap().gpu_locks, 0);
+    }
+
+    #[test]
+    fn supported_sample_counts() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let img = StorageImage::new(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        assert!(img.supported_sample_counts().sample1);
+        assert!(img.supported_sample_counts().contains(img.samples()));
+    }
+
+    #[test]
+    fn view_formats() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let flags = ImageCreateFlags {
+            mutable_format: true,
+            ..ImageCreateFlags::none()
+        };
+        let _img = StorageImage::with_view_formats(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            flags,
+            &[Format::R8G8B8A8Unorm, Format::R8G8B8A8Srgb],
+            Some(queue.family()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn incompatible_view_format() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let flags = ImageCreateFlags {
+            mutable_format: true,
+            ..ImageCreateFlags::none()
+        };
+        let res = StorageImage::with_view_formats(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            flags,
+            &[Format::R8G8B8A8Unorm, Format::R16G16B16A16Sfloat],
+            Some(queue.family()),
+        );
+
+        match res {
+            Err(ImageCreationError::IncompatibleViewFormat { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn full_description() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let dimensions = ImageDimensions::Dim2d {
+            width: 32,
+            height: 32,
+            array_layers: 1,
+        };
+        let img = StorageImage::new(
+            device,
+            dimensions,
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let description = img.full_description();
+        assert_eq!(description.dimensions, dimensions);
+        assert_eq!(description.format, Format::R8G8B8A8Unorm);
+        assert_eq!(description.samples, 1);
+        assert_eq!(description.mip_levels, 1);
+        assert!(description.usage.storage);
+        assert_eq!(description.tiling, ImageTiling::Optimal);
+        assert_eq!(description.flags, ImageCreateFlags::none());
+    }
+
+    #[test]
+    fn supports_cube_view() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+        let create = |array_layers, flags| {
+            StorageImage::with_usage(
+                device.clone(),
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers,
+                },
+                Format::R8G8B8A8Unorm,
+                usage,
+                flags,
+                Some(queue.family()),
+            )
+            .unwrap()
+        };
+        let cube_compatible = ImageCreateFlags {
+            cube_compatible: true,
+            ..ImageCreateFlags::none()
+        };
+
+        assert!(create(6, cube_compatible).supports_cube_view());
+        assert!(create(12, cube_compatible).supports_cube_view());
+        assert!(!create(8, cube_compatible).supports_cube_view());
+        assert!(!create(6, ImageCreateFlags::none()).supports_cube_view());
+
+        // A cube-compatible image can't be created with different width and height.
+        let non_square = StorageImage::with_usage(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 16,
+                array_layers: 6,
+            },
+            Format::R8G8B8A8Unorm,
+            usage,
+            cube_compatible,
+            Some(queue.family()),
+        );
+        match non_square {
+            Err(ImageCreationError::CreationFlagRequirementsNotMet) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn new_atomic() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let dimensions = ImageDimensions::Dim2d {
+            width: 32,
+            height: 32,
+            array_layers: 1,
+        };
+
+        match StorageImage::new_atomic(
+            device.clone(),
+            dimensions,
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        ) {
+            Err(ImageCreationError::FormatNotSupportedForAtomics {
+                format: Format::R8G8B8A8Unorm,
+            }) => (),
+            _ => panic!(),
+        }
+
+        let supports_atomics = Format::R32Uint
+            .properties(device.physical_device())
+            .optimal_tiling_features
+            .storage_image_atomic;
+        let img =
+            StorageImage::new_atomic(device, dimensions, Format::R32Uint, Some(queue.family()));
+        assert_eq!(img.is_ok(), supports_atomics);
+    }
+
+    #[test]
+    fn memory_type() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let img = StorageImage::new(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let index = img.memory_type_index().unwrap();
+        assert!(device.physical_device().memory_type_by_id(index).is_some());
+        assert!(img.is_device_local());
+    }
+
+    #[test]
+    fn with_pool() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let pool = CountingPool {
+            inner: Device::standard_pool(&device),
+            allocations: AtomicUsize::new(0),
+        };
+
+        let _img = StorageImage::with_pool(
+            &pool,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                storage: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags {
+                mutable_format: true,
+                ..ImageCreateFlags::none()
+            },
+            &[Format::R8G8B8A8Unorm, Format::R8G8B8A8Uint],
+            Some(queue.family()),
+        )
+        .unwrap();
+        assert_eq!(pool.allocations.load(Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,171 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::image::ImageAspect;
+use crate::vk;
+use std::ops::Range;
+
+/// A range of subresources of an image: some of its aspects, for a range of mipmap levels and a
+/// range of array layers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImageSubresourceRange {
+    /// The aspects of the image that are part of the range.
+    pub aspects: ImageAspect,
+    /// The mipmap levels that are part of the range.
+    pub mipmap_levels: Range<u32>,
+    /// The array layers that are part of the range.
+    pub array_layers: Range<u32>,
+}
+
+impl ImageSubresourceRange {
+    /// Returns true if at least one subresource is part of both `self` and `other`.
+    ///
+    /// Two views of the same image can only be accessed at the same time without a barrier if
+    /// their ranges don't overlap, or if neither of them is written to. The color aspect of a
+    /// multi-planar image covers all of its planes, and therefore overlaps every plane aspect.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use vulkano::image::ImageAspect;
+    /// use vulkano::image::ImageSubresourceRange;
+    ///
+    /// let color = ImageAspect { color: true, .. ImageAspect::none() };
+    /// let read = ImageSubresourceRange { aspects: color, mipmap_levels: 0..1, array_layers: 0..1 };
+    /// let write = ImageSubresourceRange { aspects: color, mipmap_levels: 1..2, array_layers: 0..1 };
+    /// assert!(!read.overlaps(&write));
+    /// ```
+    pub fn overlaps(&self, other: &ImageSubresourceRange) -> bool {
+        let ranges_overlap = |a: &Range<u32>, b: &Range<u32>| a.start < b.end && b.start < a.end;
+
+        aspects_overlap(self.aspects, other.aspects)
+            && ranges_overlap(&self.mipmap_levels, &other.mipmap_levels)
+            && ranges_overlap(&self.array_layers, &other.array_layers)
+    }
+
+    /// Enumerates the subresources of the range, as `(mipmap_level, array_layer)` pairs. Each
+    /// pair stands for all the aspects of the range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use vulkano::image::ImageAspect;
+    /// use vulkano::image::ImageSubresourceRange;
+    ///
+    /// let color = ImageAspect { color: true, .. ImageAspect::none() };
+    /// let range = ImageSubresourceRange { aspects: color, mipmap_levels: 0..2, array_layers: 3..5 };
+    /// let subresources: Vec<_> = range.subresources().collect();
+    /// assert_eq!(subresources, [(0, 3), (0, 4), (1, 3), (1, 4)]);
+    /// ```
+    pub fn subresources(&self) -> impl Iterator<Item = (u32, u32)> {
+        let array_layers = self.array_layers.clone();
+        self.mipmap_levels
+            .clone()
+            .flat_map(move |level| array_layers.clone().map(move |layer| (level, layer)))
+    }
+}
+
+impl From<ImageSubresourceRange> for vk::ImageSubresourceRange {
+    #[inline]
+    fn from(range: ImageSubresourceRange) -> Self {
+        vk::ImageSubresourceRange {
+            aspectMask: range.aspects.to_aspect_bits(),
+            baseMipLevel: range.mipmap_levels.start,
+            levelCount: range.mipmap_levels.end - range.mipmap_levels.start,
+            baseArrayLayer: range.array_layers.start,
+            layerCount: range.array_layers.end - range.array_layers.start,
+        }
+    }
+}
+
+fn aspects_overlap(a: ImageAspect, b: ImageAspect) -> bool {
+    let is_plane = |aspects: ImageAspect| aspects.plane0 || aspects.plane1 || aspects.plane2;
+
+    a.to_aspect_bits() & b.to_aspect_bits() != 0
+        || (a.color && is_plane(b))
+        || (b.color && is_plane(a))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::ImageAspect;
+    use crate::image::ImageSubresourceRange;
+
+    fn range(
+        aspects: ImageAspect,
+        mipmap_levels: std::ops::Range<u32>,
+        array_layers: std::ops::Range<u32>,
+    ) -> ImageSubresourceRange {
+        ImageSubresourceRange {
+            aspects,
+            mipmap_levels,
+            array_layers,
+        }
+    }
+
+    #[test]
+    fn overlapping_mipmap_levels() {
+        let color = ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        };
+
+        assert!(range(color, 0..3, 0..1).overlaps(&range(color, 2..5, 0..1)));
+        assert!(range(color, 2..5, 0..1).overlaps(&range(color, 0..3, 0..1)));
+        assert!(range(color, 0..4, 0..6).overlaps(&range(color, 1..2, 5..6)));
+    }
+
+    #[test]
+    fn disjoint_ranges() {
+        let color = ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        };
+
+        assert!(!range(color, 0..2, 0..1).overlaps(&range(color, 2..4, 0..1)));
+        assert!(!range(color, 0..1, 0..3).overlaps(&range(color, 0..1, 3..6)));
+        assert!(!range(color, 0..2, 0..3).overlaps(&range(color, 2..4, 3..6)));
+    }
+
+    #[test]
+    fn different_aspects() {
+        let depth = ImageAspect {
+            depth: true,
+            ..ImageAspect::none()
+        };
+        let stencil = ImageAspect {
+            stencil: true,
+            ..ImageAspect::none()
+        };
+        let color = ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        };
+        let plane1 = ImageAspect {
+            plane1: true,
+            ..ImageAspect::none()
+        };
+
+        assert!(!range(depth, 0..1, 0..1).overlaps(&range(stencil, 0..1, 0..1)));
+        assert!(range(depth | stencil, 0..1, 0..1).overlaps(&range(stencil, 0..1, 0..1)));
+        assert!(range(color, 0..1, 0..1).overlaps(&range(plane1, 0..1, 0..1)));
+    }
+
+    #[test]
+    fn subresources() {
+        let color = ImageAspect {
+            color: true,
+            ..ImageAspect::none()
+        };
+
+        let all: Vec<_> = range(color, 1..3, 2..4).subresources().collect();
+        assert_eq!(all, [(1, 2), (1, 3), (2, 2), (2, 3)]);
+        assert_eq!(range(color, 0..0, 0..4).subresources().count(), 0);
+    }
+}
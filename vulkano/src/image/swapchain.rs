@@ -7,11 +7,18 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::error;
+use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::Arc;
 
 use crate::buffer::BufferAccess;
+use crate::buffer::BufferUsage;
+use crate::buffer::CpuAccessibleBuffer;
+use crate::command_buffer::AutoCommandBufferBuilder;
+use crate::command_buffer::CopyBufferImageError;
+use crate::device::DeviceOwned;
 use crate::format::ClearValue;
 use crate::format::Format;
 use crate::format::FormatDesc;
@@ -21,6 +28,8 @@ use crate::image::traits::ImageContent;
 use crate::image::ImageDescriptorLayouts;
 use crate::image::ImageInner;
 use crate::image::ImageLayout;
+use crate::image::ImageUsage;
+use crate::memory::DeviceMemoryAllocError;
 use crate::swapchain::Swapchain;
 use crate::sync::AccessError;
 
@@ -76,6 +85,12 @@ impl<W> SwapchainImage<W> {
         &self.swapchain
     }
 
+    /// Returns the usage of the image, which is the usage the swapchain was created with.
+    #[inline]
+    pub fn usage(&self) -> ImageUsage {
+        self.swapchain.usage()
+    }
+
     #[inline]
     fn my_image(&self) -> ImageInner {
         self.swapchain.raw_image(self.image_offset).unwrap()
@@ -93,6 +108,50 @@ impl<W> SwapchainImage<W> {
     }
 }
 
+impl<W> SwapchainImage<W>
+where
+    W: Send + Sync + 'static,
+{
+    /// Adds to `builder` a command that copies the content of this image to a new
+    /// CPU-accessible buffer, in order to read it back from the host. This can be used to take a
+    /// screenshot.
+    ///
+    /// The texels are written to the buffer tightly packed, row after row, in the format of the
+    /// swapchain. The buffer must only be read once the command buffer has finished executing.
+    ///
+    /// The swapchain must have been created with the `transfer_source` usage, otherwise
+    /// `ScreenshotError::SwapchainNotTransferSource` is returned.
+    pub fn screenshot<L>(
+        self: &Arc<Self>,
+        builder: &mut AutoCommandBufferBuilder<L>,
+    ) -> Result<Arc<CpuAccessibleBuffer<[u8]>>, ScreenshotError> {
+        if !self.usage().transfer_source {
+            return Err(ScreenshotError::SwapchainNotTransferSource);
+        }
+
+        let [width, height] = self.swapchain.dimensions();
+        let texel_size = self
+            .swapchain
+            .format()
+            .size()
+            .expect("swapchain formats always have a texel size");
+        let len = width as usize * height as usize * texel_size;
+
+        let buffer = unsafe {
+            CpuAccessibleBuffer::uninitialized_array(
+                self.swapchain.device().clone(),
+                len,
+                BufferUsage::transfer_destination(),
+                true,
+            )?
+        };
+
+        builder.copy_image_to_buffer(self.clone(), buffer.clone())?;
+
+        Ok(buffer)
+    }
+}
+
 unsafe impl<W> ImageAccess for SwapchainImage<W> {
     #[inline]
     fn inner(&self) -> ImageInner {
@@ -202,3 +261,188 @@ impl<W> Hash for SwapchainImage<W> {
         ImageAccess::inner(self).hash(state);
     }
 }
+
+/// Error that can happen when calling `SwapchainImage::screenshot`.
+#[derive(Debug, Clone)]
+pub enum ScreenshotError {
+    /// The swapchain was not created with the `transfer_source` usage. Add this usage to the
+    /// `usage` parameter of `Swapchain::new`.
+    SwapchainNotTransferSource,
+    /// Allocating the buffer that receives the content of the image failed.
+    AllocError(DeviceMemoryAllocError),
+    /// Recording the copy command failed.
+    CopyError(CopyBufferImageError),
+}
+
+impl error::Error for ScreenshotError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ScreenshotError::AllocError(ref err) => Some(err),
+            ScreenshotError::CopyError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ScreenshotError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                ScreenshotError::SwapchainNotTransferSource => {
+                    "the swapchain was not created with the `transfer_source` usage, which must be \
+                     added to the usage passed when creating the swapchain"
+                }
+                ScreenshotError::AllocError(_) => "allocating the destination buffer failed",
+                ScreenshotError::CopyError(_) => "recording the copy command failed",
+            }
+        )
+    }
+}
+
+impl From<DeviceMemoryAllocError> for ScreenshotError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> ScreenshotError {
+        ScreenshotError::AllocError(err)
+    }
+}
+
+impl From<CopyBufferImageError> for ScreenshotError {
+    #[inline]
+    fn from(err: CopyBufferImageError) -> ScreenshotError {
+        ScreenshotError::CopyError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScreenshotError;
+    use super::SwapchainImage;
+    use crate::buffer::TypedBufferAccess;
+    use crate::command_buffer::AutoCommandBufferBuilder;
+    use crate::device::Device;
+    use crate::device::DeviceExtensions;
+    use crate::device::DeviceOwned;
+    use crate::device::Queue;
+    use crate::features::Features;
+    use crate::image::ImageUsage;
+    use crate::instance::Instance;
+    use crate::instance::InstanceExtensions;
+    use crate::instance::PhysicalDevice;
+    use crate::swapchain::display::Display;
+    use crate::swapchain::display::DisplayPlane;
+    use crate::swapchain::FullscreenExclusive;
+    use crate::swapchain::PresentMode;
+    use crate::swapchain::Surface;
+    use crate::swapchain::SurfaceTransform;
+    use crate::swapchain::Swapchain;
+    use std::sync::Arc;
+
+    // Creates a swapchain on a display surface, as there is no window to present to. Returns
+    // `None` if the implementation doesn't support it.
+    fn display_swapchain_image(usage: ImageUsage) -> Option<(Arc<Queue>, Arc<SwapchainImage<()>>)> {
+        let extensions = InstanceExtensions {
+            khr_surface: true,
+            khr_display: true,
+            ..InstanceExtensions::none()
+        };
+        let instance = Instance::new(None, &extensions, None).ok()?;
+        let physical = PhysicalDevice::enumerate(&instance).next()?;
+        let queue_family = physical.queue_families().find(|q| q.supports_graphics())?;
+
+        let display = Display::enumerate_raw(physical).ok()?.next()?;
+        let mode = display.display_modes_raw().ok()?.next()?;
+        let plane = DisplayPlane::enumerate_raw(physical)
+            .ok()?
+            .find(|plane| plane.supports(&display))?;
+        let surface = Surface::<()>::from_display_mode(&mode, &plane).ok()?;
+        if !surface.is_supported(queue_family).ok()? {
+            return None;
+        }
+        let caps = surface.capabilities(physical).ok()?;
+        if !caps.supported_usage_flags.transfer_source {
+            return None;
+        }
+
+        let (device, mut queues) = Device::new(
+            physical,
+            &Features::none(),
+            &DeviceExtensions {
+                khr_swapchain: true,
+                ..DeviceExtensions::none()
+            },
+            [(queue_family, 0.5)].iter().cloned(),
+        )
+        .ok()?;
+        let queue = queues.next().unwrap();
+
+        let (format, color_space) = caps.supported_formats[0];
+        let (_, mut images) = Swapchain::new(
+            device.clone(),
+            surface,
+            caps.min_image_count,
+            format,
+            caps.current_extent.unwrap_or(mode.visible_region()),
+            1,
+            usage,
+            &queue,
+            SurfaceTransform::Identity,
+            caps.supported_composite_alpha.iter().next()?,
+            PresentMode::Fifo,
+            FullscreenExclusive::Default,
+            true,
+            color_space,
+        )
+        .ok()?;
+
+        Some((queue, images.remove(0)))
+    }
+
+    #[test]
+    fn screenshot_not_transfer_source() {
+        let usage = ImageUsage {
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        let (queue, image) = match display_swapchain_image(usage) {
+            Some(r) => r,
+            None => return,
+        };
+
+        let mut builder = AutoCommandBufferBuilder::primary_one_time_submit(
+            queue.device().clone(),
+            queue.family(),
+        )
+        .unwrap();
+        match image.screenshot(&mut builder) {
+            Err(ScreenshotError::SwapchainNotTransferSource) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn screenshot_buffer_len() {
+        let usage = ImageUsage {
+            color_attachment: true,
+            transfer_source: true,
+            ..ImageUsage::none()
+        };
+        let (queue, image) = match display_swapchain_image(usage) {
+            Some(r) => r,
+            None => return,
+        };
+
+        let mut builder = AutoCommandBufferBuilder::primary_one_time_submit(
+            queue.device().clone(),
+            queue.family(),
+        )
+        .unwrap();
+        let buffer = image.screenshot(&mut builder).unwrap();
+        let [width, height] = image.dimensions();
+        let texel_size = image.swapchain().format().size().unwrap();
+        assert_eq!(buffer.len(), width as usize * height as usize * texel_size);
+    }
+}
@@ -24,20 +24,28 @@ use std::ops::Range;
 use std::ptr;
 use std::sync::Arc;
 
+use crate::command_buffer::validity::CheckCopyBufferImageError;
+use crate::command_buffer::CommandBufferExecError;
 use crate::device::Device;
 use crate::format::Format;
 use crate::format::FormatFeatures;
 use crate::format::FormatTy;
 use crate::format::PossibleYcbcrFormatDesc;
+use crate::image::Extent;
 use crate::image::ImageAspect;
 use crate::image::ImageCreateFlags;
 use crate::image::ImageDimensions;
+use crate::image::ImageLayout;
+use crate::image::ImageTiling;
+use crate::image::ImageType;
 use crate::image::ImageUsage;
 use crate::image::MipmapsCount;
+use crate::image::SampleCounts;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocError;
 use crate::memory::MemoryRequirements;
 use crate::sync::Sharing;
+use crate::version::Version;
 
 use crate::check_errors;
 use crate::vk;
@@ -64,6 +72,7 @@ pub struct UnsafeImage {
     usage: ImageUsage,
     format: Format,
     flags: ImageCreateFlags,
+    tiling: ImageTiling,
 
     dimensions: ImageDimensions,
     samples: u32,
@@ -72,6 +81,9 @@ pub struct UnsafeImage {
     // Features that are supported for this particular format.
     format_features: FormatFeatures,
 
+    // Sample counts that are supported for this particular format, usage and configuration.
+    supported_sample_counts: SampleCounts,
+
     // `vkDestroyImage` is called only if `needs_destruction` is true.
     needs_destruction: bool,
     preinitialized_layout: bool,
@@ -119,6 +131,60 @@ impl UnsafeImage {
             sharing,
             linear_tiling,
             preinitialized_layout,
+            &[],
+        )
+    }
+
+    /// Same as `new`, but also provides the list of formats that views of the image are allowed
+    /// to have.
+    ///
+    /// If `view_formats` is not empty, `flags.mutable_format` must be set and each format must be
+    /// compatible with `format`. The list is passed to the implementation through
+    /// `VkImageFormatListCreateInfo` if the `khr_image_format_list` extension is enabled or the
+    /// `api_version` of the device is at least Vulkan 1.2, and is otherwise only used for
+    /// validation.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the dimensions is 0.
+    /// - Panics if the number of mipmaps is 0.
+    /// - Panics if the number of samples is 0.
+    ///
+    #[inline]
+    pub unsafe fn with_view_formats<'a, Mi, I>(
+        device: Arc<Device>,
+        usage: ImageUsage,
+        format: Format,
+        flags: ImageCreateFlags,
+        dimensions: ImageDimensions,
+        num_samples: u32,
+        mipmaps: Mi,
+        sharing: Sharing<I>,
+        linear_tiling: bool,
+        preinitialized_layout: bool,
+        view_formats: &[Format],
+    ) -> Result<(UnsafeImage, MemoryRequirements), ImageCreationError>
+    where
+        Mi: Into<MipmapsCount>,
+        I: Iterator<Item = u32>,
+    {
+        let sharing = match sharing {
+            Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
+            Sharing::Concurrent(ids) => (vk::SHARING_MODE_CONCURRENT, ids.collect()),
+        };
+
+        UnsafeImage::new_impl(
+            device,
+            usage,
+            format,
+            flags,
+            dimensions,
+            num_samples,
+            mipmaps.into(),
+            sharing,
+            linear_tiling,
+            preinitialized_layout,
+            view_formats,
         )
     }
 
@@ -134,19 +200,46 @@ impl UnsafeImage {
         (sh_mode, sh_indices): (vk::SharingMode, SmallVec<[u32; 8]>),
         linear_tiling: bool,
         preinitialized_layout: bool,
+        view_formats: &[Format],
     ) -> Result<(UnsafeImage, MemoryRequirements), ImageCreationError> {
         // TODO: doesn't check that the proper features are enabled
 
-        if flags.sparse_binding
-            || flags.sparse_residency
-            || flags.sparse_aliased
-            || flags.mutable_format
-        {
-            unimplemented!();
+        // The content of an image in the `Preinitialized` layout can only be written by the host
+        // if the image uses linear tiling.
+        if preinitialized_layout && !linear_tiling {
+            return Err(ImageCreationError::PreinitializedLayoutRequiresLinearTiling);
+        }
+
+        // Checking the list of view formats.
+        if !view_formats.is_empty() {
+            if !flags.mutable_format {
+                return Err(ImageCreationError::CreationFlagRequirementsNotMet);
+            }
+
+            for &view_format in view_formats {
+                if !format.compatible_view_formats().any(|f| f == view_format) {
+                    return Err(ImageCreationError::IncompatibleViewFormat {
+                        format,
+                        view_format,
+                    });
+                }
+            }
         }
 
         let vk = device.pointers();
-        let vk_i = device.instance().pointers();
+
+        // The chroma planes of 4:2:0 formats have half the width and height of the luma plane.
+        match format {
+            Format::G8B8R8_3PLANE420Unorm | Format::G8B8R8_2PLANE420Unorm
+                if !dimensions.is_even() =>
+            {
+                return Err(ImageCreationError::OddDimensionForSubsampledFormat {
+                    format,
+                    dimensions,
+                });
+            }
+            _ => (),
+        }
 
         // Checking if image usage conforms to what is supported.
         let format_features = {
@@ -162,30 +255,17 @@ impl UnsafeImage {
                 return Err(ImageCreationError::FormatNotSupported);
             }
 
-            if usage.sampled && !features.sampled_image {
-                return Err(ImageCreationError::UnsupportedUsage);
-            }
-            if usage.storage && !features.storage_image {
-                return Err(ImageCreationError::UnsupportedUsage);
-            }
-            if usage.color_attachment && !features.color_attachment {
-                return Err(ImageCreationError::UnsupportedUsage);
-            }
-            if usage.depth_stencil_attachment && !features.depth_stencil_attachment {
-                return Err(ImageCreationError::UnsupportedUsage);
-            }
-            if usage.input_attachment
-                && !(features.color_attachment || features.depth_stencil_attachment)
+            // Give a dedicated error for the shared-exponent HDR format, which most devices can
+            // only sample from.
+            if format == Format::E5B9G9R9UfloatPack32
+                && ((usage.color_attachment && !features.color_attachment)
+                    || (usage.storage && !features.storage_image))
             {
-                return Err(ImageCreationError::UnsupportedUsage);
+                return Err(ImageCreationError::UnsupportedHdrPackedUsage { format, usage });
             }
-            if device.loaded_extensions().khr_maintenance1 {
-                if usage.transfer_source && !features.transfer_src {
-                    return Err(ImageCreationError::UnsupportedUsage);
-                }
-                if usage.transfer_destination && !features.transfer_dst {
-                    return Err(ImageCreationError::UnsupportedUsage);
-                }
+
+            if !features.supports_image_usage(usage, device.loaded_extensions().khr_maintenance1) {
+                return Err(ImageCreationError::UnsupportedUsage);
             }
 
             features
@@ -219,6 +299,22 @@ impl UnsafeImage {
         // check for additional image capabilities (section 31.4 of the specs).
         let mut capabilities_error = None;
 
+        // Querying the capabilities of the implementation for this image configuration. The
+        // sample counts are stored so that they can be returned by `supported_sample_counts()`
+        // without querying the implementation again.
+        let image_format_properties = {
+            let (image_type, tiling) = image_type_and_tiling(dimensions, linear_tiling);
+            match device.image_format_properties_raw(format, image_type, tiling, usage, flags) {
+                Ok(properties) => Some(properties),
+                Err(Error::FormatNotSupported) => None,
+                Err(err) => return Err(err.into()),
+            }
+        };
+        let supported_sample_counts = image_format_properties
+            .as_ref()
+            .map(|properties| SampleCounts::from(properties.sample_counts))
+            .unwrap_or_else(SampleCounts::none);
+
         // Compute the number of mipmaps.
         let mipmaps = match mipmaps.into() {
             MipmapsCount::Specific(num) => {
@@ -234,6 +330,15 @@ impl UnsafeImage {
                         obtained: num,
                         valid_range: 1..max_mipmaps + 1,
                     });
+                } else if let Some(properties) = image_format_properties.as_ref() {
+                    // Some formats, tilings or usages support fewer mipmaps than what the
+                    // dimensions would allow.
+                    if num > properties.max_mip_levels {
+                        return Err(ImageCreationError::TooManyMipmaps {
+                            requested: num,
+                            max: properties.max_mip_levels,
+                        });
+                    }
                 }
 
                 num
@@ -242,16 +347,26 @@ impl UnsafeImage {
             MipmapsCount::One => 1,
         };
 
+        validate_image_params(
+            dimensions.image_type(),
+            dimensions,
+            flags,
+            num_samples,
+            mipmaps,
+        )?;
+
+        // Sparse images are not supported yet. This comes after `validate_image_params` so that
+        // invalid combinations of sparse flags are still reported as errors.
+        if flags.sparse_binding || flags.sparse_residency || flags.sparse_aliased {
+            unimplemented!();
+        }
+
+        if num_samples > 1 && linear_tiling {
+            return Err(ImageCreationError::MultisampleRequirementsNotMet);
+        }
+
         // Checking whether the number of samples is supported.
-        if num_samples == 0 {
-            return Err(ImageCreationError::UnsupportedSamplesCount {
-                obtained: num_samples,
-            });
-        } else if !num_samples.is_power_of_two() {
-            return Err(ImageCreationError::UnsupportedSamplesCount {
-                obtained: num_samples,
-            });
-        } else {
+        {
             let mut supported_samples = 0x7f; // all bits up to VK_SAMPLE_COUNT_64_BIT
 
             if usage.sampled {
@@ -360,13 +475,7 @@ impl UnsafeImage {
             }
         }
 
-        // If the `shaderStorageImageMultisample` feature is not enabled and we have
-        // `usage_storage` set to true, then the number of samples must be 1.
-        if usage.storage && num_samples > 1 {
-            if !device.enabled_features().shader_storage_image_multisample {
-                return Err(ImageCreationError::ShaderStorageImageMultisampleFeatureNotEnabled);
-            }
-        }
+        usage.validate_with_samples(num_samples, &device)?;
 
         // Decoding the dimensions.
         let (ty, extent, array_layers) = match dimensions {
@@ -374,9 +483,6 @@ impl UnsafeImage {
                 width,
                 array_layers,
             } => {
-                if width == 0 || array_layers == 0 {
-                    return Err(ImageCreationError::UnsupportedDimensions { dimensions });
-                }
                 let extent = vk::Extent3D {
                     width,
                     height: 1,
@@ -389,9 +495,6 @@ impl UnsafeImage {
                 height,
                 array_layers,
             } => {
-                if width == 0 || height == 0 || array_layers == 0 {
-                    return Err(ImageCreationError::UnsupportedDimensions { dimensions });
-                }
                 let extent = vk::Extent3D {
                     width,
                     height,
@@ -404,9 +507,6 @@ impl UnsafeImage {
                 height,
                 depth,
             } => {
-                if width == 0 || height == 0 || depth == 0 {
-                    return Err(ImageCreationError::UnsupportedDimensions { dimensions });
-                }
                 let extent = vk::Extent3D {
                     width,
                     height,
@@ -416,22 +516,8 @@ impl UnsafeImage {
             }
         };
 
-        // Checking flags requirements.
-        if flags.cube_compatible {
-            if !(ty == vk::IMAGE_TYPE_2D && extent.width == extent.height && array_layers >= 6) {
-                return Err(ImageCreationError::CreationFlagRequirementsNotMet);
-            }
-        }
-
-        if flags.array_2d_compatible {
-            if !(ty == vk::IMAGE_TYPE_3D) {
-                return Err(ImageCreationError::CreationFlagRequirementsNotMet);
-            }
-        }
-
         // Checking the dimensions against the limits.
-        if array_layers > device.physical_device().limits().max_image_array_layers() {
-            let err = ImageCreationError::UnsupportedDimensions { dimensions };
+        if let Err(err) = ImageDimensions::validate_array_layers(array_layers, &device) {
             capabilities_error = Some(err);
         }
         match ty {
@@ -448,11 +534,15 @@ impl UnsafeImage {
                     capabilities_error = Some(err);
                 }
 
+                // Cube-compatible images have their own limit, which can be lower than the 2D
+                // limit and isn't reflected by the format properties queried below.
                 if flags.cube_compatible {
-                    let limit = device.physical_device().limits().max_image_dimension_cube();
-                    if extent.width > limit {
-                        let err = ImageCreationError::UnsupportedDimensions { dimensions };
-                        capabilities_error = Some(err);
+                    let max = device.physical_device().limits().max_image_dimension_cube();
+                    if extent.width > max {
+                        return Err(ImageCreationError::CubeDimensionsTooLarge {
+                            width: extent.width,
+                            max,
+                        });
                     }
                 }
             }
@@ -470,41 +560,24 @@ impl UnsafeImage {
 
         // Now that all checks have been performed, if any of the check failed we query the Vulkan
         // implementation for additional image capabilities.
+        // The properties were already queried above.
         if let Some(capabilities_error) = capabilities_error {
-            let tiling = if linear_tiling {
-                vk::IMAGE_TILING_LINEAR
-            } else {
-                vk::IMAGE_TILING_OPTIMAL
+            let properties = match image_format_properties {
+                Some(properties) => properties,
+                None => return Err(ImageCreationError::FormatNotSupported),
+            };
+            let max_extent = match properties.max_extent {
+                Extent::E1D([width]) => [width, 1, 1],
+                Extent::E2D([width, height]) => [width, height, 1],
+                Extent::E3D(extent) => extent,
             };
 
-            let mut output = MaybeUninit::uninit();
-            let physical_device = device.physical_device().internal_object();
-            let r = vk_i.GetPhysicalDeviceImageFormatProperties(
-                physical_device,
-                format as u32,
-                ty,
-                tiling,
-                usage_bits,
-                0, /* TODO */
-                output.as_mut_ptr(),
-            );
-
-            match check_errors(r) {
-                Ok(_) => (),
-                Err(Error::FormatNotSupported) => {
-                    return Err(ImageCreationError::FormatNotSupported)
-                }
-                Err(err) => return Err(err.into()),
-            }
-
-            let output = output.assume_init();
-
-            if extent.width > output.maxExtent.width
-                || extent.height > output.maxExtent.height
-                || extent.depth > output.maxExtent.depth
-                || mipmaps > output.maxMipLevels
-                || array_layers > output.maxArrayLayers
-                || (num_samples & output.sampleCounts) == 0
+            if extent.width > max_extent[0]
+                || extent.height > max_extent[1]
+                || extent.depth > max_extent[2]
+                || mipmaps > properties.max_mip_levels
+                || array_layers > properties.max_array_layers
+                || (num_samples & properties.sample_counts) == 0
             {
                 return Err(capabilities_error);
             }
@@ -512,9 +585,32 @@ impl UnsafeImage {
 
         // Everything now ok. Creating the image.
         let image = {
+            let view_formats: SmallVec<[vk::Format; 4]> =
+                view_formats.iter().map(|&f| f as vk::Format).collect();
+            let format_list = if !view_formats.is_empty()
+                && (device.loaded_extensions().khr_image_format_list
+                    || device.api_version()
+                        >= (Version {
+                            major: 1,
+                            minor: 2,
+                            patch: 0,
+                        })) {
+                Some(vk::ImageFormatListCreateInfo {
+                    sType: vk::STRUCTURE_TYPE_IMAGE_FORMAT_LIST_CREATE_INFO,
+                    pNext: ptr::null(),
+                    viewFormatCount: view_formats.len() as u32,
+                    pViewFormats: view_formats.as_ptr(),
+                })
+            } else {
+                None
+            };
+
             let infos = vk::ImageCreateInfo {
                 sType: vk::STRUCTURE_TYPE_IMAGE_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: format_list
+                    .as_ref()
+                    .map(|l| l as *const vk::ImageFormatListCreateInfo as *const _)
+                    .unwrap_or(ptr::null()),
                 flags: flags.into(),
                 imageType: ty,
                 format: format as u32,
@@ -544,7 +640,8 @@ impl UnsafeImage {
                 &infos,
                 ptr::null(),
                 output.as_mut_ptr(),
-            ))?;
+            ))
+            .map_err(|err| ImageCreationError::CreationFailed(err.into()))?;
             output.assume_init()
         };
 
@@ -598,10 +695,16 @@ impl UnsafeImage {
             usage,
             format,
             flags,
+            tiling: if linear_tiling {
+                ImageTiling::Linear
+            } else {
+                ImageTiling::Optimal
+            },
             dimensions,
             samples: num_samples,
             mipmaps,
             format_features,
+            supported_sample_counts,
             needs_destruction: true,
             preinitialized_layout,
         };
@@ -612,6 +715,9 @@ impl UnsafeImage {
     /// Creates an image from a raw handle. The image won't be destroyed.
     ///
     /// This function is for example used at the swapchain's initialization.
+    ///
+    /// Returns an error if the implementation ran out of memory while querying the sample counts
+    /// supported by the image.
     pub unsafe fn from_raw(
         device: Arc<Device>,
         handle: u64,
@@ -621,29 +727,43 @@ impl UnsafeImage {
         dimensions: ImageDimensions,
         samples: u32,
         mipmaps: u32,
-    ) -> UnsafeImage {
+    ) -> Result<UnsafeImage, OomError> {
         let format_properties = format.properties(device.physical_device());
 
         // TODO: check that usage is correct in regard to `output`?
 
-        UnsafeImage {
+        let supported_sample_counts = {
+            let (image_type, tiling) = image_type_and_tiling(dimensions, false);
+            match device.image_format_properties_raw(format, image_type, tiling, usage, flags) {
+                Ok(properties) => SampleCounts::from(properties.sample_counts),
+                Err(Error::FormatNotSupported) => SampleCounts::none(),
+                Err(err) => return Err(err.into()),
+            }
+        };
+
+        Ok(UnsafeImage {
             device: device.clone(),
             image: handle,
             usage,
             format,
             flags,
+            tiling: ImageTiling::Optimal,
             dimensions,
             samples,
             mipmaps,
             format_features: format_properties.optimal_tiling_features,
+            supported_sample_counts,
             needs_destruction: false,     // TODO: pass as parameter
             preinitialized_layout: false, // TODO: Maybe this should be passed in?
-        }
+        })
     }
 
     pub unsafe fn bind_memory(&self, memory: &DeviceMemory, offset: usize) -> Result<(), OomError> {
         let vk = self.device.pointers();
 
+        // An image in the `Preinitialized` layout is meant to be written by the host.
+        debug_assert!(!self.preinitialized_layout || memory.memory_type().is_host_visible());
+
         // We check for correctness in debug mode.
         debug_assert!({
             let mut mem_reqs = MaybeUninit::uninit();
@@ -697,6 +817,31 @@ impl UnsafeImage {
         self.samples
     }
 
+    /// Returns the sample counts that the physical device supports for an image with the same
+    /// format, usage, type and tiling as this one.
+    #[inline]
+    pub fn supported_sample_counts(&self) -> SampleCounts {
+        self.supported_sample_counts
+    }
+
+    /// Returns the memory requirements of the image, as reported by the implementation.
+    ///
+    /// Contrary to the requirements returned when creating the image, `prefer_dedicated` is
+    /// always false.
+    #[inline]
+    pub fn memory_requirements(&self) -> MemoryRequirements {
+        unsafe {
+            let vk = self.device.pointers();
+            let mut output: MaybeUninit<vk::MemoryRequirements> = MaybeUninit::uninit();
+            vk.GetImageMemoryRequirements(
+                self.device.internal_object(),
+                self.image,
+                output.as_mut_ptr(),
+            );
+            MemoryRequirements::from_vulkan_reqs(output.assume_init())
+        }
+    }
+
     /// Returns a key unique to each `UnsafeImage`. Can be used for the `conflicts_key` method.
     #[inline]
     pub fn key(&self) -> u64 {
@@ -835,6 +980,12 @@ impl UnsafeImage {
         self.flags
     }
 
+    /// Returns the tiling the image was created with.
+    #[inline]
+    pub fn tiling(&self) -> ImageTiling {
+        self.tiling
+    }
+
     /// Returns the features supported by the image's format.
     #[inline]
     pub fn format_features(&self) -> FormatFeatures {
@@ -851,6 +1002,16 @@ impl UnsafeImage {
     pub fn preinitialized_layout(&self) -> bool {
         self.preinitialized_layout
     }
+
+    /// Returns the layout the image was created in, either `Undefined` or `Preinitialized`.
+    #[inline]
+    pub fn initial_layout(&self) -> ImageLayout {
+        if self.preinitialized_layout {
+            ImageLayout::Preinitialized
+        } else {
+            ImageLayout::Undefined
+        }
+    }
 }
 
 unsafe impl VulkanObject for UnsafeImage {
@@ -902,11 +1063,89 @@ impl Hash for UnsafeImage {
     }
 }
 
+// Returns the `ImageType` and `ImageTiling` to use when querying the image format properties of
+// an image.
+#[inline]
+fn image_type_and_tiling(
+    dimensions: ImageDimensions,
+    linear_tiling: bool,
+) -> (ImageType, ImageTiling) {
+    let image_type = dimensions.image_type();
+    let tiling = if linear_tiling {
+        ImageTiling::Linear
+    } else {
+        ImageTiling::Optimal
+    };
+
+    (image_type, tiling)
+}
+
+/// Checks the parameters of an image that must be consistent with each other, independently of
+/// the device.
+///
+/// This checks that:
+///
+/// - `ty` matches the type of `dimensions`, and none of the dimensions is 0. 3D images always
+///   have a single array layer, since `ImageDimensions::Dim3d` doesn't have any.
+/// - `cube_compatible` images are 2D, square and have at least 6 array layers.
+/// - `array_2d_compatible` images are 3D.
+/// - `sparse_residency` and `sparse_aliased` are only used with `sparse_binding`.
+/// - `samples` is a power of two, and multisampled images are 2D, not cube-compatible and have a
+///   single mipmap level.
+///
+/// All the image constructors call this function before checking the parameters against the
+/// capabilities of the device.
+pub fn validate_image_params(
+    ty: ImageType,
+    dimensions: ImageDimensions,
+    flags: ImageCreateFlags,
+    samples: u32,
+    mipmaps: u32,
+) -> Result<(), ImageCreationError> {
+    if dimensions.image_type() != ty {
+        return Err(ImageCreationError::ImageTypeMismatch { ty, dimensions });
+    }
+
+    let [width, height, depth] = dimensions.width_height_depth();
+    if width == 0 || height == 0 || depth == 0 || dimensions.array_layers() == 0 {
+        return Err(ImageCreationError::UnsupportedDimensions { dimensions });
+    }
+
+    if flags.cube_compatible
+        && !(ty == ImageType::Dim2d && width == height && dimensions.array_layers() >= 6)
+    {
+        return Err(ImageCreationError::CreationFlagRequirementsNotMet);
+    }
+
+    if flags.array_2d_compatible && ty != ImageType::Dim3d {
+        return Err(ImageCreationError::CreationFlagRequirementsNotMet);
+    }
+
+    if (flags.sparse_residency || flags.sparse_aliased) && !flags.sparse_binding {
+        return Err(ImageCreationError::CreationFlagRequirementsNotMet);
+    }
+
+    if samples == 0 || !samples.is_power_of_two() {
+        return Err(ImageCreationError::UnsupportedSamplesCount { obtained: samples });
+    }
+
+    if samples > 1 && (ty != ImageType::Dim2d || flags.cube_compatible || mipmaps != 1) {
+        return Err(ImageCreationError::MultisampleRequirementsNotMet);
+    }
+
+    Ok(())
+}
+
 /// Error that can happen when creating an instance.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ImageCreationError {
     /// Allocating memory failed.
     AllocError(DeviceMemoryAllocError),
+    /// Creating the image object failed.
+    CreationFailed(OomError),
+    /// The image was created and its memory was allocated, but binding the memory to the image
+    /// failed.
+    BindFailed(OomError),
     /// The specified creation flags have requirements (e.g. specific dimension) that were not met.
     CreationFlagRequirementsNotMet,
     /// A wrong number of mipmaps was provided.
@@ -916,6 +1155,12 @@ pub enum ImageCreationError {
         obtained: u32,
         valid_range: Range<u32>,
     },
+    /// The requested number of mipmaps is greater than what the implementation supports for this
+    /// format, usage and tiling.
+    TooManyMipmaps { requested: u32, max: u32 },
+    /// The requested number of array layers is greater than the `max_image_array_layers` limit
+    /// of the device.
+    TooManyArrayLayers { requested: u32, max: u32 },
     /// The requested number of samples is not supported, or is 0.
     UnsupportedSamplesCount { obtained: u32 },
     /// The dimensions are too large, or one of the dimensions is 0.
@@ -924,6 +1169,54 @@ pub enum ImageCreationError {
     UnsupportedUsage,
     /// The `shader_storage_image_multisample` feature must be enabled to create such an image.
     ShaderStorageImageMultisampleFeatureNotEnabled,
+    /// The `Preinitialized` initial layout was requested for an image that doesn't use linear
+    /// tiling.
+    PreinitializedLayoutRequiresLinearTiling,
+    /// One of the formats in the list of view formats is not compatible with the image's format.
+    IncompatibleViewFormat { format: Format, view_format: Format },
+    /// The data provided to initialize the image is too small for its dimensions and format.
+    SourceDataTooSmall {
+        required_len: usize,
+        obtained_len: usize,
+    },
+    /// The buffer provided to initialize the image can't be copied to it, for example because it
+    /// doesn't have the `transfer_source` usage or because the format of the image doesn't have
+    /// a color aspect.
+    InvalidSourceData(CheckCopyBufferImageError),
+    /// The command buffer that initializes the image couldn't be executed, for example because
+    /// the source buffer is still being written by the GPU.
+    UploadFailed(CommandBufferExecError),
+    /// The data provided for a mipmap level doesn't have the size that the dimensions of the
+    /// level and the format require.
+    WrongMipDataLength {
+        level: u32,
+        expected: usize,
+        actual: usize,
+    },
+    /// The image is cube-compatible and its width exceeds the `max_image_dimension_cube` limit.
+    CubeDimensionsTooLarge { width: u32, max: u32 },
+    /// The type of the image doesn't match the type of its dimensions.
+    ImageTypeMismatch {
+        ty: ImageType,
+        dimensions: ImageDimensions,
+    },
+    /// A multisampled image must be 2D, not cube-compatible, use optimal tiling and have a single
+    /// mipmap level.
+    MultisampleRequirementsNotMet,
+    /// The format subsamples the chroma horizontally and vertically, but the width or the height
+    /// of the image is odd.
+    OddDimensionForSubsampledFormat {
+        format: Format,
+        dimensions: ImageDimensions,
+    },
+    /// Atomic operations were requested on the image, but its format doesn't support atomic
+    /// operations on storage images.
+    FormatNotSupportedForAtomics { format: Format },
+    /// The image is meant to be sampled, but its format can't be sampled with optimal tiling.
+    FormatNotSampleable { format: Format },
+    /// The image uses a packed HDR format as a color attachment or a storage image, but the
+    /// format doesn't support this usage on the device.
+    UnsupportedHdrPackedUsage { format: Format, usage: ImageUsage },
 }
 
 impl error::Error for ImageCreationError {
@@ -931,6 +1224,10 @@ impl error::Error for ImageCreationError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             ImageCreationError::AllocError(ref err) => Some(err),
+            ImageCreationError::CreationFailed(ref err) => Some(err),
+            ImageCreationError::BindFailed(ref err) => Some(err),
+            ImageCreationError::InvalidSourceData(ref err) => Some(err),
+            ImageCreationError::UploadFailed(ref err) => Some(err),
             _ => None,
         }
     }
@@ -944,6 +1241,8 @@ impl fmt::Display for ImageCreationError {
             "{}",
             match *self {
                 ImageCreationError::AllocError(_) => "allocating memory failed",
+                ImageCreationError::CreationFailed(_) => "creating the image object failed",
+                ImageCreationError::BindFailed(_) => "binding memory to the image failed",
                 ImageCreationError::CreationFlagRequirementsNotMet => {
                     "the requested creation flags have additional requirements that were not met"
                 }
@@ -953,6 +1252,13 @@ impl fmt::Display for ImageCreationError {
                 ImageCreationError::InvalidMipmapsCount { .. } => {
                     "a wrong number of mipmaps was provided"
                 }
+                ImageCreationError::TooManyMipmaps { .. } => {
+                    "the requested number of mipmaps is greater than what the implementation \
+                 supports"
+                }
+                ImageCreationError::TooManyArrayLayers { .. } => {
+                    "the requested number of array layers is greater than what the device supports"
+                }
                 ImageCreationError::UnsupportedSamplesCount { .. } => {
                     "the requested number of samples is not supported, or is 0"
                 }
@@ -967,6 +1273,50 @@ impl fmt::Display for ImageCreationError {
                     "the `shader_storage_image_multisample` feature must be enabled to create such \
                  an image"
                 }
+                ImageCreationError::PreinitializedLayoutRequiresLinearTiling => {
+                    "the `Preinitialized` initial layout can only be used with linear tiling"
+                }
+                ImageCreationError::IncompatibleViewFormat { .. } => {
+                    "one of the formats in the list of view formats is not compatible with the \
+                 image's format"
+                }
+                ImageCreationError::SourceDataTooSmall { .. } => {
+                    "the data provided to initialize the image is too small"
+                }
+                ImageCreationError::InvalidSourceData(_) => {
+                    "the data provided to initialize the image can't be copied to it"
+                }
+                ImageCreationError::UploadFailed(_) => {
+                    "the command buffer that initializes the image couldn't be executed"
+                }
+                ImageCreationError::WrongMipDataLength { .. } => {
+                    "the data provided for a mipmap level doesn't have the size that the level \
+                     requires"
+                }
+                ImageCreationError::CubeDimensionsTooLarge { .. } => {
+                    "the dimensions of the cube-compatible image exceed the cube dimension limit"
+                }
+                ImageCreationError::ImageTypeMismatch { .. } => {
+                    "the type of the image doesn't match the type of its dimensions"
+                }
+                ImageCreationError::MultisampleRequirementsNotMet => {
+                    "a multisampled image must be 2D, not cube-compatible, use optimal tiling and \
+                 have a single mipmap level"
+                }
+                ImageCreationError::OddDimensionForSubsampledFormat { .. } => {
+                    "the width or the height of the image is odd, but its format subsamples the \
+                 chroma"
+                }
+                ImageCreationError::FormatNotSupportedForAtomics { .. } => {
+                    "the format doesn't support atomic operations on storage images"
+                }
+                ImageCreationError::FormatNotSampleable { .. } => {
+                    "the format can't be sampled with optimal tiling"
+                }
+                ImageCreationError::UnsupportedHdrPackedUsage { .. } => {
+                    "the packed HDR format can't be used as a color attachment or a storage \
+                     image on this device"
+                }
             }
         )
     }
@@ -1023,9 +1373,11 @@ pub struct LinearLayout {
 
 #[cfg(test)]
 mod tests {
+    use std::error::Error;
     use std::iter::Empty;
     use std::u32;
 
+    use super::validate_image_params;
     use super::ImageCreateFlags;
     use super::ImageCreationError;
     use super::ImageUsage;
@@ -1033,7 +1385,31 @@ mod tests {
 
     use crate::format::Format;
     use crate::image::ImageDimensions;
+    use crate::image::ImageTiling;
+    use crate::image::ImageType;
+    use crate::memory::DeviceMemoryAllocError;
     use crate::sync::Sharing;
+    use crate::OomError;
+
+    #[test]
+    fn error_source() {
+        // Forcing a real allocation failure isn't portable, so check the conversions instead.
+        let err: ImageCreationError = DeviceMemoryAllocError::TooManyObjects.into();
+        match err {
+            ImageCreationError::AllocError(DeviceMemoryAllocError::TooManyObjects) => (),
+            _ => panic!(),
+        }
+        assert!(err.source().unwrap().is::<DeviceMemoryAllocError>());
+
+        let err = ImageCreationError::BindFailed(OomError::OutOfDeviceMemory);
+        assert_eq!(
+            err.source().unwrap().downcast_ref::<OomError>(),
+            Some(&OomError::OutOfDeviceMemory)
+        );
+
+        let err = ImageCreationError::CreationFailed(OomError::OutOfHostMemory);
+        assert!(err.source().unwrap().is::<OomError>());
+    }
 
     #[test]
     fn create_sampled() {
@@ -1239,6 +1615,93 @@ mod tests {
         };
     }
 
+    #[test]
+    fn mipmaps_above_implementation_max() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        // Linear tiling is commonly limited to a single mipmap level. If the implementation
+        // supports more than that, there is nothing to test.
+        let max_mip_levels = match device.image_format_properties(
+            Format::R8G8B8A8Unorm,
+            ImageType::Dim2d,
+            ImageTiling::Linear,
+            usage,
+            ImageCreateFlags::none(),
+        ) {
+            Ok(properties) => properties.max_mip_levels,
+            Err(_) => return,
+        };
+        if max_mip_levels >= 2 {
+            return;
+        }
+
+        let res = unsafe {
+            UnsafeImage::new(
+                device,
+                usage,
+                Format::R8G8B8A8Unorm,
+                ImageCreateFlags::none(),
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                1,
+                2,
+                Sharing::Exclusive::<Empty<_>>,
+                true,
+                false,
+            )
+        };
+
+        match res {
+            Err(ImageCreationError::TooManyMipmaps { requested, max }) => {
+                assert_eq!(requested, 2);
+                assert_eq!(max, max_mip_levels);
+            }
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn preinitialized_layout_optimal_tiling() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        let res = unsafe {
+            UnsafeImage::new(
+                device,
+                usage,
+                Format::R8G8B8A8Unorm,
+                ImageCreateFlags::none(),
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                1,
+                1,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                true,
+            )
+        };
+
+        match res {
+            Err(ImageCreationError::PreinitializedLayoutRequiresLinearTiling) => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn shader_storage_image_multisample() {
         let (device, _) = gfx_dev_and_queue!();
@@ -1380,4 +1843,303 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn odd_dimension_for_subsampled_format() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        let res = unsafe {
+            UnsafeImage::new(
+                device.clone(),
+                usage,
+                Format::G8B8R8_3PLANE420Unorm,
+                ImageCreateFlags::none(),
+                ImageDimensions::Dim2d {
+                    width: 33,
+                    height: 32,
+                    array_layers: 1,
+                },
+                1,
+                1,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                false,
+            )
+        };
+
+        match res {
+            Err(ImageCreationError::OddDimensionForSubsampledFormat { .. }) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn cubecompatible_dims_too_large() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let limits = device.physical_device().limits();
+        let max_cube = limits.max_image_dimension_cube();
+        // There is nothing to test if the cube limit isn't lower than the 2D limit.
+        if max_cube >= limits.max_image_dimension_2d() {
+            return;
+        }
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        let res = unsafe {
+            UnsafeImage::new(
+                device.clone(),
+                usage,
+                Format::R8G8B8A8Unorm,
+                ImageCreateFlags {
+                    cube_compatible: true,
+                    ..ImageCreateFlags::none()
+                },
+                ImageDimensions::Dim2d {
+                    width: max_cube + 1,
+                    height: max_cube + 1,
+                    array_layers: 6,
+                },
+                1,
+                1,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                false,
+            )
+        };
+
+        match res {
+            Err(ImageCreationError::CubeDimensionsTooLarge { width, max }) => {
+                assert_eq!(width, max_cube + 1);
+                assert_eq!(max, max_cube);
+            }
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn sparse_residency_without_binding() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        let res = unsafe {
+            UnsafeImage::new(
+                device,
+                usage,
+                Format::R8G8B8A8Unorm,
+                ImageCreateFlags {
+                    sparse_residency: true,
+                    ..ImageCreateFlags::none()
+                },
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                1,
+                1,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                false,
+            )
+        };
+
+        match res {
+            Err(ImageCreationError::CreationFlagRequirementsNotMet) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn too_many_array_layers() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let max_layers = device.physical_device().limits().max_image_array_layers();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        let res = unsafe {
+            UnsafeImage::new(
+                device.clone(),
+                usage,
+                Format::R8G8B8A8Unorm,
+                ImageCreateFlags::none(),
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: max_layers + 1,
+                },
+                1,
+                1,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                false,
+            )
+        };
+
+        match res {
+            Err(ImageCreationError::TooManyArrayLayers { requested, max }) => {
+                assert_eq!(requested, max_layers + 1);
+                assert_eq!(max, max_layers);
+            }
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn validate_image_params_matrix() {
+        let none = ImageCreateFlags::none();
+        let cube = ImageCreateFlags {
+            cube_compatible: true,
+            ..ImageCreateFlags::none()
+        };
+        let array_2d = ImageCreateFlags {
+            array_2d_compatible: true,
+            ..ImageCreateFlags::none()
+        };
+        let residency = ImageCreateFlags {
+            sparse_residency: true,
+            ..ImageCreateFlags::none()
+        };
+        let sparse = ImageCreateFlags {
+            sparse_binding: true,
+            sparse_residency: true,
+            ..ImageCreateFlags::none()
+        };
+
+        let dim1d = ImageDimensions::Dim1d {
+            width: 32,
+            array_layers: 1,
+        };
+        let dim2d = ImageDimensions::Dim2d {
+            width: 32,
+            height: 32,
+            array_layers: 1,
+        };
+        let dim2d_6 = ImageDimensions::Dim2d {
+            width: 32,
+            height: 32,
+            array_layers: 6,
+        };
+        let dim2d_rect_6 = ImageDimensions::Dim2d {
+            width: 32,
+            height: 16,
+            array_layers: 6,
+        };
+        let dim2d_empty = ImageDimensions::Dim2d {
+            width: 0,
+            height: 32,
+            array_layers: 1,
+        };
+        let dim3d = ImageDimensions::Dim3d {
+            width: 32,
+            height: 32,
+            depth: 32,
+        };
+
+        let valid = [
+            (ImageType::Dim1d, dim1d, none, 1, 6),
+            (ImageType::Dim2d, dim2d, none, 1, 6),
+            (ImageType::Dim2d, dim2d, none, 4, 1),
+            (ImageType::Dim2d, dim2d_6, cube, 1, 6),
+            (ImageType::Dim3d, dim3d, none, 1, 6),
+            (ImageType::Dim3d, dim3d, array_2d, 1, 1),
+            (ImageType::Dim2d, dim2d, sparse, 1, 1),
+        ];
+        for &(ty, dimensions, flags, samples, mipmaps) in valid.iter() {
+            assert_eq!(
+                validate_image_params(ty, dimensions, flags, samples, mipmaps),
+                Ok(()),
+                "{:?} {:?} {:?} {} {}",
+                ty,
+                dimensions,
+                flags,
+                samples,
+                mipmaps
+            );
+        }
+
+        let invalid = [
+            (
+                (ImageType::Dim3d, dim2d, none, 1, 1),
+                ImageCreationError::ImageTypeMismatch {
+                    ty: ImageType::Dim3d,
+                    dimensions: dim2d,
+                },
+            ),
+            (
+                (ImageType::Dim2d, dim2d_empty, none, 1, 1),
+                ImageCreationError::UnsupportedDimensions {
+                    dimensions: dim2d_empty,
+                },
+            ),
+            (
+                (ImageType::Dim2d, dim2d, cube, 1, 1),
+                ImageCreationError::CreationFlagRequirementsNotMet,
+            ),
+            (
+                (ImageType::Dim2d, dim2d_rect_6, cube, 1, 1),
+                ImageCreationError::CreationFlagRequirementsNotMet,
+            ),
+            (
+                (ImageType::Dim2d, dim2d, array_2d, 1, 1),
+                ImageCreationError::CreationFlagRequirementsNotMet,
+            ),
+            (
+                (ImageType::Dim2d, dim2d, residency, 1, 1),
+                ImageCreationError::CreationFlagRequirementsNotMet,
+            ),
+            (
+                (ImageType::Dim2d, dim2d, none, 0, 1),
+                ImageCreationError::UnsupportedSamplesCount { obtained: 0 },
+            ),
+            (
+                (ImageType::Dim2d, dim2d, none, 3, 1),
+                ImageCreationError::UnsupportedSamplesCount { obtained: 3 },
+            ),
+            (
+                (ImageType::Dim2d, dim2d, none, 4, 2),
+                ImageCreationError::MultisampleRequirementsNotMet,
+            ),
+            (
+                (ImageType::Dim2d, dim2d_6, cube, 4, 1),
+                ImageCreationError::MultisampleRequirementsNotMet,
+            ),
+            (
+                (ImageType::Dim1d, dim1d, none, 4, 1),
+                ImageCreationError::MultisampleRequirementsNotMet,
+            ),
+            (
+                (ImageType::Dim3d, dim3d, none, 4, 1),
+                ImageCreationError::MultisampleRequirementsNotMet,
+            ),
+        ];
+        for &((ty, dimensions, flags, samples, mipmaps), ref err) in invalid.iter() {
+            assert_eq!(
+                validate_image_params(ty, dimensions, flags, samples, mipmaps).as_ref(),
+                Err(err),
+                "{:?} {:?} {:?} {} {}",
+                ty,
+                dimensions,
+                flags,
+                samples,
+                mipmaps
+            );
+        }
+    }
 }
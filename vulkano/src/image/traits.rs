@@ -21,10 +21,19 @@ use crate::format::PossibleSintFormatDesc;
 use crate::format::PossibleStencilFormatDesc;
 use crate::format::PossibleUintFormatDesc;
 use crate::image::sys::UnsafeImage;
+use crate::image::ImageAspect;
+use crate::image::ImageCreateFlags;
 use crate::image::ImageDescriptorLayouts;
 use crate::image::ImageDimensions;
 use crate::image::ImageLayout;
+use crate::image::ImageMemoryBarrierInfo;
+use crate::image::ImageSubresourceRange;
+use crate::image::ImageTiling;
+use crate::image::ImageUsage;
+use crate::image::SampleCounts;
 use crate::sync::AccessError;
+use crate::sync::AccessFlagBits;
+use crate::sync::PipelineStages;
 
 use crate::SafeDeref;
 
@@ -75,6 +84,16 @@ pub unsafe trait ImageAccess {
         self.inner().image.samples()
     }
 
+    /// Returns the sample counts that the physical device supports for an image with the same
+    /// format, usage, type and tiling as this one.
+    ///
+    /// This can for example be used to know which multisampling levels can be offered for a
+    /// render target with the same configuration.
+    #[inline]
+    fn supported_sample_counts(&self) -> SampleCounts {
+        self.inner().image.supported_sample_counts()
+    }
+
     /// Returns the dimensions of the image.
     #[inline]
     fn dimensions(&self) -> ImageDimensions {
@@ -82,6 +101,23 @@ pub unsafe trait ImageAccess {
         self.inner().image.dimensions()
     }
 
+    /// Returns a description of the image, bundling its dimensions, format, number of samples,
+    /// number of mipmap levels, usage, tiling and creation flags.
+    #[inline]
+    fn full_description(&self) -> ImageDescription {
+        let image = self.inner().image;
+
+        ImageDescription {
+            dimensions: self.dimensions(),
+            format: self.format(),
+            samples: self.samples(),
+            mip_levels: self.mipmap_levels(),
+            usage: image.usage(),
+            tiling: image.tiling(),
+            flags: image.flags(),
+        }
+    }
+
     /// Returns true if the image can be used as a source for blits.
     #[inline]
     fn supports_blit_source(&self) -> bool {
@@ -94,6 +130,48 @@ pub unsafe trait ImageAccess {
         self.inner().image.format_features().blit_dst
     }
 
+    /// Returns true if the image can be viewed as a cubemap or a cubemap array. In other words,
+    /// if it is a 2D image created with the `cube_compatible` flag, and its number of array
+    /// layers is a multiple of 6.
+    ///
+    /// Cube-compatible images are always square, as `validate_image_params` rejects the others.
+    #[inline]
+    fn supports_cube_view(&self) -> bool {
+        match self.dimensions() {
+            ImageDimensions::Dim2d { array_layers, .. } => {
+                array_layers != 0
+                    && array_layers % 6 == 0
+                    && self.inner().image.flags().cube_compatible
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the index of the memory type of the memory that the image is bound to.
+    ///
+    /// Returns `None` if the memory of the image isn't managed by vulkano, for example for
+    /// swapchain images.
+    #[inline]
+    fn memory_type_index(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns true if the image is bound to memory of a device-local memory type.
+    ///
+    /// Returns `false` if the memory of the image isn't managed by vulkano.
+    #[inline]
+    fn is_device_local(&self) -> bool {
+        self.memory_type_index()
+            .and_then(|index| {
+                self.inner()
+                    .image
+                    .device()
+                    .physical_device()
+                    .memory_type_by_id(index)
+            })
+            .map_or(false, |ty| ty.is_device_local())
+    }
+
     /// When images are created their memory layout is initially `Undefined` or `Preinitialized`.
     /// This method allows the image memory barrier creation process to signal when an image
     /// has been transitioned out of its initial `Undefined` or `Preinitialized` state. This
@@ -117,6 +195,12 @@ pub unsafe trait ImageAccess {
         self.inner().image.preinitialized_layout()
     }
 
+    /// Returns the layout the image was created in, either `Undefined` or `Preinitialized`.
+    #[inline]
+    fn initial_layout(&self) -> ImageLayout {
+        self.inner().image.initial_layout()
+    }
+
     /// Returns the layout that the image has when it is first used in a primary command buffer.
     ///
     /// The first time you use an image in an `AutoCommandBufferBuilder`, vulkano will suppose that
@@ -191,6 +275,114 @@ pub unsafe trait ImageAccess {
     /// verify whether they actually overlap.
     fn conflict_key(&self) -> u64;
 
+    /// Returns the granularity at which accesses to the image can be tracked.
+    ///
+    /// `PerSubresource` means that accesses to disjoint subresource ranges of the image, as
+    /// returned by `subresource_range`, never overlap in memory. This allows a synchronization
+    /// system to let independent writes to different mipmap levels or array layers run without a
+    /// barrier between them. `Whole` means that any two accesses to the image must be considered
+    /// conflicting, which is always correct but may over-synchronize.
+    ///
+    /// > **Note**: The automatic synchronization of `AutoCommandBufferBuilder` currently tracks
+    /// > whole images, whatever this returns.
+    ///
+    /// The default implementation returns `Whole`.
+    #[inline]
+    fn conflict_granularity(&self) -> ConflictGranularity {
+        ConflictGranularity::Whole
+    }
+
+    /// Returns the range of subresources that an access to this image covers: all the aspects of
+    /// its format, and the mipmap levels and array layers of `inner()`.
+    ///
+    /// Use `ImageSubresourceRange::subresources` to enumerate them.
+    #[inline]
+    fn subresource_range(&self) -> ImageSubresourceRange {
+        let inner = self.inner();
+        let first_layer = inner.first_layer as u32;
+        let first_mipmap_level = inner.first_mipmap_level as u32;
+
+        ImageSubresourceRange {
+            aspects: self.format().aspects(),
+            mipmap_levels: first_mipmap_level..first_mipmap_level + inner.num_mipmap_levels as u32,
+            array_layers: first_layer..first_layer + inner.num_layers as u32,
+        }
+    }
+
+    /// Returns the range of all the subresources of the underlying image: all the aspects of its
+    /// format, all of its mipmap levels and all of its array layers.
+    ///
+    /// Contrary to `subresource_range`, this ignores the mipmap levels and array layers that
+    /// `inner()` restricts the access to.
+    #[inline]
+    fn full_subresource_range(&self) -> ImageSubresourceRange {
+        let image = self.inner().image;
+
+        ImageSubresourceRange {
+            aspects: self.format().aspects(),
+            mipmap_levels: 0..image.mipmap_levels(),
+            array_layers: 0..image.dimensions().array_layers(),
+        }
+    }
+
+    /// Returns the same range as `full_subresource_range`, but only with the aspects of `aspects`
+    /// that the format of the image has.
+    ///
+    /// For example, passing the depth aspect for a depth/stencil image returns the range to use
+    /// when the image is used as a depth-only attachment or sampled for its depth.
+    #[inline]
+    fn subresource_range_for_aspect(&self, aspects: ImageAspect) -> ImageSubresourceRange {
+        let full = self.full_subresource_range();
+
+        ImageSubresourceRange {
+            aspects: full.aspects & aspects,
+            ..full
+        }
+    }
+
+    /// Returns a pipeline barrier that covers the whole image, as returned by
+    /// `full_subresource_range`, along with that range.
+    ///
+    /// For multi-planar formats, the range only has the color aspect, since the images created
+    /// by vulkano are never disjoint and their barriers can't name individual planes.
+    ///
+    /// The barrier makes all the memory writes of `source_stages` available and visible to all the
+    /// memory accesses of `destination_stages`, whatever they are. This is always correct but may
+    /// synchronize more than needed; use `barrier_between` to compute a minimal barrier instead.
+    fn whole_image_barrier(
+        &self,
+        old_layout: ImageLayout,
+        new_layout: ImageLayout,
+        source_stages: PipelineStages,
+        destination_stages: PipelineStages,
+    ) -> (ImageSubresourceRange, ImageMemoryBarrierInfo) {
+        let barrier = ImageMemoryBarrierInfo {
+            source_stages,
+            source_access: AccessFlagBits {
+                memory_write: true,
+                ..AccessFlagBits::none()
+            },
+            destination_stages,
+            destination_access: AccessFlagBits {
+                memory_read: true,
+                memory_write: true,
+                ..AccessFlagBits::none()
+            },
+            old_layout,
+            new_layout,
+        };
+
+        let mut range = self.full_subresource_range();
+        if range.aspects.color {
+            range.aspects = ImageAspect {
+                color: true,
+                ..ImageAspect::none()
+            };
+        }
+
+        (range, barrier)
+    }
+
     /// Returns the current mip level that is accessed by the gpu
     fn current_miplevels_access(&self) -> std::ops::Range<u32>;
 
@@ -250,6 +442,38 @@ pub unsafe trait ImageAccess {
     /// - The transitioned layout must not be `Undefined`.
     ///
     unsafe fn unlock(&self, transitioned_layout: Option<ImageLayout>);
+
+    /// Returns the access state that the image tracks, for debugging purposes. This can help
+    /// understand why `try_gpu_lock` returned an error such as `AlreadyInUse`.
+    ///
+    /// The returned state reflects the calls to `try_gpu_lock` and `unlock` made when command
+    /// buffers are submitted and their futures are cleaned up, not what the GPU is actually
+    /// doing at the moment. It is never used by vulkano to make synchronization decisions.
+    ///
+    /// The default implementation returns `None`, meaning that the image doesn't track its state.
+    #[inline]
+    fn current_access_state(&self) -> Option<AccessState> {
+        None
+    }
+}
+
+/// Description of an image, as returned by `ImageAccess::full_description`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ImageDescription {
+    /// The dimensions of the image.
+    pub dimensions: ImageDimensions,
+    /// The format of the image.
+    pub format: Format,
+    /// The number of samples per texel.
+    pub samples: u32,
+    /// The number of mipmap levels.
+    pub mip_levels: u32,
+    /// The usage the image was created with.
+    pub usage: ImageUsage,
+    /// The tiling the image was created with.
+    pub tiling: ImageTiling,
+    /// The flags the image was created with.
+    pub flags: ImageCreateFlags,
 }
 
 /// Inner information about an image.
@@ -271,6 +495,28 @@ pub struct ImageInner<'a> {
     pub num_mipmap_levels: usize,
 }
 
+/// Granularity at which the accesses to an image can be tracked, as returned by
+/// `ImageAccess::conflict_granularity`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConflictGranularity {
+    /// Any two accesses to the image conflict.
+    Whole,
+    /// Accesses to disjoint subresource ranges of the image don't conflict.
+    PerSubresource,
+}
+
+/// Access state tracked by an image, as returned by `ImageAccess::current_access_state`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AccessState {
+    /// The layout that the image is in once its pending accesses are done, or `Undefined` if its
+    /// content hasn't been initialized yet.
+    pub layout: ImageLayout,
+    /// The number of locks currently held on the image by submitted command buffers.
+    pub gpu_locks: usize,
+    /// True if the last successful call to `try_gpu_lock` requested exclusive access.
+    pub exclusive: bool,
+}
+
 unsafe impl<T> ImageAccess for T
 where
     T: SafeDeref,
@@ -296,6 +542,16 @@ where
         (**self).descriptor_layouts()
     }
 
+    #[inline]
+    fn memory_type_index(&self) -> Option<u32> {
+        (**self).memory_type_index()
+    }
+
+    #[inline]
+    fn is_device_local(&self) -> bool {
+        (**self).is_device_local()
+    }
+
     #[inline]
     fn conflicts_buffer(&self, other: &dyn BufferAccess) -> bool {
         (**self).conflicts_buffer(other)
@@ -311,6 +567,21 @@ where
         (**self).conflict_key()
     }
 
+    #[inline]
+    fn conflict_granularity(&self) -> ConflictGranularity {
+        (**self).conflict_granularity()
+    }
+
+    #[inline]
+    fn subresource_range(&self) -> ImageSubresourceRange {
+        (**self).subresource_range()
+    }
+
+    #[inline]
+    fn current_access_state(&self) -> Option<AccessState> {
+        (**self).current_access_state()
+    }
+
     #[inline]
     fn try_gpu_lock(
         &self,
@@ -401,6 +672,11 @@ where
         self.image.descriptor_layouts()
     }
 
+    #[inline]
+    fn memory_type_index(&self) -> Option<u32> {
+        self.image.memory_type_index()
+    }
+
     #[inline]
     fn conflicts_buffer(&self, other: &dyn BufferAccess) -> bool {
         self.image.conflicts_buffer(other)
@@ -416,6 +692,21 @@ where
         self.image.conflict_key()
     }
 
+    #[inline]
+    fn conflict_granularity(&self) -> ConflictGranularity {
+        self.image.conflict_granularity()
+    }
+
+    #[inline]
+    fn subresource_range(&self) -> ImageSubresourceRange {
+        self.image.subresource_range()
+    }
+
+    #[inline]
+    fn current_access_state(&self) -> Option<AccessState> {
+        self.image.current_access_state()
+    }
+
     #[inline]
     fn try_gpu_lock(
         &self,
@@ -7,6 +7,8 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use crate::device::Device;
+use crate::image::sys::ImageCreationError;
 use crate::vk;
 use std::ops::BitOr;
 
@@ -153,6 +155,30 @@ impl ImageUsage {
         }
     }
 
+    /// Checks that an image with this usage can have `samples` samples per texel on `device`.
+    ///
+    /// A multisampled image with the `storage` usage requires the
+    /// `shader_storage_image_multisample` feature. The transfer usages are allowed, since
+    /// multisampled images can be copied to each other with `copy_image`, but they can't be the
+    /// source or destination of a blit or of a copy from or to a buffer.
+    ///
+    /// Whether the number of samples is supported by the format is checked separately when
+    /// creating the image.
+    pub fn validate_with_samples(
+        &self,
+        samples: u32,
+        device: &Device,
+    ) -> Result<(), ImageCreationError> {
+        if self.storage
+            && samples > 1
+            && !device.enabled_features().shader_storage_image_multisample
+        {
+            return Err(ImageCreationError::ShaderStorageImageMultisampleFeatureNotEnabled);
+        }
+
+        Ok(())
+    }
+
     #[inline]
     pub(crate) fn to_usage_bits(&self) -> vk::ImageUsageFlagBits {
         let mut result = 0;
@@ -215,3 +241,33 @@ impl BitOr for ImageUsage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::image::sys::ImageCreationError;
+    use crate::image::ImageUsage;
+
+    #[test]
+    fn validate_with_samples() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let storage = ImageUsage {
+            storage: true,
+            ..ImageUsage::none()
+        };
+        assert!(storage.validate_with_samples(1, &device).is_ok());
+        match storage.validate_with_samples(4, &device) {
+            Err(ImageCreationError::ShaderStorageImageMultisampleFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        // Multisampled images can be the source and destination of `vkCmdCopyImage`.
+        let transfer = ImageUsage {
+            color_attachment: true,
+            transfer_source: true,
+            transfer_destination: true,
+            ..ImageUsage::none()
+        };
+        assert!(transfer.validate_with_samples(4, &device).is_ok());
+    }
+}
@@ -14,13 +14,20 @@
 //! to be used in a shader descriptor or as a framebuffer attachment.
 
 use crate::check_errors;
+use crate::descriptor::descriptor::DescriptorImageDesc;
+use crate::descriptor::descriptor::DescriptorImageDescArray;
+use crate::descriptor::descriptor::DescriptorImageDescDimensions;
 use crate::device::Device;
 use crate::format::Format;
 use crate::format::FormatTy;
 use crate::image::sys::UnsafeImage;
 use crate::image::ImageAccess;
+use crate::image::ImageAspect;
 use crate::image::ImageDimensions;
+use crate::image::ImageSubresourceRange;
+use crate::image::ImageType;
 use crate::memory::DeviceMemoryAllocError;
+use crate::render_pass::FramebufferAttachmentImageInfo;
 use crate::sampler::Sampler;
 use crate::vk;
 use crate::OomError;
@@ -32,6 +39,7 @@ use std::hash::Hash;
 use std::hash::Hasher;
 use std::mem::MaybeUninit;
 use std::ops::Range;
+use std::ops::RangeInclusive;
 use std::ptr;
 use std::sync::Arc;
 
@@ -47,6 +55,9 @@ where
     ty: ImageViewType,
     component_mapping: ComponentMapping,
     array_layers: Range<u32>,
+    mipmap_levels: Range<u32>,
+    aspect: ImageAspect,
+    min_lod: f32,
 }
 
 impl<I> ImageView<I>
@@ -54,11 +65,137 @@ where
     I: ImageAccess,
 {
     /// Creates a default `ImageView`. Equivalent to `ImageView::start(image).build()`.
+    ///
+    /// The view covers the whole image:
+    ///
+    /// - Its type is inferred from the dimensions of the image: `Dim1d`, `Dim2d` or `Dim3d` for
+    ///   images with a single array layer, `Dim1dArray` or `Dim2dArray` otherwise. Cube-compatible
+    ///   images are viewed as 2D arrays; use the builder to get a `Cubemap` view.
+    /// - It covers every mipmap level and every array layer.
+    /// - Its format is the format of the image, and its component mapping is the identity.
+    /// - It covers every aspect of the format.
+    ///
+    /// Use `ImageView::start` to customize any of these.
     #[inline]
     pub fn new(image: I) -> Result<Arc<ImageView<I>>, ImageViewCreationError> {
         Self::start(image).build()
     }
 
+    /// Creates a 2D view of a single array layer of `image`.
+    ///
+    /// This is needed to render into one layer of a 2D array or cubemap image without a layered
+    /// framebuffer. Returns `ArrayLayersOutOfRange` if `layer` is not a layer of the image.
+    #[inline]
+    pub fn layer(image: I, layer: u32) -> Result<Arc<ImageView<I>>, ImageViewCreationError> {
+        let end = layer
+            .checked_add(1)
+            .ok_or(ImageViewCreationError::ArrayLayersOutOfRange)?;
+
+        Self::start(image)
+            .with_type(ImageViewType::Dim2d)
+            .with_array_layers(layer..end)
+            .build()
+    }
+
+    /// Creates a view of `image` that can be written to a descriptor described by `desc`.
+    ///
+    /// The type of the view is chosen from the dimensions and arrayness of the descriptor. It
+    /// covers every mipmap level, and as many array layers as the descriptor accepts. For a
+    /// depth/stencil image, only the depth aspect is viewed, or the stencil aspect if there is no
+    /// depth.
+    ///
+    /// Returns an error if the image doesn't have the usage required by the descriptor, if its
+    /// format doesn't support it or isn't the one expected, or if its number of samples doesn't
+    /// match.
+    pub fn for_descriptor(
+        image: I,
+        desc: &DescriptorImageDesc,
+    ) -> Result<Arc<ImageView<I>>, ImageViewCreationError> {
+        let image_inner = image.inner().image;
+        let format = image.format();
+
+        if desc.sampled {
+            if !image_inner.usage().sampled {
+                return Err(ImageViewCreationError::MissingSampledUsage);
+            }
+            if !image_inner.format_features().sampled_image {
+                return Err(ImageViewCreationError::FormatNotSupportedByDescriptor { format });
+            }
+        } else {
+            if !image_inner.usage().storage {
+                return Err(ImageViewCreationError::MissingStorageUsage);
+            }
+            if !image_inner.format_features().storage_image {
+                return Err(ImageViewCreationError::FormatNotSupportedByDescriptor { format });
+            }
+        }
+
+        if let Some(expected) = desc.format {
+            if expected != format {
+                return Err(ImageViewCreationError::DescriptorFormatMismatch {
+                    expected,
+                    obtained: format,
+                });
+            }
+        }
+
+        if desc.multisampled != (image.samples() > 1) {
+            return Err(ImageViewCreationError::DescriptorMultisampledMismatch {
+                expected: desc.multisampled,
+            });
+        }
+
+        let arrayed = match desc.array_layers {
+            DescriptorImageDescArray::NonArrayed => false,
+            DescriptorImageDescArray::Arrayed { .. } => true,
+        };
+        let ty = match (desc.dimensions, arrayed) {
+            (DescriptorImageDescDimensions::OneDimensional, false) => ImageViewType::Dim1d,
+            (DescriptorImageDescDimensions::OneDimensional, true) => ImageViewType::Dim1dArray,
+            (DescriptorImageDescDimensions::TwoDimensional, false) => ImageViewType::Dim2d,
+            (DescriptorImageDescDimensions::TwoDimensional, true) => ImageViewType::Dim2dArray,
+            (DescriptorImageDescDimensions::ThreeDimensional, _) => ImageViewType::Dim3d,
+            (DescriptorImageDescDimensions::Cube, false) => ImageViewType::Cubemap,
+            (DescriptorImageDescDimensions::Cube, true) => ImageViewType::CubemapArray,
+        };
+
+        let layers_per_element = if desc.dimensions == DescriptorImageDescDimensions::Cube {
+            6
+        } else {
+            1
+        };
+        let image_layers = image.dimensions().array_layers();
+        let num_layers = match desc.array_layers {
+            DescriptorImageDescArray::NonArrayed => layers_per_element,
+            DescriptorImageDescArray::Arrayed {
+                max_layers: Some(max_layers),
+            } => image_layers.min(max_layers * layers_per_element),
+            DescriptorImageDescArray::Arrayed { max_layers: None } => image_layers,
+        };
+
+        let aspects = format.aspects();
+        let aspect = if aspects.depth {
+            ImageAspect {
+                depth: true,
+                ..ImageAspect::none()
+            }
+        } else if aspects.stencil {
+            ImageAspect {
+                stencil: true,
+                ..ImageAspect::none()
+            }
+        } else {
+            aspects
+        };
+
+        let builder = Self::start(image).with_type(ty).with_aspect(aspect);
+        if ty == ImageViewType::Dim3d {
+            builder.build()
+        } else {
+            builder.with_array_layers(0..num_layers).build()
+        }
+    }
+
     /// Begins building an `ImageView`.
     pub fn start(image: I) -> ImageViewBuilder<I> {
         let ty = match image.dimensions() {
@@ -73,14 +210,15 @@ where
             ImageDimensions::Dim3d { .. } => ImageViewType::Dim3d,
         };
         let mipmap_levels = 0..image.mipmap_levels();
-        let array_layers = 0..image.dimensions().array_layers();
 
         ImageViewBuilder {
             image,
             ty,
             component_mapping: ComponentMapping::default(),
             mipmap_levels,
-            array_layers,
+            array_layers: None,
+            aspect: None,
+            min_lod: None,
         }
     }
 
@@ -88,6 +226,57 @@ where
     pub fn image(&self) -> &I {
         &self.image
     }
+
+    /// Returns the minimum LOD that can be accessed through this view.
+    ///
+    /// Like mipmap levels, this is relative to the image and not to the view. It is the value
+    /// passed to `ImageViewBuilder::with_min_lod`, or the first mipmap level of the view if no
+    /// minimum LOD was set.
+    #[inline]
+    pub fn min_lod(&self) -> f32 {
+        self.min_lod
+    }
+
+    /// Returns the subresources of the image that this view covers.
+    ///
+    /// A 3D image has a single array layer. The array layers of a 2D or 2D array view of a 3D
+    /// image are depth slices, which are not subresources, so the returned range covers the
+    /// single array layer of the image whatever the slices of the view.
+    #[inline]
+    pub fn subresource_range(&self) -> ImageSubresourceRange {
+        let array_layers = match self.image.dimensions() {
+            ImageDimensions::Dim3d { .. } => 0..1,
+            _ => self.array_layers.clone(),
+        };
+
+        ImageSubresourceRange {
+            aspects: self.aspect,
+            mipmap_levels: self.mipmap_levels.clone(),
+            array_layers,
+        }
+    }
+}
+
+impl<I> PartialEq for ImageView<I>
+where
+    I: ImageAccess,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<I> Eq for ImageView<I> where I: ImageAccess {}
+
+impl<I> Hash for ImageView<I>
+where
+    I: ImageAccess,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
 }
 
 #[derive(Debug)]
@@ -96,7 +285,9 @@ pub struct ImageViewBuilder<I> {
     ty: ImageViewType,
     component_mapping: ComponentMapping,
     mipmap_levels: Range<u32>,
-    array_layers: Range<u32>,
+    array_layers: Option<Range<u32>>,
+    aspect: Option<ImageAspect>,
+    min_lod: Option<f32>,
 }
 
 impl<I> ImageViewBuilder<I>
@@ -107,7 +298,7 @@ where
     ///
     /// By default, this is determined from the image, based on its dimensions and number of layers.
     /// The value of `ty` must be compatible with the dimensions of the image and the selected
-    /// array layers.
+    /// array layers. `CubemapArray` views require the `image_cube_array` feature.
     #[inline]
     pub fn with_type(mut self, ty: ImageViewType) -> Self {
         self.ty = ty;
@@ -135,9 +326,37 @@ where
     /// Sets the range of array layers that the view should cover.
     ///
     /// By default, this is the full range of array layers present in the image.
+    ///
+    /// For a 2D or 2D array view of a 3D image, the array layers of the view are the depth
+    /// slices of the selected mipmap level. By default, a 2D array view covers all the slices
+    /// and a 2D view covers the first one.
     #[inline]
     pub fn with_array_layers(mut self, array_layers: Range<u32>) -> Self {
-        self.array_layers = array_layers;
+        self.array_layers = Some(array_layers);
+        self
+    }
+
+    /// Sets the aspect of the image that the view should cover.
+    ///
+    /// By default, this is every aspect of the format of the image. To sample the depth or the
+    /// stencil of a depth/stencil image, a view of only this aspect is needed. A view of the
+    /// stencil aspect yields unnormalized integer samples, see
+    /// `ImageViewAbstract::samples_as_integer`.
+    #[inline]
+    pub fn with_aspect(mut self, aspect: ImageAspect) -> Self {
+        self.aspect = Some(aspect);
+        self
+    }
+
+    /// Sets the minimum LOD that can be accessed through the view.
+    ///
+    /// By default, there is no minimum other than the first mipmap level of the view. The value
+    /// is relative to the image, and must be within the range of mipmap levels of the view.
+    /// The `ext_image_view_min_lod` extension and the `min_lod` feature must be enabled on the
+    /// device.
+    #[inline]
+    pub fn with_min_lod(mut self, min_lod: f32) -> Self {
+        self.min_lod = Some(min_lod);
         self
     }
 
@@ -155,9 +374,53 @@ where
             return Err(ImageViewCreationError::MipMapLevelsOutOfRange);
         }
 
-        if self.array_layers.end <= self.array_layers.start
-            || self.array_layers.end > dimensions.array_layers()
-        {
+        if let Some(min_lod) = self.min_lod {
+            let device = image_inner.device();
+            if !device.loaded_extensions().ext_image_view_min_lod {
+                return Err(ImageViewCreationError::MinLodExtensionNotEnabled);
+            }
+
+            if !device.enabled_features().min_lod {
+                return Err(ImageViewCreationError::MinLodFeatureNotEnabled);
+            }
+
+            if !(min_lod >= self.mipmap_levels.start as f32
+                && min_lod <= (self.mipmap_levels.end - 1) as f32)
+            {
+                return Err(ImageViewCreationError::MinLodOutOfRange);
+            }
+        }
+
+        let format_aspect = default_aspect(format);
+        let aspect = match self.aspect {
+            Some(aspect) => {
+                if aspect == ImageAspect::none() || !aspect.is_subset_of(format_aspect) {
+                    return Err(ImageViewCreationError::InvalidAspect);
+                }
+                aspect
+            }
+            None => format_aspect,
+        };
+
+        // The layers of a 2D or 2D array view of a 3D image are the depth slices of the image.
+        let slices_as_layers = matches!(dimensions, ImageDimensions::Dim3d { .. })
+            && matches!(self.ty, ImageViewType::Dim2d | ImageViewType::Dim2dArray);
+        let max_array_layers = if slices_as_layers {
+            dimensions
+                .mipmap_dimensions(self.mipmap_levels.start)
+                .unwrap()
+                .depth()
+        } else {
+            dimensions.array_layers()
+        };
+
+        let array_layers = match self.array_layers {
+            Some(array_layers) => array_layers,
+            None if slices_as_layers && self.ty == ImageViewType::Dim2d => 0..1,
+            None => 0..max_array_layers,
+        };
+
+        if array_layers.end <= array_layers.start || array_layers.end > max_array_layers {
             return Err(ImageViewCreationError::ArrayLayersOutOfRange);
         }
 
@@ -171,11 +434,25 @@ where
             return Err(ImageViewCreationError::InvalidImageUsage);
         }
 
+        // Multisampled images can only be viewed as 2D images, with a single mipmap level
+        if image_inner.samples() > 1
+            && (!matches!(self.ty, ImageViewType::Dim2d | ImageViewType::Dim2dArray)
+                || self.mipmap_levels.end - self.mipmap_levels.start != 1)
+        {
+            return Err(ImageViewCreationError::MultisampleViewConstraint);
+        }
+
+        if self.ty == ImageViewType::CubemapArray
+            && !image_inner.device().enabled_features().image_cube_array
+        {
+            return Err(ImageViewCreationError::ImageCubeArrayFeatureNotEnabled);
+        }
+
         // Check for compatibility with the image
         match (
             self.ty,
-            self.image.dimensions(),
-            self.array_layers.end - self.array_layers.start,
+            dimensions,
+            array_layers.end - array_layers.start,
             self.mipmap_levels.end - self.mipmap_levels.start,
         ) {
             (ImageViewType::Dim1d, ImageDimensions::Dim1d { .. }, 1, _) => (),
@@ -203,18 +480,26 @@ where
             {
                 ()
             }
-            _ => return Err(ImageViewCreationError::IncompatibleType),
+            _ => {
+                return Err(ImageViewCreationError::IncompatibleType {
+                    image_type: dimensions.image_type(),
+                    view_type: self.ty,
+                })
+            }
         }
 
         let inner = unsafe {
-            UnsafeImageView::new(
+            UnsafeImageView::new_impl(
                 image_inner,
                 self.ty,
                 self.component_mapping,
-                self.mipmap_levels,
-                self.array_layers.clone(),
+                self.mipmap_levels.clone(),
+                array_layers.clone(),
+                aspect,
+                self.min_lod,
             )?
         };
+        let min_lod = self.min_lod.unwrap_or(self.mipmap_levels.start as f32);
 
         Ok(Arc::new(ImageView {
             image: self.image,
@@ -223,7 +508,10 @@ where
 
             ty: self.ty,
             component_mapping: self.component_mapping,
-            array_layers: self.array_layers,
+            array_layers,
+            mipmap_levels: self.mipmap_levels,
+            aspect,
+            min_lod,
         }))
     }
 }
@@ -238,11 +526,51 @@ pub enum ImageViewCreationError {
     /// The specified range of mipmap levels was out of range for the image.
     MipMapLevelsOutOfRange,
     /// The requested [`ImageViewType`] was not compatible with the image, or with the specified ranges of array layers and mipmap levels.
-    IncompatibleType,
+    IncompatibleType {
+        /// The type of the image.
+        image_type: ImageType,
+        /// The requested type of the view.
+        view_type: ImageViewType,
+    },
     /// The image was not created with
     /// [one of the required usages](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/html/vkspec.html#valid-imageview-imageusage)
     /// for image views.
     InvalidImageUsage,
+    /// The image is multisampled, and the view is not a 2D or 2D array view with a single
+    /// mipmap level.
+    MultisampleViewConstraint,
+    /// The requested aspect is empty or is not present in the format of the image.
+    InvalidAspect,
+    /// A minimum LOD was requested, but the `ext_image_view_min_lod` extension was not enabled.
+    MinLodExtensionNotEnabled,
+    /// A minimum LOD was requested, but the `min_lod` feature was not enabled.
+    MinLodFeatureNotEnabled,
+    /// The requested minimum LOD is outside the range of mipmap levels of the view.
+    MinLodOutOfRange,
+    /// A `CubemapArray` view was requested, but the `image_cube_array` feature was not enabled.
+    ImageCubeArrayFeatureNotEnabled,
+    /// The descriptor is a sampled image, but the image wasn't created with the `sampled` usage.
+    MissingSampledUsage,
+    /// The descriptor is a storage image, but the image wasn't created with the `storage` usage.
+    MissingStorageUsage,
+    /// The format of the image doesn't support being used in the descriptor, as a sampled or a
+    /// storage image.
+    FormatNotSupportedByDescriptor {
+        /// The format of the image.
+        format: Format,
+    },
+    /// The descriptor expects another format than the format of the image.
+    DescriptorFormatMismatch {
+        /// The format expected by the descriptor.
+        expected: Format,
+        /// The format of the image.
+        obtained: Format,
+    },
+    /// The descriptor expects a multisampled image and the image isn't, or the other way around.
+    DescriptorMultisampledMismatch {
+        /// True if the descriptor expects a multisampled image.
+        expected: bool,
+    },
 }
 
 impl error::Error for ImageViewCreationError {
@@ -265,10 +593,37 @@ impl fmt::Display for ImageViewCreationError {
                 ImageViewCreationError::AllocError(err) => "allocating memory failed",
                 ImageViewCreationError::ArrayLayersOutOfRange => "array layers are out of range",
                 ImageViewCreationError::MipMapLevelsOutOfRange => "mipmap levels are out of range",
-                ImageViewCreationError::IncompatibleType =>
+                ImageViewCreationError::IncompatibleType { .. } =>
                     "image view type is not compatible with image, array layers or mipmap levels",
                 ImageViewCreationError::InvalidImageUsage =>
                     "the usage of the image is not compatible with image views",
+                ImageViewCreationError::MultisampleViewConstraint =>
+                    "multisampled images can only have 2D or 2D array views with a single mipmap \
+                     level",
+                ImageViewCreationError::InvalidAspect =>
+                    "the requested aspect is empty or is not present in the format of the image",
+                ImageViewCreationError::MinLodExtensionNotEnabled =>
+                    "a minimum LOD was requested, but the `ext_image_view_min_lod` extension was \
+                     not enabled",
+                ImageViewCreationError::MinLodFeatureNotEnabled =>
+                    "a minimum LOD was requested, but the `min_lod` feature was not enabled",
+                ImageViewCreationError::MinLodOutOfRange =>
+                    "the minimum LOD is outside the range of mipmap levels of the view",
+                ImageViewCreationError::ImageCubeArrayFeatureNotEnabled =>
+                    "a cubemap array view was requested, but the `image_cube_array` feature was \
+                     not enabled",
+                ImageViewCreationError::MissingSampledUsage =>
+                    "the descriptor is a sampled image, but the image doesn't have the `sampled` \
+                     usage",
+                ImageViewCreationError::MissingStorageUsage =>
+                    "the descriptor is a storage image, but the image doesn't have the `storage` \
+                     usage",
+                ImageViewCreationError::FormatNotSupportedByDescriptor { .. } =>
+                    "the format of the image doesn't support being used in the descriptor",
+                ImageViewCreationError::DescriptorFormatMismatch { .. } =>
+                    "the descriptor expects another format than the format of the image",
+                ImageViewCreationError::DescriptorMultisampledMismatch { .. } =>
+                    "the number of samples of the image doesn't match the descriptor",
             }
         )
     }
@@ -281,6 +636,15 @@ impl From<OomError> for ImageViewCreationError {
     }
 }
 
+// Returns every aspect of `format`.
+fn default_aspect(format: Format) -> ImageAspect {
+    match format.ty() {
+        // Not yet supported --> would require changes to ImmutableImage API :-)
+        FormatTy::Ycbcr => unimplemented!(),
+        _ => format.aspects(),
+    }
+}
+
 /// A low-level wrapper around a `vkImageView`.
 pub struct UnsafeImageView {
     view: vk::ImageView,
@@ -296,6 +660,7 @@ impl UnsafeImageView {
     /// - `ty` must be compatible with the dimensions and flags of the image.
     /// - `mipmap_levels` must not be empty, must be within the range of levels of the image, and be compatible with the requested `ty`.
     /// - `array_layers` must not be empty, must be within the range of layers of the image, and be compatible with the requested `ty`.
+    ///   For a 2D or 2D array view of a 3D image, the range of layers is the range of depth slices of the selected mipmap level.
     ///
     /// # Panics
     /// Panics if the image is a YcbCr image, since the Vulkano API is not yet flexible enough to
@@ -306,29 +671,105 @@ impl UnsafeImageView {
         component_mapping: ComponentMapping,
         mipmap_levels: Range<u32>,
         array_layers: Range<u32>,
+    ) -> Result<UnsafeImageView, OomError> {
+        UnsafeImageView::with_aspect(
+            image,
+            ty,
+            component_mapping,
+            mipmap_levels,
+            array_layers,
+            default_aspect(image.format()),
+        )
+    }
+
+    /// Same as `new`, but only covers the given aspect of the image.
+    ///
+    /// # Safety
+    /// Same as `new`. In addition, `aspect` must not be empty and must only contain aspects
+    /// present in the format of the image.
+    pub unsafe fn with_aspect(
+        image: &UnsafeImage,
+        ty: ImageViewType,
+        component_mapping: ComponentMapping,
+        mipmap_levels: Range<u32>,
+        array_layers: Range<u32>,
+        aspect: ImageAspect,
+    ) -> Result<UnsafeImageView, OomError> {
+        UnsafeImageView::new_impl(
+            image,
+            ty,
+            component_mapping,
+            mipmap_levels,
+            array_layers,
+            aspect,
+            None,
+        )
+    }
+
+    /// Same as `with_aspect`, but also clamps the LODs that can be accessed through the view to
+    /// `min_lod`.
+    ///
+    /// # Safety
+    /// Same as `with_aspect`. In addition, the `ext_image_view_min_lod` extension and the
+    /// `min_lod` feature must be enabled, and `min_lod` must be within `mipmap_levels`.
+    #[inline]
+    pub unsafe fn with_min_lod(
+        image: &UnsafeImage,
+        ty: ImageViewType,
+        component_mapping: ComponentMapping,
+        mipmap_levels: Range<u32>,
+        array_layers: Range<u32>,
+        aspect: ImageAspect,
+        min_lod: f32,
+    ) -> Result<UnsafeImageView, OomError> {
+        UnsafeImageView::new_impl(
+            image,
+            ty,
+            component_mapping,
+            mipmap_levels,
+            array_layers,
+            aspect,
+            Some(min_lod),
+        )
+    }
+
+    unsafe fn new_impl(
+        image: &UnsafeImage,
+        ty: ImageViewType,
+        component_mapping: ComponentMapping,
+        mipmap_levels: Range<u32>,
+        array_layers: Range<u32>,
+        aspect: ImageAspect,
+        min_lod: Option<f32>,
     ) -> Result<UnsafeImageView, OomError> {
         let vk = image.device().pointers();
 
         debug_assert!(mipmap_levels.end > mipmap_levels.start);
         debug_assert!(mipmap_levels.end <= image.mipmap_levels());
         debug_assert!(array_layers.end > array_layers.start);
-        debug_assert!(array_layers.end <= image.dimensions().array_layers());
-
-        let aspect_mask = match image.format().ty() {
-            FormatTy::Float | FormatTy::Uint | FormatTy::Sint | FormatTy::Compressed => {
-                vk::IMAGE_ASPECT_COLOR_BIT
-            }
-            FormatTy::Depth => vk::IMAGE_ASPECT_DEPTH_BIT,
-            FormatTy::Stencil => vk::IMAGE_ASPECT_STENCIL_BIT,
-            FormatTy::DepthStencil => vk::IMAGE_ASPECT_DEPTH_BIT | vk::IMAGE_ASPECT_STENCIL_BIT,
-            // Not yet supported --> would require changes to ImmutableImage API :-)
-            FormatTy::Ycbcr => unimplemented!(),
-        };
+        debug_assert!(
+            array_layers.end
+                <= match image.dimensions() {
+                    ImageDimensions::Dim3d { depth, .. } => depth,
+                    dimensions => dimensions.array_layers(),
+                }
+        );
+
+        let aspect_mask = aspect.to_aspect_bits();
+
+        let min_lod_info = min_lod.map(|min_lod| vk::ImageViewMinLodCreateInfoEXT {
+            sType: vk::STRUCTURE_TYPE_IMAGE_VIEW_MIN_LOD_CREATE_INFO_EXT,
+            pNext: ptr::null(),
+            minLod: min_lod,
+        });
 
         let view = {
             let infos = vk::ImageViewCreateInfo {
                 sType: vk::STRUCTURE_TYPE_IMAGE_VIEW_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: min_lod_info
+                    .as_ref()
+                    .map(|info| info as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
                 flags: 0, // reserved
                 image: image.internal_object(),
                 viewType: ty.into(),
@@ -515,6 +956,18 @@ pub unsafe trait ImageViewAbstract {
     /// Returns the range of array layers of the wrapped image that this view exposes.
     fn array_layers(&self) -> Range<u32>;
 
+    /// Returns the range of mipmap levels of the wrapped image that this view exposes.
+    fn mipmap_levels(&self) -> Range<u32>;
+
+    /// Returns the number of mipmap levels of the wrapped image that this view exposes.
+    ///
+    /// This can be lower than the number of levels of the image if the view starts at a base
+    /// level other than 0 or doesn't include the last levels.
+    #[inline]
+    fn mip_levels(&self) -> u32 {
+        self.mipmap_levels().len() as u32
+    }
+
     /// Returns the format of this view. This can be different from the parent's format.
     fn format(&self) -> Format;
 
@@ -524,13 +977,71 @@ pub unsafe trait ImageViewAbstract {
     /// Returns the [`ImageViewType`] of this image view.
     fn ty(&self) -> ImageViewType;
 
+    /// Returns the range of LODs that can be sampled from this view.
+    ///
+    /// LODs are relative to the view, not to the image: LOD 0 is the first mipmap level of the
+    /// view. Samplers bound with this view should not exceed the end of this range.
+    #[inline]
+    fn lod_range(&self) -> RangeInclusive<f32> {
+        0.0..=(self.mip_levels() - 1) as f32
+    }
+
+    /// Returns true if sampling this view yields integer values rather than floating-point
+    /// values.
+    ///
+    /// This is the case for views of `Uint` and `Sint` formats, and for views of the stencil
+    /// aspect of a stencil or depth/stencil format. Such views can't be sampled with linear
+    /// filtering.
+    #[inline]
+    fn samples_as_integer(&self) -> bool {
+        matches!(
+            self.format().ty(),
+            FormatTy::Uint | FormatTy::Sint | FormatTy::Stencil
+        )
+    }
+
+    /// Returns the raw Vulkan handle of this image view.
+    ///
+    /// The handle stays the same for the whole lifetime of the view, which makes it usable to
+    /// cheaply detect whether the view bound somewhere has changed.
+    #[inline]
+    fn view_handle(&self) -> u64 {
+        self.inner().internal_object()
+    }
+
+    /// Returns true if this view can be used as an attachment of an imageless framebuffer that
+    /// was created with the given attachment description.
+    ///
+    /// The flags and the usage of the view's image must be identical to those of `info`, the
+    /// base mipmap level of the view must have the same width and height, the view must have the
+    /// same number of layers, and its format must be one of `info.view_formats`.
+    fn matches_framebuffer_attachment_info(&self, info: &FramebufferAttachmentImageInfo) -> bool {
+        let image = &self.image().inner().image;
+        let dimensions = match image
+            .dimensions()
+            .mipmap_dimensions(self.mipmap_levels().start)
+        {
+            Some(d) => d,
+            None => return false,
+        };
+        let array_layers = self.array_layers();
+
+        image.flags() == info.flags
+            && image.usage() == info.usage
+            && dimensions.width() == info.width
+            && dimensions.height() == info.height
+            && array_layers.end - array_layers.start == info.layer_count
+            && info.view_formats.contains(&self.format())
+    }
+
     /// Returns true if the given sampler can be used with this image view.
     ///
     /// This method should check whether the sampler's configuration can be used with the format
     /// of the view.
     // TODO: return a Result and propagate it when binding to a descriptor set
-    fn can_be_sampled(&self, _sampler: &Sampler) -> bool {
-        true /* FIXME */
+    fn can_be_sampled(&self, sampler: &Sampler) -> bool {
+        // Integer samples can't be filtered linearly.
+        !(self.samples_as_integer() && sampler.uses_linear_filtering())
     }
 }
 
@@ -553,6 +1064,26 @@ where
         self.array_layers.clone()
     }
 
+    #[inline]
+    fn mipmap_levels(&self) -> Range<u32> {
+        self.mipmap_levels.clone()
+    }
+
+    #[inline]
+    fn samples_as_integer(&self) -> bool {
+        match self.format.ty() {
+            FormatTy::Uint | FormatTy::Sint => true,
+            FormatTy::Stencil | FormatTy::DepthStencil => self.aspect.stencil && !self.aspect.depth,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    fn lod_range(&self) -> RangeInclusive<f32> {
+        let min_lod = self.min_lod - self.mipmap_levels.start as f32;
+        min_lod..=(self.mip_levels() - 1) as f32
+    }
+
     #[inline]
     fn format(&self) -> Format {
         // TODO: remove this default impl
@@ -590,6 +1121,21 @@ where
         (**self).array_layers()
     }
 
+    #[inline]
+    fn mipmap_levels(&self) -> Range<u32> {
+        (**self).mipmap_levels()
+    }
+
+    #[inline]
+    fn samples_as_integer(&self) -> bool {
+        (**self).samples_as_integer()
+    }
+
+    #[inline]
+    fn lod_range(&self) -> RangeInclusive<f32> {
+        (**self).lod_range()
+    }
+
     #[inline]
     fn format(&self) -> Format {
         (**self).format()
@@ -605,6 +1151,11 @@ where
         (**self).ty()
     }
 
+    #[inline]
+    fn view_handle(&self) -> u64 {
+        (**self).view_handle()
+    }
+
     #[inline]
     fn can_be_sampled(&self, sampler: &Sampler) -> bool {
         (**self).can_be_sampled(sampler)
@@ -626,3 +1177,806 @@ impl Hash for dyn ImageViewAbstract + Send + Sync {
         self.inner().hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ImageView;
+    use super::ImageViewAbstract;
+    use super::ImageViewCreationError;
+    use super::ImageViewType;
+    use crate::descriptor::descriptor::DescriptorImageDesc;
+    use crate::descriptor::descriptor::DescriptorImageDescArray;
+    use crate::descriptor::descriptor::DescriptorImageDescDimensions;
+    use crate::device::Device;
+    use crate::device::DeviceExtensions;
+    use crate::features::Features;
+    use crate::format::Format;
+    use crate::image::AttachmentImage;
+    use crate::image::ImageAspect;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageLayout;
+    use crate::image::ImageType;
+    use crate::image::ImageUsage;
+    use crate::image::ImmutableImage;
+    use crate::image::MipmapsCount;
+    use crate::image::StorageImage;
+    use crate::instance;
+    use crate::render_pass::FramebufferAttachmentImageInfo;
+    use crate::sampler::Filter;
+    use crate::sampler::Sampler;
+    use crate::sampler::UnnormalizedSamplerAddressMode;
+
+    #[test]
+    fn equality() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::new(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let view1 = ImageView::new(image.clone()).unwrap();
+        let view2 = ImageView::new(image).unwrap();
+        let view1_clone = view1.clone();
+
+        assert!(view1 == view1_clone);
+        assert_eq!(view1.view_handle(), view1_clone.view_handle());
+        assert!(view1 != view2);
+    }
+
+    #[test]
+    fn new_covers_whole_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        let cases = [
+            (
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                ImageViewType::Dim2d,
+            ),
+            (
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 4,
+                },
+                ImageViewType::Dim2dArray,
+            ),
+            (
+                ImageDimensions::Dim3d {
+                    width: 16,
+                    height: 16,
+                    depth: 8,
+                },
+                ImageViewType::Dim3d,
+            ),
+        ];
+
+        for &(dimensions, ty) in &cases {
+            let (image, _) = ImmutableImage::uninitialized(
+                device.clone(),
+                dimensions,
+                Format::R8G8B8A8Unorm,
+                MipmapsCount::Log2,
+                usage,
+                ImageCreateFlags::none(),
+                ImageLayout::ShaderReadOnlyOptimal,
+                Some(queue.family()),
+            )
+            .unwrap();
+
+            let view = ImageView::new(image.clone()).unwrap();
+            assert_eq!(view.ty(), ty);
+            assert_eq!(view.array_layers(), 0..dimensions.array_layers());
+            assert_eq!(view.mip_levels(), image.mipmap_levels());
+            assert_eq!(view.format(), Format::R8G8B8A8Unorm);
+            assert!(view.component_mapping().is_identity());
+        }
+    }
+
+    #[test]
+    fn dim3d_view_of_dim2d_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::new(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        match ImageView::start(image)
+            .with_type(ImageViewType::Dim3d)
+            .build()
+        {
+            Err(ImageViewCreationError::IncompatibleType {
+                image_type: ImageType::Dim2d,
+                view_type: ImageViewType::Dim3d,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn dim2d_array_view_of_dim3d_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim3d {
+                width: 32,
+                height: 32,
+                depth: 4,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags {
+                array_2d_compatible: true,
+                ..ImageCreateFlags::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        ImageView::start(image)
+            .with_type(ImageViewType::Dim2dArray)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn dim2d_array_view_of_dim3d_image_slices() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim3d {
+                width: 32,
+                height: 32,
+                depth: 8,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags {
+                array_2d_compatible: true,
+                ..ImageCreateFlags::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+        assert_eq!(image.dimensions().array_layers(), 1);
+
+        // The layers of the view are depth slices, so they are checked against the depth.
+        let view = ImageView::start(image.clone())
+            .with_type(ImageViewType::Dim2dArray)
+            .with_array_layers(2..5)
+            .build()
+            .unwrap();
+        assert_eq!(view.array_layers(), 2..5);
+
+        let view = ImageView::start(image)
+            .with_type(ImageViewType::Dim2d)
+            .with_array_layers(7..8)
+            .build()
+            .unwrap();
+        assert_eq!(view.array_layers(), 7..8);
+    }
+
+    #[test]
+    fn subresource_range_of_dim3d_image_slices() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim3d {
+                width: 32,
+                height: 32,
+                depth: 8,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags {
+                array_2d_compatible: true,
+                ..ImageCreateFlags::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let first = ImageView::start(image.clone())
+            .with_type(ImageViewType::Dim2d)
+            .with_array_layers(2..3)
+            .build()
+            .unwrap();
+        let second = ImageView::start(image)
+            .with_type(ImageViewType::Dim2dArray)
+            .with_array_layers(5..8)
+            .build()
+            .unwrap();
+
+        // The depth slices aren't array layers of the image.
+        assert_eq!(first.subresource_range().array_layers, 0..1);
+        assert_eq!(second.subresource_range().array_layers, 0..1);
+        assert!(first
+            .subresource_range()
+            .overlaps(&second.subresource_range()));
+    }
+
+    #[test]
+    fn for_sampled_descriptor() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 4,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let desc = DescriptorImageDesc {
+            sampled: true,
+            dimensions: DescriptorImageDescDimensions::TwoDimensional,
+            format: None,
+            multisampled: false,
+            array_layers: DescriptorImageDescArray::NonArrayed,
+        };
+        let view = ImageView::for_descriptor(image.clone(), &desc).unwrap();
+        assert_eq!(view.ty(), ImageViewType::Dim2d);
+        assert_eq!(view.array_layers(), 0..1);
+
+        let storage = DescriptorImageDesc {
+            sampled: false,
+            ..desc
+        };
+        match ImageView::for_descriptor(image.clone(), &storage) {
+            Err(ImageViewCreationError::MissingStorageUsage) => (),
+            _ => panic!(),
+        }
+
+        let wrong_format = DescriptorImageDesc {
+            format: Some(Format::R8G8B8A8Srgb),
+            ..desc
+        };
+        match ImageView::for_descriptor(image, &wrong_format) {
+            Err(ImageViewCreationError::DescriptorFormatMismatch { expected, obtained }) => {
+                assert_eq!(expected, Format::R8G8B8A8Srgb);
+                assert_eq!(obtained, Format::R8G8B8A8Unorm);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn for_storage_descriptor() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 4,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                storage: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let desc = DescriptorImageDesc {
+            sampled: false,
+            dimensions: DescriptorImageDescDimensions::TwoDimensional,
+            format: Some(Format::R8G8B8A8Unorm),
+            multisampled: false,
+            array_layers: DescriptorImageDescArray::Arrayed { max_layers: None },
+        };
+        let view = ImageView::for_descriptor(image.clone(), &desc).unwrap();
+        assert_eq!(view.ty(), ImageViewType::Dim2dArray);
+        assert_eq!(view.array_layers(), 0..4);
+
+        let limited = DescriptorImageDesc {
+            array_layers: DescriptorImageDescArray::Arrayed {
+                max_layers: Some(2),
+            },
+            ..desc
+        };
+        let view = ImageView::for_descriptor(image.clone(), &limited).unwrap();
+        assert_eq!(view.array_layers(), 0..2);
+
+        let sampled = DescriptorImageDesc {
+            sampled: true,
+            ..desc
+        };
+        match ImageView::for_descriptor(image, &sampled) {
+            Err(ImageViewCreationError::MissingSampledUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn cubemap_array_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+        assert!(!device.enabled_features().image_cube_array);
+
+        let image = StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 12,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags {
+                cube_compatible: true,
+                ..ImageCreateFlags::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        match ImageView::start(image.clone())
+            .with_type(ImageViewType::CubemapArray)
+            .build()
+        {
+            Err(ImageViewCreationError::ImageCubeArrayFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        ImageView::start(image)
+            .with_type(ImageViewType::Cubemap)
+            .with_array_layers(6..12)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn multisampled_image_views() {
+        let (device, _) = gfx_dev_and_queue!();
+        let image =
+            AttachmentImage::sampled_multisampled(device, [32, 32], 4, Format::R8G8B8A8Unorm)
+                .unwrap();
+
+        match ImageView::start(image.clone())
+            .with_type(ImageViewType::Cubemap)
+            .build()
+        {
+            Err(ImageViewCreationError::MultisampleViewConstraint) => (),
+            _ => panic!(),
+        }
+
+        ImageView::start(image)
+            .with_type(ImageViewType::Dim2d)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn dim2d_array_storage_view_of_dim3d_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let usage = ImageUsage {
+            storage: true,
+            ..ImageUsage::none()
+        };
+        let dimensions = ImageDimensions::Dim3d {
+            width: 16,
+            height: 16,
+            depth: 8,
+        };
+
+        let image = StorageImage::with_usage(
+            device.clone(),
+            dimensions,
+            Format::R8G8B8A8Unorm,
+            usage,
+            ImageCreateFlags {
+                array_2d_compatible: true,
+                ..ImageCreateFlags::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let view = ImageView::start(image.clone())
+            .with_type(ImageViewType::Dim2dArray)
+            .build()
+            .unwrap();
+        assert_eq!(view.array_layers(), 0..8);
+
+        let view = ImageView::start(image.clone())
+            .with_type(ImageViewType::Dim2dArray)
+            .with_array_layers(2..6)
+            .build()
+            .unwrap();
+        assert_eq!(view.array_layers(), 2..6);
+
+        match ImageView::start(image)
+            .with_type(ImageViewType::Dim2dArray)
+            .with_array_layers(0..9)
+            .build()
+        {
+            Err(ImageViewCreationError::ArrayLayersOutOfRange) => (),
+            _ => panic!(),
+        }
+
+        let image = StorageImage::with_usage(
+            device,
+            dimensions,
+            Format::R8G8B8A8Unorm,
+            usage,
+            ImageCreateFlags::none(),
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        match ImageView::start(image)
+            .with_type(ImageViewType::Dim2dArray)
+            .build()
+        {
+            Err(ImageViewCreationError::IncompatibleType {
+                image_type: ImageType::Dim3d,
+                view_type: ImageViewType::Dim2dArray,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn base_mip_level_view() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let (image, _) = ImmutableImage::uninitialized(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            MipmapsCount::Specific(6),
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            ImageLayout::ShaderReadOnlyOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let full = ImageView::new(image.clone()).unwrap();
+        assert_eq!(full.mip_levels(), 6);
+        assert_eq!(full.lod_range(), 0.0..=5.0);
+
+        let view = ImageView::start(image)
+            .with_mipmap_levels(2..6)
+            .build()
+            .unwrap();
+        assert_eq!(view.mip_levels(), 4);
+        assert_eq!(view.lod_range(), 0.0..=3.0);
+    }
+
+    #[test]
+    fn min_lod_view() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let (image, _) = ImmutableImage::uninitialized(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            MipmapsCount::Specific(6),
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            ImageLayout::ShaderReadOnlyOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let full = ImageView::new(image.clone()).unwrap();
+        assert_eq!(full.min_lod(), 0.0);
+
+        let res = ImageView::start(image.clone())
+            .with_mipmap_levels(2..6)
+            .with_min_lod(3.5)
+            .build();
+
+        // The extension isn't enabled on this device.
+        match res {
+            Err(ImageViewCreationError::MinLodExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn min_lod_view_enabled() {
+        let instance = instance!();
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+        let family = match physical.queue_families().find(|q| q.supports_graphics()) {
+            Some(q) => q,
+            None => return,
+        };
+
+        let extensions = DeviceExtensions {
+            ext_image_view_min_lod: true,
+            ..DeviceExtensions::none()
+        };
+        let features = Features {
+            min_lod: true,
+            ..Features::none()
+        };
+        if !DeviceExtensions::supported_by_device(physical).ext_image_view_min_lod
+            || !physical.supported_features().superset_of(&features)
+        {
+            return;
+        }
+
+        let (device, mut queues) =
+            Device::new(physical, &features, &extensions, Some((family, 0.5))).unwrap();
+        let queue = queues.next().unwrap();
+
+        let (image, _) = ImmutableImage::uninitialized(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            MipmapsCount::Specific(6),
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            ImageLayout::ShaderReadOnlyOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let view = ImageView::start(image.clone())
+            .with_mipmap_levels(2..6)
+            .with_min_lod(3.5)
+            .build()
+            .unwrap();
+        assert_eq!(view.min_lod(), 3.5);
+        assert_eq!(view.lod_range(), 1.5..=3.0);
+
+        match ImageView::start(image)
+            .with_mipmap_levels(2..6)
+            .with_min_lod(1.0)
+            .build()
+        {
+            Err(ImageViewCreationError::MinLodOutOfRange) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn stencil_aspect_view() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let features = Format::D24Unorm_S8Uint
+            .properties(device.physical_device())
+            .optimal_tiling_features;
+        if !(features.sampled_image && features.depth_stencil_attachment) {
+            return;
+        }
+
+        let image =
+            AttachmentImage::sampled(device.clone(), [32, 32], Format::D24Unorm_S8Uint).unwrap();
+
+        let stencil = ImageView::start(image.clone())
+            .with_aspect(ImageAspect {
+                stencil: true,
+                ..ImageAspect::none()
+            })
+            .build()
+            .unwrap();
+        assert!(stencil.samples_as_integer());
+
+        let depth = ImageView::start(image.clone())
+            .with_aspect(ImageAspect {
+                depth: true,
+                ..ImageAspect::none()
+            })
+            .build()
+            .unwrap();
+        assert!(!depth.samples_as_integer());
+
+        let linear = Sampler::simple_repeat_linear(device.clone());
+        assert!(!stencil.can_be_sampled(&linear));
+        assert!(depth.can_be_sampled(&linear));
+
+        let nearest = Sampler::unnormalized(
+            device,
+            Filter::Nearest,
+            UnnormalizedSamplerAddressMode::ClampToEdge,
+            UnnormalizedSamplerAddressMode::ClampToEdge,
+        )
+        .unwrap();
+        assert!(stencil.can_be_sampled(&nearest));
+
+        match ImageView::start(image)
+            .with_aspect(ImageAspect {
+                color: true,
+                ..ImageAspect::none()
+            })
+            .build()
+        {
+            Err(ImageViewCreationError::InvalidAspect) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn single_layer_view() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::new(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 4,
+            },
+            Format::R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let view = ImageView::layer(image.clone(), 2).unwrap();
+        assert_eq!(view.ty(), ImageViewType::Dim2d);
+        assert_eq!(view.array_layers(), 2..3);
+
+        match ImageView::layer(image.clone(), 4) {
+            Err(ImageViewCreationError::ArrayLayersOutOfRange) => (),
+            _ => panic!(),
+        }
+
+        match ImageView::layer(image, u32::MAX) {
+            Err(ImageViewCreationError::ArrayLayersOutOfRange) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn matches_framebuffer_attachment_info() {
+        let (device, _) = gfx_dev_and_queue!();
+        let usage = ImageUsage {
+            color_attachment: true,
+            sampled: true,
+            ..ImageUsage::none()
+        };
+        let image =
+            AttachmentImage::with_usage(device, [64, 32], Format::R8G8B8A8Unorm, usage).unwrap();
+        let view = ImageView::new(image).unwrap();
+
+        let info = FramebufferAttachmentImageInfo {
+            flags: ImageCreateFlags::none(),
+            usage,
+            width: 64,
+            height: 32,
+            layer_count: 1,
+            view_formats: vec![Format::R8G8B8A8Srgb, Format::R8G8B8A8Unorm],
+        };
+        assert!(view.matches_framebuffer_attachment_info(&info));
+
+        let wrong_size = FramebufferAttachmentImageInfo {
+            width: 32,
+            ..info.clone()
+        };
+        assert!(!view.matches_framebuffer_attachment_info(&wrong_size));
+
+        let wrong_layers = FramebufferAttachmentImageInfo {
+            layer_count: 2,
+            ..info.clone()
+        };
+        assert!(!view.matches_framebuffer_attachment_info(&wrong_layers));
+
+        let wrong_usage = FramebufferAttachmentImageInfo {
+            usage: ImageUsage {
+                color_attachment: true,
+                ..ImageUsage::none()
+            },
+            ..info.clone()
+        };
+        assert!(!view.matches_framebuffer_attachment_info(&wrong_usage));
+
+        let wrong_format = FramebufferAttachmentImageInfo {
+            view_formats: vec![Format::B8G8R8A8Unorm],
+            ..info
+        };
+        assert!(!view.matches_framebuffer_attachment_info(&wrong_format));
+    }
+
+    #[test]
+    fn matches_framebuffer_attachment_info_base_mip_level() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let usage = ImageUsage {
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        let (image, _) = ImmutableImage::uninitialized(
+            device,
+            ImageDimensions::Dim2d {
+                width: 64,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            MipmapsCount::Specific(2),
+            usage,
+            ImageCreateFlags::none(),
+            ImageLayout::ColorAttachmentOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+        let view = ImageView::start(image)
+            .with_mipmap_levels(1..2)
+            .build()
+            .unwrap();
+
+        let info = FramebufferAttachmentImageInfo {
+            flags: ImageCreateFlags::none(),
+            usage,
+            width: 32,
+            height: 16,
+            layer_count: 1,
+            view_formats: vec![Format::R8G8B8A8Unorm],
+        };
+        assert!(view.matches_framebuffer_attachment_info(&info));
+
+        let image_size = FramebufferAttachmentImageInfo {
+            width: 64,
+            height: 32,
+            ..info
+        };
+        assert!(!view.matches_framebuffer_attachment_info(&image_size));
+    }
+}
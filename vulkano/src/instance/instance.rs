@@ -15,6 +15,8 @@ use crate::instance::loader::FunctionPointers;
 use crate::instance::loader::Loader;
 use crate::instance::loader::LoadingError;
 use crate::instance::{InstanceExtensions, RawInstanceExtensions};
+use crate::memory::MemoryPropertyFlags;
+use crate::render_pass::ResolveModes;
 use crate::sync::PipelineStage;
 use crate::version::Version;
 use crate::vk;
@@ -23,6 +25,7 @@ use crate::OomError;
 use crate::VulkanObject;
 use smallvec::SmallVec;
 use std::borrow::Cow;
+use std::cmp;
 use std::error;
 use std::ffi::CStr;
 use std::ffi::CString;
@@ -98,6 +101,7 @@ pub struct Instance {
     vk: vk::InstancePointers,
     extensions: RawInstanceExtensions,
     layers: SmallVec<[CString; 16]>,
+    api_version: Version,
     function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader + Send + Sync>>>,
 }
 
@@ -257,6 +261,16 @@ impl Instance {
             .map(|extension| extension.as_ptr())
             .collect::<SmallVec<[_; 32]>>();
 
+        // Vulkan 1.0 is used if the application doesn't request a version.
+        let api_version = app_infos
+            .as_ref()
+            .map(|app| Version::from_vulkan_version(app.apiVersion))
+            .unwrap_or(Version {
+                major: 1,
+                minor: 0,
+                patch: 0,
+            });
+
         // Creating the Vulkan instance.
         let instance = unsafe {
             let mut output = MaybeUninit::uninit();
@@ -307,7 +321,7 @@ impl Instance {
             .iter()
             .any(|v| *v == vk_khr_get_physical_device_properties2)
         {
-            Instance::init_physical_devices2(&vk, physical_devices, &extensions)
+            Instance::init_physical_devices2(&vk, physical_devices, &extensions, api_version)?
         } else {
             Instance::init_physical_devices(&vk, physical_devices)
         };
@@ -319,6 +333,7 @@ impl Instance {
             vk,
             extensions,
             layers,
+            api_version,
             function_pointers,
         }))
     }
@@ -377,16 +392,70 @@ impl Instance {
         vk: &vk::InstancePointers,
         physical_devices: Vec<vk::PhysicalDevice>,
         extensions: &RawInstanceExtensions,
-    ) -> Vec<PhysicalDeviceInfos> {
+        instance_api_version: Version,
+    ) -> Result<Vec<PhysicalDeviceInfos>, InstanceCreationError> {
         let mut output = Vec::with_capacity(physical_devices.len());
 
         for device in physical_devices.into_iter() {
             let mut extended_properties = PhysicalDeviceExtendedProperties::empty();
 
+            // Depth/stencil resolve properties can only be queried if the device supports
+            // `VK_KHR_depth_stencil_resolve` or if both the device and the instance use Vulkan 1.2.
+            let supports_depth_stencil_resolve = unsafe {
+                let mut output = MaybeUninit::uninit();
+                vk.GetPhysicalDeviceProperties(device, output.as_mut_ptr());
+                let api_version = cmp::min(
+                    instance_api_version,
+                    Version::from_vulkan_version(output.assume_init().apiVersion),
+                );
+
+                let mut num = 0;
+                check_errors(vk.EnumerateDeviceExtensionProperties(
+                    device,
+                    ptr::null(),
+                    &mut num,
+                    ptr::null_mut(),
+                ))?;
+                let mut device_extensions: Vec<vk::ExtensionProperties> =
+                    Vec::with_capacity(num as usize);
+                check_errors(vk.EnumerateDeviceExtensionProperties(
+                    device,
+                    ptr::null(),
+                    &mut num,
+                    device_extensions.as_mut_ptr(),
+                ))?;
+                device_extensions.set_len(num as usize);
+
+                api_version
+                    >= (Version {
+                        major: 1,
+                        minor: 2,
+                        patch: 0,
+                    })
+                    || device_extensions.iter().any(|ext| {
+                        CStr::from_ptr(ext.extensionName.as_ptr()).to_bytes()
+                            == b"VK_KHR_depth_stencil_resolve"
+                    })
+            };
+
             let properties: vk::PhysicalDeviceProperties = unsafe {
+                let mut depth_stencil_resolve_properties =
+                    vk::PhysicalDeviceDepthStencilResolveProperties {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DEPTH_STENCIL_RESOLVE_PROPERTIES,
+                        pNext: ptr::null_mut(),
+                        supportedDepthResolveModes: 0,
+                        supportedStencilResolveModes: 0,
+                        independentResolveNone: 0,
+                        independentResolve: 0,
+                    };
+
                 let mut subgroup_properties = vk::PhysicalDeviceSubgroupProperties {
                     sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES,
-                    pNext: ptr::null_mut(),
+                    pNext: if supports_depth_stencil_resolve {
+                        &mut depth_stencil_resolve_properties as *mut _ as *const _
+                    } else {
+                        ptr::null()
+                    },
                     subgroupSize: 0,
                     supportedStages: 0,
                     supportedOperations: 0,
@@ -403,6 +472,24 @@ impl Instance {
 
                 extended_properties = PhysicalDeviceExtendedProperties {
                     subgroup_size: Some(subgroup_properties.subgroupSize),
+                    supported_depth_resolve_modes: if supports_depth_stencil_resolve {
+                        Some(
+                            depth_stencil_resolve_properties
+                                .supportedDepthResolveModes
+                                .into(),
+                        )
+                    } else {
+                        None
+                    },
+                    supported_stencil_resolve_modes: if supports_depth_stencil_resolve {
+                        Some(
+                            depth_stencil_resolve_properties
+                                .supportedStencilResolveModes
+                                .into(),
+                        )
+                    } else {
+                        None
+                    },
 
                     ..extended_properties
                 };
@@ -459,7 +546,7 @@ impl Instance {
                 available_features,
             });
         }
-        output
+        Ok(output)
     }
 
     /*/// Same as `new`, but provides an allocator that will be used by the Vulkan library whenever
@@ -476,6 +563,15 @@ impl Instance {
         &self.vk
     }
 
+    /// Returns the version of Vulkan that the instance was created for.
+    ///
+    /// A device can't use the functionality of a higher version than this one, even if its
+    /// physical device supports it. See `Device::api_version`.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
     /// Returns the list of extensions that have been loaded.
     ///
     /// This list is equal to what was passed to `Instance::new()`.
@@ -741,12 +837,16 @@ struct PhysicalDeviceInfos {
 /// TODO: Only a small subset of available properties(https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceProperties2.html) is implemented at this moment.
 pub struct PhysicalDeviceExtendedProperties {
     subgroup_size: Option<u32>,
+    supported_depth_resolve_modes: Option<ResolveModes>,
+    supported_stencil_resolve_modes: Option<ResolveModes>,
 }
 
 impl PhysicalDeviceExtendedProperties {
     fn empty() -> Self {
         Self {
             subgroup_size: None,
+            supported_depth_resolve_modes: None,
+            supported_stencil_resolve_modes: None,
         }
     }
 
@@ -757,6 +857,26 @@ impl PhysicalDeviceExtendedProperties {
     pub fn subgroup_size(&self) -> &Option<u32> {
         &self.subgroup_size
     }
+
+    /// The modes that can be used to resolve the depth aspect of a multisampled attachment.
+    ///
+    /// Only available if the device supports `VK_KHR_depth_stencil_resolve` or Vulkan 1.2.
+    ///
+    /// See https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceDepthStencilResolveProperties.html for details
+    #[inline]
+    pub fn supported_depth_resolve_modes(&self) -> &Option<ResolveModes> {
+        &self.supported_depth_resolve_modes
+    }
+
+    /// The modes that can be used to resolve the stencil aspect of a multisampled attachment.
+    ///
+    /// Only available if the device supports `VK_KHR_depth_stencil_resolve` or Vulkan 1.2.
+    ///
+    /// See https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceDepthStencilResolveProperties.html for details
+    #[inline]
+    pub fn supported_stencil_resolve_modes(&self) -> &Option<ResolveModes> {
+        &self.supported_stencil_resolve_modes
+    }
 }
 
 /// Represents one of the available devices on this machine.
@@ -1305,6 +1425,12 @@ impl<'a> MemoryType<'a> {
         (self.flags() & vk::MEMORY_PROPERTY_LAZILY_ALLOCATED_BIT) != 0
     }
 
+    /// Returns all the properties of this memory type.
+    #[inline]
+    pub fn property_flags(&self) -> MemoryPropertyFlags {
+        self.flags().into()
+    }
+
     /// Internal utility function that returns the flags of this queue family.
     #[inline]
     fn flags(&self) -> u32 {
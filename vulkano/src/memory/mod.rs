@@ -142,6 +142,79 @@ impl MemoryRequirements {
     }
 }
 
+/// Properties of a memory type.
+///
+/// Used to express which properties a memory type must or should have when choosing where to
+/// allocate memory for a resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct MemoryPropertyFlags {
+    pub device_local: bool,
+    pub host_visible: bool,
+    pub host_coherent: bool,
+    pub host_cached: bool,
+    pub lazily_allocated: bool,
+}
+
+impl MemoryPropertyFlags {
+    /// Builds a `MemoryPropertyFlags` with all values set to false. Useful as a default value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use vulkano::memory::MemoryPropertyFlags;
+    ///
+    /// let _props = MemoryPropertyFlags {
+    ///     device_local: true,
+    ///     .. MemoryPropertyFlags::none()
+    /// };
+    /// ```
+    #[inline]
+    pub fn none() -> MemoryPropertyFlags {
+        MemoryPropertyFlags {
+            device_local: false,
+            host_visible: false,
+            host_coherent: false,
+            host_cached: false,
+            lazily_allocated: false,
+        }
+    }
+
+    /// Returns true if all the properties of `other` are also in `self`.
+    #[inline]
+    pub fn contains(&self, other: &MemoryPropertyFlags) -> bool {
+        (self.device_local || !other.device_local)
+            && (self.host_visible || !other.host_visible)
+            && (self.host_coherent || !other.host_coherent)
+            && (self.host_cached || !other.host_cached)
+            && (self.lazily_allocated || !other.lazily_allocated)
+    }
+
+    /// Returns the union of `self` and `other`.
+    #[inline]
+    pub fn union(&self, other: &MemoryPropertyFlags) -> MemoryPropertyFlags {
+        MemoryPropertyFlags {
+            device_local: self.device_local || other.device_local,
+            host_visible: self.host_visible || other.host_visible,
+            host_coherent: self.host_coherent || other.host_coherent,
+            host_cached: self.host_cached || other.host_cached,
+            lazily_allocated: self.lazily_allocated || other.lazily_allocated,
+        }
+    }
+}
+
+impl From<vk::MemoryPropertyFlags> for MemoryPropertyFlags {
+    #[inline]
+    fn from(val: vk::MemoryPropertyFlags) -> MemoryPropertyFlags {
+        MemoryPropertyFlags {
+            device_local: (val & vk::MEMORY_PROPERTY_DEVICE_LOCAL_BIT) != 0,
+            host_visible: (val & vk::MEMORY_PROPERTY_HOST_VISIBLE_BIT) != 0,
+            host_coherent: (val & vk::MEMORY_PROPERTY_HOST_COHERENT_BIT) != 0,
+            host_cached: (val & vk::MEMORY_PROPERTY_HOST_CACHED_BIT) != 0,
+            lazily_allocated: (val & vk::MEMORY_PROPERTY_LAZILY_ALLOCATED_BIT) != 0,
+        }
+    }
+}
+
 /// Indicates whether we want to allocate memory for a specific resource, or in a generic way.
 ///
 /// Using dedicated allocations can yield better performance, but requires the
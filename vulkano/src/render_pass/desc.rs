@@ -289,6 +289,66 @@ pub enum StoreOp {
     DontCare = vk::ATTACHMENT_STORE_OP_DONT_CARE,
 }
 
+/// Describes how the samples of a multisampled depth or stencil attachment are combined when it
+/// is resolved into a single-sampled attachment.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ResolveMode {
+    /// The value of sample 0 is used.
+    SampleZero = vk::RESOLVE_MODE_SAMPLE_ZERO_BIT,
+    /// The average of all the samples is used.
+    Average = vk::RESOLVE_MODE_AVERAGE_BIT,
+    /// The smallest of all the samples is used.
+    Min = vk::RESOLVE_MODE_MIN_BIT,
+    /// The largest of all the samples is used.
+    Max = vk::RESOLVE_MODE_MAX_BIT,
+}
+
+/// A set of `ResolveMode`s, for example the modes supported by a device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ResolveModes {
+    pub sample_zero: bool,
+    pub average: bool,
+    pub min: bool,
+    pub max: bool,
+}
+
+impl ResolveModes {
+    /// Builds a `ResolveModes` with all values set to false.
+    #[inline]
+    pub fn none() -> ResolveModes {
+        ResolveModes {
+            sample_zero: false,
+            average: false,
+            min: false,
+            max: false,
+        }
+    }
+
+    /// Returns true if `mode` is in the set.
+    #[inline]
+    pub fn contains(&self, mode: ResolveMode) -> bool {
+        match mode {
+            ResolveMode::SampleZero => self.sample_zero,
+            ResolveMode::Average => self.average,
+            ResolveMode::Min => self.min,
+            ResolveMode::Max => self.max,
+        }
+    }
+}
+
+impl From<vk::ResolveModeFlags> for ResolveModes {
+    #[inline]
+    fn from(val: vk::ResolveModeFlags) -> ResolveModes {
+        ResolveModes {
+            sample_zero: (val & vk::RESOLVE_MODE_SAMPLE_ZERO_BIT) != 0,
+            average: (val & vk::RESOLVE_MODE_AVERAGE_BIT) != 0,
+            min: (val & vk::RESOLVE_MODE_MIN_BIT) != 0,
+            max: (val & vk::RESOLVE_MODE_MAX_BIT) != 0,
+        }
+    }
+}
+
 /// Describes what the implementation should do with an attachment at the start of the subpass.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u32)]
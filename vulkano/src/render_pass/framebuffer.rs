@@ -10,7 +10,10 @@
 use crate::check_errors;
 use crate::device::Device;
 use crate::device::DeviceOwned;
+use crate::format::Format;
 use crate::image::view::ImageViewAbstract;
+use crate::image::ImageCreateFlags;
+use crate::image::ImageUsage;
 use crate::render_pass::ensure_image_view_compatible;
 use crate::render_pass::AttachmentsList;
 use crate::render_pass::IncompatibleRenderPassAttachmentError;
@@ -215,13 +218,14 @@ where
                     || view_dimensions[1] < current[1]
                     || view_dimensions[2] < current[2]
                 {
-                    return Err(FramebufferCreationError::AttachmentDimensionsIncompatible {
-                        expected: current,
-                        obtained: view_dimensions,
+                    return Err(FramebufferCreationError::AttachmentTooSmall {
+                        index: self.raw_ids.len(),
+                        attachment_dims: view_dimensions,
+                        required: current,
                     });
                 }
 
-                FramebufferBuilderDimensions::Specific(view_dimensions)
+                FramebufferBuilderDimensions::Specific(current)
             }
         };
 
@@ -401,6 +405,44 @@ pub unsafe trait FramebufferAbstract {
     fn layers(&self) -> u32 {
         self.dimensions()[2]
     }
+
+    /// Checks that a render area starting at `offset` and of size `extent` fits within every
+    /// attachment of the framebuffer, and within the framebuffer itself. The size of an attachment
+    /// is the size of the base mipmap level of its view.
+    ///
+    /// The attachments are checked in order, so that the error reports the first attachment that
+    /// is too small, for example a half-resolution buffer.
+    fn check_render_area(&self, offset: [u32; 2], extent: [u32; 2]) -> Result<(), RenderAreaError> {
+        for index in 0.. {
+            let view = match self.attached_image_view(index) {
+                Some(v) => v,
+                None => break,
+            };
+
+            let dimensions = view
+                .image()
+                .dimensions()
+                .mipmap_dimensions(view.mipmap_levels().start)
+                .unwrap();
+            if !dimensions.contains_render_area(offset, extent) {
+                return Err(RenderAreaError::AttachmentTooSmall {
+                    index,
+                    attachment_dims: [dimensions.width(), dimensions.height()],
+                });
+            }
+        }
+
+        let [width, height, _] = self.dimensions();
+        if offset[0] as u64 + extent[0] as u64 > width as u64
+            || offset[1] as u64 + extent[1] as u64 > height as u64
+        {
+            return Err(RenderAreaError::OutOfFramebuffer {
+                framebuffer_dims: [width, height],
+            });
+        }
+
+        Ok(())
+    }
 }
 
 unsafe impl<T> FramebufferAbstract for T
@@ -500,6 +542,15 @@ pub enum FramebufferCreationError {
         /// Attachment dimensions.
         obtained: [u32; 3],
     },
+    /// One of the attachments is smaller than the dimensions of the framebuffer.
+    AttachmentTooSmall {
+        /// Index of the attachment.
+        index: usize,
+        /// Dimensions of the attachment.
+        attachment_dims: [u32; 3],
+        /// Dimensions of the framebuffer.
+        required: [u32; 3],
+    },
     /// The number of attachments doesn't match the number expected by the render pass.
     AttachmentsCountMismatch {
         /// Expected number of attachments.
@@ -545,6 +596,9 @@ impl fmt::Display for FramebufferCreationError {
                 FramebufferCreationError::AttachmentDimensionsIncompatible { .. } => {
                     "the attachment has a size that isn't compatible with the framebuffer dimensions"
                 }
+                FramebufferCreationError::AttachmentTooSmall { .. } => {
+                    "one of the attachments is smaller than the dimensions of the framebuffer"
+                }
                 FramebufferCreationError::AttachmentsCountMismatch { .. } => {
                     "the number of attachments doesn't match the number expected by the render pass"
                 }
@@ -566,13 +620,80 @@ impl From<Error> for FramebufferCreationError {
     }
 }
 
+/// Description of an image that will be attached to an imageless framebuffer.
+///
+/// With the `khr_imageless_framebuffer` extension, a framebuffer is created from these
+/// placeholders instead of actual image views, and the views are only provided when beginning a
+/// render pass. Each view must then match the corresponding placeholder, see
+/// `ImageViewAbstract::matches_framebuffer_attachment_info`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FramebufferAttachmentImageInfo {
+    /// The flags that the image of the view must have been created with.
+    pub flags: ImageCreateFlags,
+    /// The usage that the image of the view must have been created with.
+    pub usage: ImageUsage,
+    /// The width of the base mipmap level of the view.
+    pub width: u32,
+    /// The height of the base mipmap level of the view.
+    pub height: u32,
+    /// The number of array layers of the view.
+    pub layer_count: u32,
+    /// The formats that the view is allowed to have.
+    pub view_formats: Vec<Format>,
+}
+
+/// Error that can happen when checking a render area against a framebuffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RenderAreaError {
+    /// The render area doesn't fit within one of the attachments.
+    AttachmentTooSmall {
+        /// Index of the attachment.
+        index: usize,
+        /// Width and height of the attachment.
+        attachment_dims: [u32; 2],
+    },
+    /// The render area doesn't fit within the dimensions of the framebuffer.
+    OutOfFramebuffer {
+        /// Width and height of the framebuffer.
+        framebuffer_dims: [u32; 2],
+    },
+}
+
+impl error::Error for RenderAreaError {}
+
+impl fmt::Display for RenderAreaError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                RenderAreaError::AttachmentTooSmall { .. } => {
+                    "the render area doesn't fit within one of the attachments"
+                }
+                RenderAreaError::OutOfFramebuffer { .. } => {
+                    "the render area doesn't fit within the framebuffer"
+                }
+            }
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
     use crate::image::attachment::AttachmentImage;
     use crate::image::view::ImageView;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageLayout;
+    use crate::image::ImageUsage;
+    use crate::image::ImmutableImage;
+    use crate::image::MipmapsCount;
     use crate::render_pass::Framebuffer;
+    use crate::render_pass::FramebufferAbstract;
     use crate::render_pass::FramebufferCreationError;
+    use crate::render_pass::RenderAreaError;
     use crate::render_pass::RenderPass;
     use std::sync::Arc;
 
@@ -718,12 +839,70 @@ mod tests {
         .unwrap();
 
         match Framebuffer::with_dimensions(render_pass, [600, 600, 1]).add(view) {
-            Err(FramebufferCreationError::AttachmentDimensionsIncompatible {
-                expected,
-                obtained,
+            Err(FramebufferCreationError::AttachmentTooSmall {
+                index,
+                attachment_dims,
+                required,
             }) => {
-                assert_eq!(expected, [600, 600, 1]);
-                assert_eq!(obtained, [512, 700, 1]);
+                assert_eq!(index, 0);
+                assert_eq!(attachment_dims, [512, 700, 1]);
+                assert_eq!(required, [600, 600, 1]);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn depth_attachment_smaller_than_specified() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = Arc::new(
+            single_pass_renderpass!(device.clone(),
+                attachments: {
+                    color: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    },
+                    depth: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::D16Unorm,
+                        samples: 1,
+                    }
+                },
+                pass: {
+                    color: [color],
+                    depth_stencil: {depth}
+                }
+            )
+            .unwrap(),
+        );
+
+        let color = ImageView::new(
+            AttachmentImage::new(device.clone(), [800, 600], Format::R8G8B8A8Unorm).unwrap(),
+        )
+        .unwrap();
+        // A depth buffer that was not recreated after a resize.
+        let depth = ImageView::new(
+            AttachmentImage::new(device.clone(), [640, 480], Format::D16Unorm).unwrap(),
+        )
+        .unwrap();
+
+        match Framebuffer::with_dimensions(render_pass, [800, 600, 1])
+            .add(color)
+            .unwrap()
+            .add(depth)
+        {
+            Err(FramebufferCreationError::AttachmentTooSmall {
+                index,
+                attachment_dims,
+                required,
+            }) => {
+                assert_eq!(index, 1);
+                assert_eq!(attachment_dims, [640, 480, 1]);
+                assert_eq!(required, [800, 600, 1]);
             }
             _ => panic!(),
         }
@@ -829,6 +1008,126 @@ mod tests {
         }
     }
 
+    #[test]
+    fn render_area_larger_than_attachment() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = Arc::new(
+            single_pass_renderpass!(device.clone(),
+                attachments: {
+                    a: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    },
+                    b: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    }
+                },
+                pass: {
+                    color: [a, b],
+                    depth_stencil: {}
+                }
+            )
+            .unwrap(),
+        );
+
+        let a = ImageView::new(
+            AttachmentImage::new(device.clone(), [512, 512], Format::R8G8B8A8Unorm).unwrap(),
+        )
+        .unwrap();
+        let b = ImageView::new(
+            AttachmentImage::new(device.clone(), [256, 256], Format::R8G8B8A8Unorm).unwrap(),
+        )
+        .unwrap();
+
+        let fb = Framebuffer::with_intersecting_dimensions(render_pass)
+            .add(a)
+            .unwrap()
+            .add(b)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        fb.check_render_area([0, 0], [256, 256]).unwrap();
+        fb.check_render_area([128, 128], [128, 128]).unwrap();
+
+        match fb.check_render_area([0, 0], [512, 512]) {
+            Err(RenderAreaError::AttachmentTooSmall {
+                index: 1,
+                attachment_dims: [256, 256],
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn render_area_base_mip_level() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let render_pass = Arc::new(
+            single_pass_renderpass!(device.clone(),
+                attachments: {
+                    a: {
+                        load: Clear,
+                        store: DontCare,
+                        format: Format::R8G8B8A8Unorm,
+                        samples: 1,
+                    }
+                },
+                pass: {
+                    color: [a],
+                    depth_stencil: {}
+                }
+            )
+            .unwrap(),
+        );
+
+        let (image, _) = ImmutableImage::uninitialized(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 512,
+                height: 512,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8Unorm,
+            MipmapsCount::Specific(2),
+            ImageUsage {
+                color_attachment: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            ImageLayout::ColorAttachmentOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+        let view = ImageView::start(image)
+            .with_mipmap_levels(1..2)
+            .build()
+            .unwrap();
+
+        let fb = Framebuffer::with_dimensions(render_pass, [256, 256, 1])
+            .add(view)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        fb.check_render_area([0, 0], [256, 256]).unwrap();
+
+        // The view starts at the second level, which is half the size of the image.
+        match fb.check_render_area([0, 0], [512, 512]) {
+            Err(RenderAreaError::AttachmentTooSmall {
+                index: 0,
+                attachment_dims: [256, 256],
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn not_enough_attachments() {
         let (device, _) = gfx_dev_and_queue!();
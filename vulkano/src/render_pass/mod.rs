@@ -31,14 +31,18 @@ pub use self::compat_atch::IncompatibleRenderPassAttachmentError;
 pub use self::desc::AttachmentDesc;
 pub use self::desc::LoadOp;
 pub use self::desc::RenderPassDesc;
+pub use self::desc::ResolveMode;
+pub use self::desc::ResolveModes;
 pub use self::desc::StoreOp;
 pub use self::desc::SubpassDependencyDesc;
 pub use self::desc::SubpassDesc;
 pub use self::framebuffer::Framebuffer;
 pub use self::framebuffer::FramebufferAbstract;
+pub use self::framebuffer::FramebufferAttachmentImageInfo;
 pub use self::framebuffer::FramebufferBuilder;
 pub use self::framebuffer::FramebufferCreationError;
 pub use self::framebuffer::FramebufferSys;
+pub use self::framebuffer::RenderAreaError;
 pub use self::render_pass::RenderPass;
 pub use self::render_pass::RenderPassCreationError;
 pub use self::render_pass::RenderPassSys;
@@ -111,6 +111,18 @@ impl RenderPass {
     ) -> Result<RenderPass, RenderPassCreationError> {
         let vk = device.pointers();
 
+        for pass in description.subpasses() {
+            for &(attachment, _) in &pass.resolve_attachments {
+                let format = match description.attachments().get(attachment) {
+                    Some(desc) => desc.format,
+                    None => continue,
+                };
+                if !format.supports_resolve(&device, false) {
+                    return Err(RenderPassCreationError::ResolveFormatNotSupported { attachment });
+                }
+            }
+        }
+
         // If the first use of an attachment in this render pass is as an input attachment, and
         // the attachment is not also used as a color or depth/stencil attachment in the same
         // subpass, then loadOp must not be VK_ATTACHMENT_LOAD_OP_CLEAR
@@ -521,6 +533,11 @@ pub enum RenderPassCreationError {
     OomError(OomError),
     /// The maximum number of color attachments has been exceeded.
     ColorAttachmentsLimitExceeded,
+    /// The format of a resolve attachment can't be used as a resolve destination.
+    ResolveFormatNotSupported {
+        /// Index of the attachment.
+        attachment: usize,
+    },
 }
 
 impl error::Error for RenderPassCreationError {
@@ -544,6 +561,9 @@ impl fmt::Display for RenderPassCreationError {
                 RenderPassCreationError::ColorAttachmentsLimitExceeded => {
                     "the maximum number of color attachments has been exceeded"
                 }
+                RenderPassCreationError::ResolveFormatNotSupported { .. } => {
+                    "the format of a resolve attachment can't be used as a resolve destination"
+                }
             }
         )
     }
@@ -800,6 +820,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn resolve_format_not_supported() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // A depth format can never be used as a color attachment.
+        let rp = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 4, },
+                b: { load: DontCare, store: Store, format: Format::D16Unorm, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {},
+                resolve: [b],
+            }
+        };
+
+        match rp {
+            Err(RenderPassCreationError::ResolveFormatNotSupported { attachment: 1 }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn non_zero_granularity() {
         let (device, _) = gfx_dev_and_queue!();
@@ -87,6 +87,7 @@ pub struct Sampler {
     usable_with_float_formats: bool,
     usable_with_int_formats: bool,
     usable_with_swizzling: bool,
+    linear_filtering: bool,
 }
 
 impl Sampler {
@@ -362,6 +363,7 @@ impl Sampler {
         Ok(Arc::new(Sampler {
             sampler: sampler,
             device: device.clone(),
+            linear_filtering: mag_filter == Filter::Linear || min_filter == Filter::Linear,
             compare_mode: compare.is_some(),
             unnormalized: false,
             usable_with_float_formats: match border_color {
@@ -455,6 +457,7 @@ impl Sampler {
             device: device.clone(),
             compare_mode: false,
             unnormalized: true,
+            linear_filtering: filter == Filter::Linear,
             usable_with_float_formats: match border_color {
                 Some(BorderColor::FloatTransparentBlack) => true,
                 Some(BorderColor::FloatOpaqueBlack) => true,
@@ -509,6 +512,14 @@ impl Sampler {
     pub fn usable_with_swizzling(&self) -> bool {
         self.usable_with_swizzling
     }
+
+    /// Returns true if the sampler uses linear filtering for magnification or minification.
+    ///
+    /// Such a sampler can't be used with image views whose samples are integers.
+    #[inline]
+    pub fn uses_linear_filtering(&self) -> bool {
+        self.linear_filtering
+    }
 }
 
 unsafe impl DeviceOwned for Sampler {
@@ -645,14 +645,14 @@ impl<W> Swapchain<W> {
                 };
 
                 let img =
-                    UnsafeImage::from_raw(device.clone(), image, usage, format, flags, dims, 1, 1);
+                    UnsafeImage::from_raw(device.clone(), image, usage, format, flags, dims, 1, 1)?;
 
-                ImageEntry {
+                Ok(ImageEntry {
                     image: img,
                     undefined_layout: AtomicBool::new(true),
-                }
+                })
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, OomError>>()?;
 
         let fullscreen_exclusive_held = old_swapchain
             .as_ref()
@@ -748,6 +748,14 @@ impl<W> Swapchain<W> {
         self.layers
     }
 
+    /// Returns the usage of the images of the swapchain.
+    ///
+    /// See the documentation of `Swapchain::new`.
+    #[inline]
+    pub fn usage(&self) -> ImageUsage {
+        self.usage
+    }
+
     /// Returns the transform that was passed when creating the swapchain.
     ///
     /// See the documentation of `Swapchain::new`.
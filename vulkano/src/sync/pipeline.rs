@@ -104,7 +104,7 @@ pipeline_stages! {
 
 macro_rules! access_flags {
     ($($elem:ident => $val:expr,)+) => (
-        #[derive(Debug, Copy, Clone)]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
         #[allow(missing_docs)]
         pub struct AccessFlagBits {
             $(
@@ -9,6 +9,23 @@
 
 #![cfg(test)]
 
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::instance::MemoryType;
+use crate::memory::pool::AllocFromRequirementsFilter;
+use crate::memory::pool::AllocLayout;
+use crate::memory::pool::MappingRequirement;
+use crate::memory::pool::MemoryPool;
+use crate::memory::pool::PotentialDedicatedAllocation;
+use crate::memory::pool::StdMemoryPool;
+use crate::memory::pool::StdMemoryPoolAlloc;
+use crate::memory::DedicatedAlloc;
+use crate::memory::DeviceMemoryAllocError;
+use crate::memory::MemoryRequirements;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
 /// Creates an instance or returns if initialization fails.
 macro_rules! instance {
     () => {{
@@ -93,3 +110,61 @@ macro_rules! assert_should_panic {
         }
     }};
 }
+
+/// Memory pool that counts the allocations made through it.
+pub(crate) struct CountingPool {
+    pub(crate) inner: Arc<StdMemoryPool>,
+    pub(crate) allocations: AtomicUsize,
+}
+
+unsafe impl DeviceOwned for CountingPool {
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+unsafe impl MemoryPool for CountingPool {
+    type Alloc = StdMemoryPoolAlloc;
+
+    fn alloc_generic(
+        &self,
+        ty: MemoryType,
+        size: usize,
+        alignment: usize,
+        layout: AllocLayout,
+        map: MappingRequirement,
+    ) -> Result<StdMemoryPoolAlloc, DeviceMemoryAllocError> {
+        self.allocations.fetch_add(1, Ordering::SeqCst);
+        self.inner.alloc_generic(ty, size, alignment, layout, map)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn alloc_generic_with_exportable_fd(
+        &self,
+        ty: MemoryType,
+        size: usize,
+        alignment: usize,
+        layout: AllocLayout,
+        map: MappingRequirement,
+    ) -> Result<StdMemoryPoolAlloc, DeviceMemoryAllocError> {
+        self.allocations.fetch_add(1, Ordering::SeqCst);
+        self.inner
+            .alloc_generic_with_exportable_fd(ty, size, alignment, layout, map)
+    }
+
+    fn alloc_from_requirements<F>(
+        &self,
+        requirements: &MemoryRequirements,
+        layout: AllocLayout,
+        map: MappingRequirement,
+        dedicated: DedicatedAlloc,
+        filter: F,
+    ) -> Result<PotentialDedicatedAllocation<StdMemoryPoolAlloc>, DeviceMemoryAllocError>
+    where
+        F: FnMut(MemoryType) -> AllocFromRequirementsFilter,
+    {
+        self.allocations.fetch_add(1, Ordering::SeqCst);
+        self.inner
+            .alloc_from_requirements(requirements, layout, map, dedicated, filter)
+    }
+}